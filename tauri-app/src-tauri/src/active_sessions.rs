@@ -0,0 +1,206 @@
+// tauri-app/src-tauri/src/active_sessions.rs
+
+use crate::error::AppError;
+use crate::pricing::resolve_profile;
+use crate::session_events::{load_session_event_settings, recent_sessions, SessionState};
+use crate::settings::frontend_pricing_provider;
+use crate::transcript_archive::{all_transcripts, TranscriptFile};
+use crate::transcripts::{SessionUsage, UsageAccumulator};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+/// A session Claude Code is still writing to, with totals so far
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSession {
+    pub session_id: String,
+    pub project: String,
+    /// Project path from history.jsonl, else the encoded transcript folder
+    pub project_path: String,
+    pub started_at: i64,
+    pub last_activity: i64,
+    pub elapsed_seconds: i64,
+    pub state: SessionState,
+    pub message_count: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+    /// Model with the highest cost so far
+    pub model: Option<String>,
+}
+
+/// Usage of a transcript parsed up to `offset`; transcripts are append-only, so
+/// only lines written after it need reading next time
+struct CachedUsage {
+    offset: u64,
+    /// Pricing profile the costs were computed with, serialized so edits to
+    /// the profile invalidate the entry as well as switching profiles
+    pricing: String,
+    usage: UsageAccumulator,
+}
+
+static USAGE_CACHE: Mutex<Option<HashMap<PathBuf, CachedUsage>>> = Mutex::new(None);
+
+fn modified_millis(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// Add the lines written after `offset`, returning the offset after the last
+/// complete line (a line still being written is picked up next time)
+fn read_appended(
+    path: &Path,
+    mut offset: u64,
+    usage: &mut UsageAccumulator,
+) -> std::io::Result<u64> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 || line.last() != Some(&b'\n') {
+            return Ok(offset);
+        }
+        offset += read as u64;
+        if let Ok(entry) = serde_json::from_slice(&line) {
+            usage.add(entry);
+        }
+    }
+}
+
+fn cached_usage(
+    transcript: &TranscriptFile,
+    app: &AppHandle,
+    provider: &str,
+) -> Option<SessionUsage> {
+    let len = std::fs::metadata(&transcript.path).ok()?.len();
+    let profile = resolve_profile(app, provider);
+    let pricing = serde_json::to_string(&profile).ok()?;
+
+    // Taken out of the cache so the lock isn't held while reading the file
+    let cached = USAGE_CACHE
+        .lock()
+        .ok()?
+        .get_or_insert_with(HashMap::new)
+        .remove(&transcript.path);
+    let mut cached = match cached {
+        Some(cached) if cached.pricing == pricing && cached.offset <= len => cached,
+        // New, repriced or rewritten transcript: start over
+        _ => CachedUsage {
+            offset: 0,
+            pricing,
+            usage: UsageAccumulator::new(transcript, profile),
+        },
+    };
+    if cached.offset < len {
+        cached.offset = read_appended(&transcript.path, cached.offset, &mut cached.usage).ok()?;
+    }
+
+    let usage = cached.usage.usage();
+    USAGE_CACHE
+        .lock()
+        .ok()?
+        .get_or_insert_with(HashMap::new)
+        .insert(transcript.path.clone(), cached);
+    Some(usage)
+}
+
+/// Sessions with a history entry or transcript write within the session end
+/// window, most recently active first
+fn collect_active_sessions(app: &AppHandle) -> Vec<ActiveSession> {
+    let settings = load_session_event_settings(app);
+    let provider = frontend_pricing_provider(app);
+    let now = chrono::Utc::now().timestamp_millis();
+    let since = now - settings.end_after_minutes.max(1) as i64 * 60_000;
+
+    // session id -> (project path, first, last activity in ms)
+    let mut candidates: HashMap<String, (Option<String>, i64, i64)> = HashMap::new();
+    for (session_id, project_path, first, last) in recent_sessions(since) {
+        candidates.insert(session_id, (Some(project_path), first, last));
+    }
+    let transcripts: HashMap<String, TranscriptFile> = all_transcripts()
+        .into_iter()
+        .filter(|t| !t.archived)
+        .map(|t| (t.session_id.clone(), t))
+        .collect();
+    for transcript in transcripts.values() {
+        let written = std::fs::metadata(&transcript.path)
+            .and_then(|m| m.modified())
+            .map(modified_millis)
+            .unwrap_or_default();
+        if written >= since {
+            let candidate = candidates
+                .entry(transcript.session_id.clone())
+                .or_insert((None, written, written));
+            candidate.2 = candidate.2.max(written);
+        }
+    }
+
+    let mut sessions: Vec<ActiveSession> = candidates
+        .into_iter()
+        .filter_map(|(session_id, (project_path, first, last))| {
+            let transcript = transcripts.get(&session_id);
+            let usage = transcript
+                .and_then(|t| cached_usage(t, app, &provider))
+                .unwrap_or_default();
+            let started_at = usage.first_activity.map_or(first, |t| first.min(t * 1000));
+            let last_activity = usage.last_activity.map_or(last, |t| last.max(t * 1000));
+            let state = settings.state_for(last_activity, now);
+            if state == SessionState::Ended {
+                return None;
+            }
+            let project_path = project_path
+                .or_else(|| transcript.map(|t| t.project_folder.clone()))
+                .unwrap_or_default();
+
+            Some(ActiveSession {
+                project: std::path::Path::new(&project_path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| project_path.clone()),
+                project_path,
+                started_at,
+                last_activity,
+                elapsed_seconds: (now - started_at).max(0) / 1000,
+                state,
+                message_count: usage.messages,
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                cache_read_tokens: usage.cache_read_tokens,
+                cache_creation_tokens: usage.cache_creation_tokens,
+                total_tokens: usage.total_tokens,
+                cost_usd: usage.cost_usd,
+                model: usage.by_model.first().map(|m| m.model.clone()),
+                session_id,
+            })
+        })
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+    sessions
+}
+
+/// Recompute the active sessions after Claude data changed and push them to
+/// the frontend as `active-sessions-updated`
+pub fn refresh_active_sessions(app: &AppHandle) {
+    let _ = app.emit("active-sessions-updated", collect_active_sessions(app));
+}
+
+/// Sessions running right now, with live message, token and cost totals
+#[tauri::command]
+pub async fn get_active_sessions(app: AppHandle) -> Result<Vec<ActiveSession>, AppError> {
+    tauri::async_runtime::spawn_blocking(move || collect_active_sessions(&app))
+        .await
+        .map_err(|e| AppError::Other(e.to_string()))
+}
@@ -0,0 +1,146 @@
+// tauri-app/src-tauri/src/alertmanager.rs
+
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tauri::AppHandle;
+
+const ALERTMANAGER_KEY: &str = "alertmanager";
+const ALERTMANAGER_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlertmanagerSettings {
+    pub url: Option<String>,
+    /// Alertmanager matchers such as `job="otel-collector"`; empty shows every alert
+    pub matchers: Vec<String>,
+    /// Show the number of firing alerts next to the cost in the tray
+    pub show_in_tray: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlertStatus {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GettableAlert {
+    fingerprint: String,
+    labels: HashMap<String, String>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    starts_at: String,
+    status: AlertStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAlert {
+    pub fingerprint: String,
+    /// The `alertname` label
+    pub name: String,
+    pub severity: Option<String>,
+    /// The `summary` annotation, else `description`
+    pub summary: Option<String>,
+    pub labels: HashMap<String, String>,
+    pub annotations: HashMap<String, String>,
+    pub starts_at: String,
+    /// "active", "suppressed" or "unprocessed"
+    pub state: String,
+}
+
+impl From<GettableAlert> for ActiveAlert {
+    fn from(alert: GettableAlert) -> Self {
+        Self {
+            fingerprint: alert.fingerprint,
+            name: alert.labels.get("alertname").cloned().unwrap_or_default(),
+            severity: alert.labels.get("severity").cloned(),
+            summary: alert
+                .annotations
+                .get("summary")
+                .or(alert.annotations.get("description"))
+                .cloned(),
+            labels: alert.labels,
+            annotations: alert.annotations,
+            starts_at: alert.starts_at,
+            state: alert.status.state,
+        }
+    }
+}
+
+/// Firing alerts as of the last `get_active_alerts`, shown in the tray when enabled
+static TRAY_ALERT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn tray_alert_count() -> usize {
+    TRAY_ALERT_COUNT.load(Ordering::Relaxed)
+}
+
+async fn fetch_alerts(url: &str, matchers: &[String]) -> Result<Vec<ActiveAlert>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(ALERTMANAGER_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut params = vec![
+        ("active", "true"),
+        ("silenced", "false"),
+        ("inhibited", "false"),
+    ];
+    params.extend(matchers.iter().map(|m| ("filter", m.as_str())));
+
+    let response = client
+        .get(format!("{}/api/v2/alerts", url.trim_end_matches('/')))
+        .query(&params)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Alertmanager returned {}", response.status()));
+    }
+    let alerts: Vec<GettableAlert> = response.json().await.map_err(|e| e.to_string())?;
+    Ok(alerts.into_iter().map(ActiveAlert::from).collect())
+}
+
+/// Firing, unsilenced alerts matching the configured matchers, newest first.
+/// `alertmanager_url` overrides the saved URL.
+#[tauri::command]
+pub async fn get_active_alerts(
+    app: AppHandle,
+    alertmanager_url: Option<String>,
+) -> Result<Vec<ActiveAlert>, String> {
+    let settings: AlertmanagerSettings = load_setting(&app, ALERTMANAGER_KEY);
+    let url = alertmanager_url
+        .or(settings.url)
+        .filter(|u| !u.trim().is_empty())
+        .ok_or("Alertmanager URL is not configured")?;
+
+    let mut alerts = fetch_alerts(&url, &settings.matchers).await?;
+    alerts.sort_by(|a, b| b.starts_at.cmp(&a.starts_at));
+
+    let count = if settings.show_in_tray {
+        alerts.len()
+    } else {
+        0
+    };
+    TRAY_ALERT_COUNT.store(count, Ordering::Relaxed);
+    Ok(alerts)
+}
+
+#[tauri::command]
+pub async fn get_alertmanager_settings(app: AppHandle) -> Result<AlertmanagerSettings, String> {
+    Ok(load_setting(&app, ALERTMANAGER_KEY))
+}
+
+#[tauri::command]
+pub async fn set_alertmanager_settings(
+    app: AppHandle,
+    settings: AlertmanagerSettings,
+) -> Result<(), String> {
+    if !settings.show_in_tray {
+        TRAY_ALERT_COUNT.store(0, Ordering::Relaxed);
+    }
+    save_setting(&app, ALERTMANAGER_KEY, &settings)
+}
@@ -0,0 +1,296 @@
+// tauri-app/src-tauri/src/alerts.rs
+
+use crate::error::AppError;
+use crate::settings::{load_setting, save_setting};
+use crate::webhooks::{deliver, envelope, WebhookConfig};
+use chrono::{Datelike, Duration, Local, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const QUIET_HOURS_KEY: &str = "quietHours";
+const ALERT_HISTORY_KEY: &str = "alertHistory";
+/// Oldest entries are dropped past this
+const MAX_ALERT_HISTORY: usize = 500;
+
+/// Discord embed colors
+const COLOR_RED: u32 = 0xe74c3c;
+const COLOR_GREEN: u32 = 0x2ecc71;
+const COLOR_BLUE: u32 = 0x3498db;
+
+/// Body layout for a webhook: chat services only render their own message shapes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// `{ event, timestamp, data }` with the raw event payload
+    #[default]
+    Generic,
+    Slack,
+    Discord,
+}
+
+fn format_usd(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("${:.2}", v))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Title and body for budget, connectivity and cost alerts; None for other events
+pub fn alert_text(event: &str, payload: &Value) -> Option<(String, String)> {
+    let text = |key: &str| payload[key].as_str().unwrap_or_default().to_string();
+    let usd = |key: &str| format_usd(payload[key].as_f64());
+
+    match event {
+        "budget-exceeded" => Some((
+            "Budget exceeded".to_string(),
+            format!(
+                "{} spend is {} of a {} budget",
+                text("period"),
+                usd("spentUsd"),
+                usd("limitUsd")
+            ),
+        )),
+        "prometheus-down" => Some((
+            "Prometheus is unreachable".to_string(),
+            match payload["error"].as_str() {
+                Some(error) => format!("{}: {}", text("url"), error),
+                None => text("url"),
+            },
+        )),
+        "prometheus-up" => Some(("Prometheus is back".to_string(), text("url"))),
+        "session-cost-threshold" => Some((
+            "Session cost threshold reached".to_string(),
+            format!("{} has cost {}", text("project"), usd("costUsd")),
+        )),
+        "test" => Some((
+            "Test alert".to_string(),
+            "Claude Code Monitor can deliver to this webhook".to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// Daily window during which alert notifications are held back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuietHours {
+    pub enabled: bool,
+    /// Local "HH:MM"; a window ending earlier than it starts runs past midnight
+    pub start: String,
+    pub end: String,
+    /// Days the window starts on, 0 = Monday ... 6 = Sunday (empty = every day)
+    pub days: Vec<u8>,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+            days: Vec::new(),
+        }
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M").ok()
+}
+
+impl QuietHours {
+    /// Whether `now` falls in a window that started on one of the configured days
+    fn contains(&self, now: NaiveDateTime) -> bool {
+        let (Some(start), Some(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+        if !self.enabled || start == end {
+            return false;
+        }
+        let starts_on = |date: chrono::NaiveDate| {
+            self.days.is_empty()
+                || self
+                    .days
+                    .contains(&(date.weekday().num_days_from_monday() as u8))
+        };
+
+        let (date, time) = (now.date(), now.time());
+        if start < end {
+            starts_on(date) && time >= start && time < end
+        } else {
+            // Overnight: the evening part starts today, the morning part started yesterday
+            (starts_on(date) && time >= start)
+                || (starts_on(date - Duration::days(1)) && time < end)
+        }
+    }
+}
+
+pub fn in_quiet_hours(app: &AppHandle) -> bool {
+    load_setting::<QuietHours>(app, QUIET_HOURS_KEY).contains(Local::now().naive_local())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertHistoryEntry {
+    pub event: String,
+    pub title: String,
+    pub body: String,
+    /// Milliseconds since the epoch
+    pub timestamp: i64,
+    /// Fired during quiet hours, so no notification was shown
+    pub suppressed: bool,
+}
+
+/// Serializes read-modify-write of the history between concurrent events
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Log an alert event to the history; returns false during quiet hours, when
+/// notifications for it should be held back. Events without alert text pass.
+pub fn record_alert(app: &AppHandle, event: &str, payload: &Value) -> bool {
+    let Some((title, body)) = alert_text(event, payload) else {
+        return true;
+    };
+    let suppressed = in_quiet_hours(app);
+
+    if let Ok(_guard) = HISTORY_LOCK.lock() {
+        let mut history: Vec<AlertHistoryEntry> = load_setting(app, ALERT_HISTORY_KEY);
+        history.push(AlertHistoryEntry {
+            event: event.to_string(),
+            title,
+            body,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            suppressed,
+        });
+        let excess = history.len().saturating_sub(MAX_ALERT_HISTORY);
+        history.drain(..excess);
+        let _ = save_setting(app, ALERT_HISTORY_KEY, &history);
+    }
+    !suppressed
+}
+
+fn color(event: &str) -> u32 {
+    match event {
+        "budget-exceeded" | "prometheus-down" | "session-cost-threshold" => COLOR_RED,
+        "prometheus-up" => COLOR_GREEN,
+        _ => COLOR_BLUE,
+    }
+}
+
+/// Request body for `event` in the webhook's format. Events without alert text
+/// are posted to chat services as the event name and its JSON payload.
+pub fn webhook_body(format: WebhookFormat, event: &str, payload: &Value) -> Value {
+    let (title, body) =
+        alert_text(event, payload).unwrap_or_else(|| (event.to_string(), payload.to_string()));
+    match format {
+        WebhookFormat::Generic => envelope(event, payload),
+        WebhookFormat::Slack => json!({ "text": format!("*{}*\n{}", title, body) }),
+        WebhookFormat::Discord => json!({
+            "embeds": [{
+                "title": title,
+                "description": body,
+                "color": color(event),
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            }]
+        }),
+    }
+}
+
+/// Send a sample alert to a webhook (saved or not) to check the URL and format
+#[tauri::command]
+pub async fn test_webhook(webhook: WebhookConfig) -> Result<(), AppError> {
+    if !(webhook.url.starts_with("http://") || webhook.url.starts_with("https://")) {
+        return Err(AppError::InvalidInput(
+            "Webhook URL must start with http:// or https://".to_string(),
+        ));
+    }
+    let body = webhook_body(
+        webhook.format,
+        "test",
+        &json!({ "message": "Test alert from Claude Code Monitor" }),
+    );
+    deliver(&webhook, &body)
+        .await
+        .map_err(AppError::ConnectionFailed)
+}
+
+#[tauri::command]
+pub async fn get_quiet_hours(app: AppHandle) -> Result<QuietHours, AppError> {
+    Ok(load_setting(&app, QUIET_HOURS_KEY))
+}
+
+#[tauri::command]
+pub async fn set_quiet_hours(app: AppHandle, quiet_hours: QuietHours) -> Result<(), AppError> {
+    if parse_time(&quiet_hours.start).is_none() || parse_time(&quiet_hours.end).is_none() {
+        return Err(AppError::InvalidInput(
+            "Quiet hours must be given as HH:MM".to_string(),
+        ));
+    }
+    if quiet_hours.days.iter().any(|&d| d > 6) {
+        return Err(AppError::InvalidInput(
+            "Days must be 0 (Monday) to 6 (Sunday)".to_string(),
+        ));
+    }
+    Ok(save_setting(&app, QUIET_HOURS_KEY, &quiet_hours)?)
+}
+
+/// Alerts fired so far, newest first, including ones held back by quiet hours
+#[tauri::command]
+pub async fn get_alert_history(app: AppHandle) -> Result<Vec<AlertHistoryEntry>, AppError> {
+    let mut history: Vec<AlertHistoryEntry> = load_setting(&app, ALERT_HISTORY_KEY);
+    history.reverse();
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // 2026-01-05 is a Monday
+        NaiveDate::from_ymd_opt(2026, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn quiet_hours(start: &str, end: &str, days: Vec<u8>) -> QuietHours {
+        QuietHours {
+            enabled: true,
+            start: start.to_string(),
+            end: end.to_string(),
+            days,
+        }
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_the_day_it_starts() {
+        // Friday night only
+        let quiet = quiet_hours("22:00", "07:00", vec![4]);
+
+        assert!(quiet.contains(at(9, 23)));
+        assert!(quiet.contains(at(10, 6)));
+        assert!(!quiet.contains(at(10, 7)));
+        assert!(!quiet.contains(at(10, 23)));
+        assert!(!quiet.contains(at(9, 6)));
+    }
+
+    #[test]
+    fn daytime_window_ends_before_its_end_time() {
+        let quiet = quiet_hours("09:00", "17:00", Vec::new());
+
+        assert!(quiet.contains(at(5, 9)));
+        assert!(quiet.contains(at(11, 16)));
+        assert!(!quiet.contains(at(5, 17)));
+        assert!(!quiet.contains(at(5, 8)));
+    }
+
+    #[test]
+    fn disabled_or_empty_window_never_matches() {
+        let mut quiet = quiet_hours("00:00", "23:59", Vec::new());
+        quiet.enabled = false;
+        assert!(!quiet.contains(at(5, 12)));
+        assert!(!quiet_hours("08:00", "08:00", Vec::new()).contains(at(5, 8)));
+        assert!(!quiet_hours("8am", "17:00", Vec::new()).contains(at(5, 12)));
+    }
+}
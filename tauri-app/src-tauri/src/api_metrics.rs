@@ -0,0 +1,291 @@
+// tauri-app/src-tauri/src/api_metrics.rs
+
+use crate::commands::{range_anchor, resolve_time_range, step_and_rate_window, to_time_series};
+use crate::error::AppError;
+use crate::metrics::TimeSeriesPoint;
+use crate::prometheus::{PrometheusClient, QueryResult};
+use crate::query_tuning::{load_query_tuning, QueryTuning};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+const REQUEST_METRIC: &str = "claude_code_api_request_total";
+const ERROR_METRIC: &str = "claude_code_api_error_total";
+/// Request duration histogram, recorded in milliseconds
+const DURATION_BUCKETS: &str = "claude_code_api_request_duration_milliseconds_bucket";
+const QUANTILES: [f64; 3] = [0.5, 0.9, 0.99];
+
+/// Failed API requests for one status code or model
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiErrorCount {
+    pub label: String,
+    pub errors: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiErrorMetrics {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_requests: u64,
+    pub total_errors: u64,
+    /// Errors as a share of requests, 0-100
+    pub error_rate: f64,
+    /// Most errors first
+    pub by_status_code: Vec<ApiErrorCount>,
+    pub by_model: Vec<ApiErrorCount>,
+    /// Errors per bucket, on the same steps as the dashboard cost chart
+    pub errors_over_time: Vec<TimeSeriesPoint>,
+}
+
+/// p50/p90/p99 request duration in milliseconds; None without requests
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyPercentiles {
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+impl LatencyPercentiles {
+    /// Set the percentile for `QUANTILES[index]`
+    fn set(&mut self, index: usize, value: f64) {
+        let value = value.is_finite().then_some(value);
+        match index {
+            0 => self.p50 = value,
+            1 => self.p90 = value,
+            _ => self.p99 = value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelLatency {
+    pub model: String,
+    #[serde(flatten)]
+    pub percentiles: LatencyPercentiles,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiLatencyMetrics {
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Across all models
+    pub overall: LatencyPercentiles,
+    /// Slowest p90 first
+    pub by_model: Vec<ModelLatency>,
+    pub p50_over_time: Vec<TimeSeriesPoint>,
+    pub p90_over_time: Vec<TimeSeriesPoint>,
+    pub p99_over_time: Vec<TimeSeriesPoint>,
+}
+
+fn value(result: &QueryResult) -> f64 {
+    result
+        .value
+        .as_ref()
+        .and_then(|(_, v)| v.parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+async fn total(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    metric: &str,
+    range: &str,
+    anchor: Option<i64>,
+) -> Result<u64, AppError> {
+    let query = format!(
+        "sum(increase({}{}))",
+        metric,
+        tuning.selector(metric, range, anchor)
+    );
+    Ok(client
+        .query(&query)
+        .await?
+        .first()
+        .map(value)
+        .unwrap_or(0.0)
+        .round() as u64)
+}
+
+async fn errors_by(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    label: &str,
+    range: &str,
+    anchor: Option<i64>,
+) -> Result<Vec<ApiErrorCount>, AppError> {
+    let query = format!(
+        "sum by ({}) (increase({}{}))",
+        label,
+        ERROR_METRIC,
+        tuning.selector(ERROR_METRIC, range, anchor)
+    );
+    let mut counts: Vec<ApiErrorCount> = client
+        .query(&query)
+        .await?
+        .iter()
+        .map(|r| ApiErrorCount {
+            label: r
+                .metric
+                .get(label)
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string()),
+            errors: value(r).round() as u64,
+        })
+        .filter(|c| c.errors > 0)
+        .collect();
+    counts.sort_by_key(|c| std::cmp::Reverse(c.errors));
+    Ok(counts)
+}
+
+/// API error rate, errors by status code and model, and errors over time, to
+/// line up retries and failed requests with cost spikes
+#[tauri::command]
+pub async fn get_api_error_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<ApiErrorMetrics, AppError> {
+    let tuning = load_query_tuning(&app);
+    let client =
+        PrometheusClient::new(&prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let (start_time, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let anchor = range_anchor(&time_range, end_time);
+    let (step, rate_window) = step_and_rate_window(&time_range, start_time, end_time);
+
+    let errors_range_query = format!(
+        "sum(increase({}[{}]))",
+        ERROR_METRIC,
+        tuning.rate_window(ERROR_METRIC, rate_window)
+    );
+    let (total_requests, total_errors, by_status_code, by_model, errors_over_time) = tokio::join!(
+        total(&client, &tuning, REQUEST_METRIC, &range, anchor),
+        total(&client, &tuning, ERROR_METRIC, &range, anchor),
+        errors_by(&client, &tuning, "status_code", &range, anchor),
+        errors_by(&client, &tuning, "model", &range, anchor),
+        client.query_range(&errors_range_query, start_time, end_time, step),
+    );
+    let (total_requests, total_errors) = (total_requests?, total_errors?);
+
+    let error_rate = if total_requests > 0 {
+        total_errors as f64 / total_requests as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(ApiErrorMetrics {
+        start_time,
+        end_time,
+        total_requests,
+        total_errors,
+        error_rate,
+        by_status_code: by_status_code?,
+        by_model: by_model?,
+        errors_over_time: errors_over_time?
+            .first()
+            .map(to_time_series)
+            .unwrap_or_default(),
+    })
+}
+
+fn quantile_query(quantile: f64, by: &str, window: &str) -> String {
+    format!(
+        "histogram_quantile({}, sum by (le{}) (rate({}[{}])))",
+        quantile, by, DURATION_BUCKETS, window
+    )
+}
+
+/// Quantile series with the empty buckets (NaN without requests) dropped
+async fn quantile_over_time(
+    client: &PrometheusClient,
+    quantile: f64,
+    window: &str,
+    start_time: i64,
+    end_time: i64,
+    step: &str,
+) -> Result<Vec<TimeSeriesPoint>, AppError> {
+    let query = quantile_query(quantile, "", window);
+    let mut points = client
+        .query_range(&query, start_time, end_time, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
+    points.retain(|p| p.value.is_finite());
+    Ok(points)
+}
+
+/// p50/p90/p99 API request latency overall and per model, plus percentiles
+/// over time for the dashboard
+#[tauri::command]
+pub async fn get_api_latency_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<ApiLatencyMetrics, AppError> {
+    let tuning = load_query_tuning(&app);
+    let client =
+        PrometheusClient::new(&prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let (start_time, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let (step, rate_window) = step_and_rate_window(&time_range, start_time, end_time);
+    let window = tuning.window(DURATION_BUCKETS, &range);
+    let anchor = range_anchor(&time_range, end_time);
+
+    let mut overall = LatencyPercentiles::default();
+    let mut by_model: BTreeMap<String, LatencyPercentiles> = BTreeMap::new();
+    for (index, quantile) in QUANTILES.into_iter().enumerate() {
+        let query = quantile_query(quantile, ", model", &window);
+        for result in &client.query_at(&query, anchor).await? {
+            let model = result
+                .metric
+                .get("model")
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string());
+            by_model.entry(model).or_default().set(index, value(result));
+        }
+
+        let query = quantile_query(quantile, "", &window);
+        if let Some(result) = client.query_at(&query, anchor).await?.first() {
+            overall.set(index, value(result));
+        }
+    }
+
+    let mut by_model: Vec<ModelLatency> = by_model
+        .into_iter()
+        .filter(|(_, p)| p.p50.is_some() || p.p90.is_some() || p.p99.is_some())
+        .map(|(model, percentiles)| ModelLatency { model, percentiles })
+        .collect();
+    by_model.sort_by(|a, b| {
+        b.percentiles
+            .p90
+            .partial_cmp(&a.percentiles.p90)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let rate_window = tuning.rate_window(DURATION_BUCKETS, rate_window);
+    let over_time =
+        |quantile| quantile_over_time(&client, quantile, &rate_window, start_time, end_time, step);
+    let (p50_over_time, p90_over_time, p99_over_time) = tokio::join!(
+        over_time(QUANTILES[0]),
+        over_time(QUANTILES[1]),
+        over_time(QUANTILES[2]),
+    );
+
+    Ok(ApiLatencyMetrics {
+        start_time,
+        end_time,
+        overall,
+        by_model,
+        p50_over_time: p50_over_time?,
+        p90_over_time: p90_over_time?,
+        p99_over_time: p99_over_time?,
+    })
+}
@@ -0,0 +1,181 @@
+// tauri-app/src-tauri/src/auto_refresh.rs
+
+use crate::commands::{get_prometheus_health, load_dashboard_metrics};
+use crate::filters::LabelFilters;
+use crate::sessions::get_sessions_data;
+use crate::settings::{frontend_prometheus_url, load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+
+const AUTO_REFRESH_KEY: &str = "autoRefresh";
+const MIN_INTERVAL_SECONDS: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoRefreshSettings {
+    pub enabled: bool,
+    pub interval_seconds: u32,
+    /// Dashboard and health range, e.g. "1d"
+    pub time_range: String,
+    /// Sessions range, e.g. "24h" (the sessions view has its own presets)
+    pub sessions_time_range: String,
+    /// Dashboard filters, so pushed metrics match what the dashboard shows
+    pub filters: LabelFilters,
+    /// Downsample dashboard series to at most this many points
+    pub max_points: Option<usize>,
+}
+
+impl Default for AutoRefreshSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 30,
+            time_range: "1d".to_string(),
+            sessions_time_range: "24h".to_string(),
+            filters: LabelFilters::default(),
+            max_points: None,
+        }
+    }
+}
+
+static REFRESH_TASK: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+/// Fetch everything once and emit `metrics-updated`, `sessions-updated` and
+/// `health-updated` for whichever succeeded
+async fn refresh(app: &AppHandle, settings: &AutoRefreshSettings) {
+    let url = frontend_prometheus_url(app);
+    let (metrics, sessions, health) = tokio::join!(
+        load_dashboard_metrics(
+            app.clone(),
+            settings.time_range.clone(),
+            url.clone(),
+            None,
+            None,
+            None,
+            settings.filters.clone(),
+        ),
+        get_sessions_data(
+            app.clone(),
+            settings.sessions_time_range.clone(),
+            url.clone(),
+            None,
+            None,
+        ),
+        get_prometheus_health(
+            app.clone(),
+            url.clone(),
+            Some(settings.time_range.clone()),
+            None,
+            None,
+            None,
+        ),
+    );
+
+    match metrics {
+        Ok(mut metrics) => {
+            if let Some(max_points) = settings.max_points {
+                metrics.downsample(max_points);
+            }
+            let _ = app.emit("metrics-updated", metrics);
+        }
+        Err(e) => eprintln!("Auto refresh: dashboard metrics failed: {}", e),
+    }
+    match sessions {
+        Ok(sessions) => {
+            let _ = app.emit("sessions-updated", sessions);
+        }
+        Err(e) => eprintln!("Auto refresh: sessions failed: {}", e),
+    }
+    match health {
+        Ok(health) => {
+            let _ = app.emit("health-updated", health);
+        }
+        Err(e) => eprintln!("Auto refresh: health failed: {}", e),
+    }
+}
+
+fn stop_task() {
+    if let Ok(mut guard) = REFRESH_TASK.lock() {
+        if let Some(task) = guard.take() {
+            task.abort();
+        }
+    }
+}
+
+fn start_task(app: AppHandle, settings: AutoRefreshSettings) {
+    stop_task();
+    let task = tauri::async_runtime::spawn(async move {
+        let period =
+            Duration::from_secs(settings.interval_seconds.max(MIN_INTERVAL_SECONDS) as u64);
+        // Views fetch on their own when they start the refresh, so the first
+        // push is one period later
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        // A slow Prometheus shouldn't cause a burst of catch-up refreshes
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            refresh(&app, &settings).await;
+        }
+    });
+    if let Ok(mut guard) = REFRESH_TASK.lock() {
+        *guard = Some(task);
+    }
+}
+
+/// Resume auto refresh at launch if it was left on
+pub fn resume_auto_refresh(app: AppHandle) {
+    let settings: AutoRefreshSettings = load_setting(&app, AUTO_REFRESH_KEY);
+    if settings.enabled {
+        start_task(app, settings);
+    }
+}
+
+#[tauri::command]
+pub async fn get_auto_refresh_settings(app: AppHandle) -> Result<AutoRefreshSettings, String> {
+    Ok(load_setting(&app, AUTO_REFRESH_KEY))
+}
+
+/// Start (or restart with new options) the background refresh; unset options
+/// keep their saved values
+#[tauri::command]
+pub async fn start_auto_refresh(
+    app: AppHandle,
+    interval_seconds: Option<u32>,
+    time_range: Option<String>,
+    sessions_time_range: Option<String>,
+    filters: Option<LabelFilters>,
+    max_points: Option<usize>,
+) -> Result<AutoRefreshSettings, String> {
+    let saved: AutoRefreshSettings = load_setting(&app, AUTO_REFRESH_KEY);
+    let settings = AutoRefreshSettings {
+        enabled: true,
+        interval_seconds: interval_seconds.unwrap_or(saved.interval_seconds),
+        time_range: time_range.unwrap_or(saved.time_range),
+        sessions_time_range: sessions_time_range.unwrap_or(saved.sessions_time_range),
+        filters: filters.unwrap_or(saved.filters),
+        max_points: max_points.or(saved.max_points),
+    };
+    if settings.interval_seconds < MIN_INTERVAL_SECONDS {
+        return Err(format!(
+            "Refresh interval must be at least {} seconds",
+            MIN_INTERVAL_SECONDS
+        ));
+    }
+
+    save_setting(&app, AUTO_REFRESH_KEY, &settings)?;
+    start_task(app, settings.clone());
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn stop_auto_refresh(app: AppHandle) -> Result<(), String> {
+    stop_task();
+    let settings = AutoRefreshSettings {
+        enabled: false,
+        ..load_setting(&app, AUTO_REFRESH_KEY)
+    };
+    save_setting(&app, AUTO_REFRESH_KEY, &settings)
+}
@@ -0,0 +1,175 @@
+// tauri-app/src-tauri/src/blocks.rs
+
+use crate::error::AppError;
+use crate::pricing::resolve_profile;
+use crate::settings::frontend_pricing_provider;
+use crate::verification::{for_each_usage, UsageRecord};
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Subscription usage is metered in windows of this length
+pub const BLOCK_SECONDS: i64 = 5 * 3600;
+const DEFAULT_LOOKBACK_DAYS: u32 = 7;
+
+/// One 5-hour billing window: it opens at the hour of the first message sent
+/// after the previous window closed and resets five hours later
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingBlock {
+    pub start: i64,
+    /// When the block resets
+    pub end: i64,
+    pub first_activity: i64,
+    pub last_activity: i64,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    pub messages: u32,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingBlocks {
+    /// The block the current time falls in, if anything was sent in it
+    pub active: Option<BillingBlock>,
+    /// Seconds until the active block resets
+    pub resets_in_seconds: Option<i64>,
+    /// Earlier blocks in the lookback window, newest first
+    pub recent: Vec<BillingBlock>,
+}
+
+impl BillingBlock {
+    fn open(record: &UsageRecord) -> Self {
+        let timestamp = record.timestamp.timestamp();
+        // Windows are aligned to the start of the hour
+        let start = timestamp - timestamp.rem_euclid(3600);
+        Self {
+            start,
+            end: start + BLOCK_SECONDS,
+            first_activity: timestamp,
+            last_activity: timestamp,
+            tokens: 0,
+            cost_usd: 0.0,
+            messages: 0,
+            is_active: false,
+        }
+    }
+
+    fn add(&mut self, record: &UsageRecord) {
+        self.last_activity = self.last_activity.max(record.timestamp.timestamp());
+        self.tokens += record.tokens;
+        self.cost_usd += record.cost_usd;
+        self.messages += 1;
+    }
+}
+
+/// Group usage records into consecutive 5-hour blocks, oldest first
+pub fn group_blocks(mut records: Vec<UsageRecord>, now: i64) -> Vec<BillingBlock> {
+    records.sort_by_key(|r| r.timestamp);
+
+    let mut blocks: Vec<BillingBlock> = Vec::new();
+    for record in &records {
+        match blocks.last_mut() {
+            Some(block) if record.timestamp.timestamp() < block.end => block.add(record),
+            _ => {
+                let mut block = BillingBlock::open(record);
+                block.add(record);
+                blocks.push(block);
+            }
+        }
+    }
+    if let Some(last) = blocks.last_mut() {
+        last.is_active = now < last.end;
+    }
+    blocks
+}
+
+/// Usage records from the transcripts of the last `days` days, priced with the
+/// frontend's pricing provider
+pub fn recent_usage(app: &AppHandle, days: u32, now: i64) -> Vec<UsageRecord> {
+    let pricing = resolve_profile(app, &frontend_pricing_provider(app));
+    // Start a block early so one open at the cutoff is reconstructed in full
+    let start = now - days as i64 * 86400 - BLOCK_SECONDS;
+    let mut records = Vec::new();
+    for_each_usage(start, now + 60, &pricing, |record| records.push(record));
+    records
+}
+
+fn billing_blocks(app: &AppHandle, days: u32) -> BillingBlocks {
+    let now = chrono::Utc::now().timestamp();
+    let mut blocks = group_blocks(recent_usage(app, days, now), now);
+
+    let active = match blocks.last() {
+        Some(block) if block.is_active => blocks.pop(),
+        _ => None,
+    };
+    let cutoff = now - days as i64 * 86400;
+    blocks.retain(|b| b.end > cutoff);
+    blocks.reverse();
+
+    BillingBlocks {
+        resets_in_seconds: active.as_ref().map(|b| b.end - now),
+        active,
+        recent: blocks,
+    }
+}
+
+/// Tokens and cost in the current 5-hour block, time until it resets, and the
+/// blocks before it, reconstructed from transcript usage
+#[tauri::command]
+pub async fn get_billing_blocks(
+    app: AppHandle,
+    days: Option<u32>,
+) -> Result<BillingBlocks, AppError> {
+    let days = days.unwrap_or(DEFAULT_LOOKBACK_DAYS).max(1);
+    tauri::async_runtime::spawn_blocking(move || billing_blocks(&app, days))
+        .await
+        .map_err(|e| AppError::Other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn record(timestamp: i64, tokens: u64) -> UsageRecord {
+        UsageRecord {
+            timestamp: DateTime::from_timestamp(timestamp, 0).unwrap(),
+            tokens,
+            cost_usd: tokens as f64 / 1000.0,
+        }
+    }
+
+    #[test]
+    fn records_group_into_hour_aligned_five_hour_blocks() {
+        // 2026-01-05 10:20 UTC
+        let base = 1_767_608_400;
+        let records = vec![
+            record(base + 3600, 200),
+            record(base, 100),
+            // 16:20, after the first block reset at 15:00
+            record(base + 6 * 3600, 300),
+        ];
+
+        let blocks = group_blocks(records, base + 7 * 3600);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].start, base - 20 * 60);
+        assert_eq!(blocks[0].end, blocks[0].start + BLOCK_SECONDS);
+        assert_eq!(blocks[0].first_activity, base);
+        assert_eq!(blocks[0].last_activity, base + 3600);
+        assert_eq!(blocks[0].tokens, 300);
+        assert_eq!(blocks[0].messages, 2);
+        assert!(!blocks[0].is_active);
+        assert_eq!(blocks[1].start, base + 6 * 3600 - 20 * 60);
+        assert_eq!(blocks[1].tokens, 300);
+        assert!(blocks[1].is_active);
+    }
+
+    #[test]
+    fn last_block_is_inactive_once_it_resets() {
+        let blocks = group_blocks(vec![record(0, 1)], BLOCK_SECONDS);
+        assert_eq!(blocks.len(), 1);
+        assert!(!blocks[0].is_active);
+    }
+}
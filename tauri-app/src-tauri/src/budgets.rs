@@ -0,0 +1,360 @@
+// tauri-app/src-tauri/src/budgets.rs
+
+use crate::error::AppError;
+use crate::events::publish;
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::{format_duration, load_query_tuning, QueryTuning};
+use crate::settings::{frontend_prometheus_url, load_setting, save_setting};
+use crate::timezone;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+const BUDGETS_KEY: &str = "budgets";
+const COST_METRIC: &str = "claude_code_cost_usage_USD_total";
+/// Projections use the average spend rate over this trailing window. The
+/// dashboard's 15-minute burn rate drops to zero between sessions, which would
+/// make every budget look safe.
+const PROJECTION_WINDOW_SECONDS: i64 = 24 * 3600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BudgetSettings {
+    pub enabled: bool,
+    /// Spend limits in USD; unset periods aren't tracked
+    pub daily_usd: Option<f64>,
+    pub weekly_usd: Option<f64>,
+    pub monthly_usd: Option<f64>,
+    /// Status turns to `warning` once this share of a budget is spent, 0-100
+    pub warning_percent: f64,
+    pub poll_interval_seconds: u32,
+}
+
+impl Default for BudgetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            daily_usd: None,
+            weekly_usd: None,
+            monthly_usd: None,
+            warning_percent: 80.0,
+            poll_interval_seconds: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Ordered from best to worst so the tray can show the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetState {
+    Under,
+    Warning,
+    Exceeded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub period: BudgetPeriod,
+    /// Midnight the period started at in the configured timezone (the configured week
+    /// start for weekly, the 1st for monthly)
+    pub period_start: i64,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    /// Negative once the budget is exceeded
+    pub remaining_usd: f64,
+    pub percent_used: f64,
+    pub status: BudgetState,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetProjection {
+    pub period: BudgetPeriod,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    /// Average over the last 24 hours
+    pub burn_rate_usd_per_hour: f64,
+    /// When the period resets
+    pub period_end: i64,
+    /// When spend reaches the budget at this pace (now if it already has); None
+    /// if the period resets first
+    pub exhausted_at: Option<i64>,
+}
+
+/// Budget statuses as of the last check, for the tray and `get_budget_status`
+static STATUSES: Mutex<Vec<BudgetStatus>> = Mutex::new(Vec::new());
+/// Projections as of the last check, for `get_budget_projection`
+static PROJECTIONS: Mutex<Vec<BudgetProjection>> = Mutex::new(Vec::new());
+
+pub fn load_budget_settings(app: &AppHandle) -> BudgetSettings {
+    load_setting(app, BUDGETS_KEY)
+}
+
+/// Most severe status across the configured budgets, if any are set
+pub fn tray_budget_state() -> Option<BudgetState> {
+    STATUSES.lock().ok()?.iter().map(|s| s.status).max()
+}
+
+fn period_start(period: BudgetPeriod, today: NaiveDate) -> NaiveDate {
+    match period {
+        BudgetPeriod::Daily => today,
+        BudgetPeriod::Weekly => timezone::week_start(today),
+        BudgetPeriod::Monthly => today.with_day(1).unwrap_or(today),
+    }
+}
+
+fn period_end(period: BudgetPeriod, start: NaiveDate) -> NaiveDate {
+    match period {
+        BudgetPeriod::Daily => start + Duration::days(1),
+        BudgetPeriod::Weekly => start + Duration::days(7),
+        BudgetPeriod::Monthly => start
+            .checked_add_months(Months::new(1))
+            .unwrap_or(start + Duration::days(31)),
+    }
+}
+
+/// USD spent between `start` and now
+async fn spend_since(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    start: i64,
+    now: i64,
+) -> Result<f64, AppError> {
+    let range = format_duration((now - start).max(60));
+    let query = format!(
+        "sum(increase({}{}))",
+        COST_METRIC,
+        tuning.selector(COST_METRIC, &range, None)
+    );
+    Ok(client
+        .query(&query)
+        .await?
+        .first()
+        .and_then(|r| r.value.as_ref())
+        .and_then(|(_, v)| v.parse::<f64>().ok())
+        .unwrap_or(0.0))
+}
+
+/// Current spend against each configured budget
+pub async fn evaluate_budgets(
+    app: &AppHandle,
+    settings: &BudgetSettings,
+) -> Result<Vec<BudgetStatus>, AppError> {
+    let tuning = load_query_tuning(app);
+    let client = PrometheusClient::new(&frontend_prometheus_url(app))?
+        .with_lookback_delta(tuning.lookback_delta.clone());
+    let today = timezone::today();
+    let now = chrono::Utc::now().timestamp();
+
+    let budgets = [
+        (BudgetPeriod::Daily, settings.daily_usd),
+        (BudgetPeriod::Weekly, settings.weekly_usd),
+        (BudgetPeriod::Monthly, settings.monthly_usd),
+    ];
+    let mut statuses = Vec::new();
+    for (period, limit) in budgets {
+        let Some(limit_usd) = limit else {
+            continue;
+        };
+        let period_start = timezone::start_of_day(period_start(period, today));
+        let spent_usd = spend_since(&client, &tuning, period_start, now).await?;
+        let percent_used = spent_usd / limit_usd * 100.0;
+        let status = if spent_usd >= limit_usd {
+            BudgetState::Exceeded
+        } else if percent_used >= settings.warning_percent {
+            BudgetState::Warning
+        } else {
+            BudgetState::Under
+        };
+        statuses.push(BudgetStatus {
+            period,
+            period_start,
+            limit_usd,
+            spent_usd,
+            remaining_usd: limit_usd - spent_usd,
+            percent_used,
+            status,
+        });
+    }
+    Ok(statuses)
+}
+
+/// When each budget runs out at the last 24 hours' spend rate
+pub async fn project_budgets(
+    app: &AppHandle,
+    statuses: &[BudgetStatus],
+) -> Result<Vec<BudgetProjection>, AppError> {
+    if statuses.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tuning = load_query_tuning(app);
+    let client = PrometheusClient::new(&frontend_prometheus_url(app))?
+        .with_lookback_delta(tuning.lookback_delta.clone());
+    let today = timezone::today();
+    let now = chrono::Utc::now().timestamp();
+
+    let recent_spend = spend_since(&client, &tuning, now - PROJECTION_WINDOW_SECONDS, now).await?;
+    let burn_rate_usd_per_hour = recent_spend / (PROJECTION_WINDOW_SECONDS as f64 / 3600.0);
+
+    Ok(statuses
+        .iter()
+        .map(|status| {
+            let period_end = timezone::start_of_day(period_end(
+                status.period,
+                period_start(status.period, today),
+            ));
+            let exhausted_at = if status.remaining_usd <= 0.0 {
+                Some(now)
+            } else if burn_rate_usd_per_hour > 0.0 {
+                let hours = status.remaining_usd / burn_rate_usd_per_hour;
+                Some(now + (hours * 3600.0).round() as i64).filter(|&at| at < period_end)
+            } else {
+                None
+            };
+            BudgetProjection {
+                period: status.period,
+                limit_usd: status.limit_usd,
+                spent_usd: status.spent_usd,
+                burn_rate_usd_per_hour,
+                period_end,
+                exhausted_at,
+            }
+        })
+        .collect())
+}
+
+fn store_projections(projections: &[BudgetProjection]) {
+    if let Ok(mut guard) = PROJECTIONS.lock() {
+        *guard = projections.to_vec();
+    }
+}
+
+fn store_statuses(statuses: &[BudgetStatus]) {
+    if let Ok(mut guard) = STATUSES.lock() {
+        *guard = statuses.to_vec();
+    }
+}
+
+/// Check spend every poll interval, emit `budget-updated` with the statuses,
+/// publish `budget-exceeded` once per budget period that goes over and
+/// recalculate the projections
+pub fn start_budget_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut exceeded: HashSet<(BudgetPeriod, i64)> = HashSet::new();
+
+        loop {
+            let settings = load_budget_settings(&app);
+            let statuses = if settings.enabled {
+                match evaluate_budgets(&app, &settings).await {
+                    Ok(statuses) => Some(statuses),
+                    Err(e) => {
+                        eprintln!("Budget check failed: {}", e);
+                        None
+                    }
+                }
+            } else {
+                Some(Vec::new())
+            };
+
+            // Keep the last known statuses while Prometheus is unreachable
+            if let Some(statuses) = statuses {
+                for status in &statuses {
+                    if status.status == BudgetState::Exceeded
+                        && exceeded.insert((status.period, status.period_start))
+                    {
+                        publish(&app, "budget-exceeded", status.clone());
+                    }
+                }
+                store_statuses(&statuses);
+                let _ = app.emit("budget-updated", &statuses);
+
+                match project_budgets(&app, &statuses).await {
+                    Ok(projections) => store_projections(&projections),
+                    Err(e) => eprintln!("Budget projection failed: {}", e),
+                }
+            }
+
+            let interval = settings.poll_interval_seconds.max(30) as u64;
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_budget_settings(app: AppHandle) -> Result<BudgetSettings, AppError> {
+    Ok(load_budget_settings(&app))
+}
+
+#[tauri::command]
+pub async fn set_budget_settings(
+    app: AppHandle,
+    settings: BudgetSettings,
+) -> Result<Vec<BudgetStatus>, AppError> {
+    let limits = [
+        settings.daily_usd,
+        settings.weekly_usd,
+        settings.monthly_usd,
+    ];
+    if limits.into_iter().flatten().any(|limit| limit <= 0.0) {
+        return Err(AppError::InvalidInput(
+            "Budgets must be greater than zero".to_string(),
+        ));
+    }
+    if !(0.0..=100.0).contains(&settings.warning_percent) {
+        return Err(AppError::InvalidInput(
+            "Warning threshold must be between 0 and 100%".to_string(),
+        ));
+    }
+    save_setting(&app, BUDGETS_KEY, &settings)?;
+
+    // Refresh the tray right away rather than at the next poll
+    let statuses = if settings.enabled {
+        evaluate_budgets(&app, &settings).await?
+    } else {
+        Vec::new()
+    };
+    store_statuses(&statuses);
+    store_projections(&project_budgets(&app, &statuses).await.unwrap_or_default());
+    Ok(statuses)
+}
+
+/// Spend, remaining amount and status for each configured budget
+#[tauri::command]
+pub async fn get_budget_status(app: AppHandle) -> Result<Vec<BudgetStatus>, AppError> {
+    let settings = load_budget_settings(&app);
+    if !settings.enabled {
+        return Ok(Vec::new());
+    }
+    let statuses = evaluate_budgets(&app, &settings).await?;
+    store_statuses(&statuses);
+    Ok(statuses)
+}
+
+/// When each budget will run out at the current pace, as of the last scheduled
+/// check (calculated now if none has finished yet)
+#[tauri::command]
+pub async fn get_budget_projection(app: AppHandle) -> Result<Vec<BudgetProjection>, AppError> {
+    let cached = PROJECTIONS.lock().map(|p| p.clone()).unwrap_or_default();
+    if !cached.is_empty() {
+        return Ok(cached);
+    }
+    let settings = load_budget_settings(&app);
+    if !settings.enabled {
+        return Ok(Vec::new());
+    }
+    let statuses = evaluate_budgets(&app, &settings).await?;
+    let projections = project_budgets(&app, &statuses).await?;
+    store_projections(&projections);
+    Ok(projections)
+}
@@ -0,0 +1,201 @@
+// tauri-app/src-tauri/src/claude_storage.rs
+
+use crate::history_index::{get_history_path, with_history_index};
+use crate::local_data::{collect_files, remove_empty_dirs, PurgeResult};
+use crate::processes::scan_claude_processes;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage {
+    pub name: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: u32,
+    pub bytes_older_than_30_days: u64,
+    pub bytes_older_than_90_days: u64,
+    pub oldest_modified: Option<i64>,
+    pub newest_modified: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeStorageReport {
+    pub root: String,
+    pub total_bytes: u64,
+    /// Top-level entries of ~/.claude (projects, todos, statsig, ...)
+    pub categories: Vec<StorageUsage>,
+    /// Transcript directories under ~/.claude/projects
+    pub projects: Vec<StorageUsage>,
+}
+
+fn claude_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".claude"))
+}
+
+/// Claude Code names transcript folders after the project path with every
+/// non-alphanumeric character replaced by '-'
+fn encode_project_path(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn to_unix_secs(time: SystemTime) -> Option<i64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs() as i64)
+}
+
+fn usage_for(name: String, path: &Path) -> StorageUsage {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        collect_files(path, &mut files);
+    } else if let Ok(metadata) = fs::metadata(path) {
+        files.push((
+            path.to_path_buf(),
+            metadata.len(),
+            metadata.modified().unwrap_or(UNIX_EPOCH),
+        ));
+    }
+
+    let now = SystemTime::now();
+    let older_than = |days: u32| -> u64 {
+        files
+            .iter()
+            .filter(|(_, _, modified)| *modified < now - DAY * days)
+            .map(|(_, size, _)| size)
+            .sum()
+    };
+
+    StorageUsage {
+        name,
+        path: path.to_string_lossy().to_string(),
+        total_bytes: files.iter().map(|(_, size, _)| size).sum(),
+        file_count: files.len() as u32,
+        bytes_older_than_30_days: older_than(30),
+        bytes_older_than_90_days: older_than(90),
+        oldest_modified: files.iter().filter_map(|(_, _, m)| to_unix_secs(*m)).min(),
+        newest_modified: files.iter().filter_map(|(_, _, m)| to_unix_secs(*m)).max(),
+    }
+}
+
+/// Map encoded transcript folder names back to project paths seen in history.jsonl
+pub(crate) fn project_names() -> HashMap<String, String> {
+    let Some(history_path) = get_history_path() else {
+        return HashMap::new();
+    };
+    with_history_index(&history_path, |index| {
+        index
+            .sessions
+            .iter()
+            .map(|s| (encode_project_path(&s.project_path), s.project_path.clone()))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn scan_claude_storage() -> Result<ClaudeStorageReport, String> {
+    let root = claude_dir().ok_or("Could not find home directory")?;
+    let entries = fs::read_dir(&root).map_err(|_| "Claude directory not found")?;
+
+    let mut categories: Vec<StorageUsage> = entries
+        .flatten()
+        .map(|entry| {
+            usage_for(
+                entry.file_name().to_string_lossy().to_string(),
+                &entry.path(),
+            )
+        })
+        .collect();
+    categories.sort_by_key(|c| std::cmp::Reverse(c.total_bytes));
+
+    let names = project_names();
+    let mut projects: Vec<StorageUsage> = fs::read_dir(root.join("projects"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| {
+                    let folder = entry.file_name().to_string_lossy().to_string();
+                    let name = names.get(&folder).cloned().unwrap_or(folder);
+                    usage_for(name, &entry.path())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    projects.sort_by_key(|p| std::cmp::Reverse(p.total_bytes));
+
+    Ok(ClaudeStorageReport {
+        root: root.to_string_lossy().to_string(),
+        total_bytes: categories.iter().map(|c| c.total_bytes).sum(),
+        categories,
+        projects,
+    })
+}
+
+/// Delete transcripts under ~/.claude/projects last modified more than
+/// `older_than_days` ago. Transcripts of sessions with a running Claude process
+/// are never removed.
+fn trim_transcripts(older_than_days: u32, dry_run: bool) -> Result<PurgeResult, String> {
+    if older_than_days == 0 {
+        return Err("Transcripts must be at least one day old to be trimmed".to_string());
+    }
+    let projects_dir = claude_dir()
+        .ok_or("Could not find home directory")?
+        .join("projects");
+
+    let live_sessions: HashSet<String> = scan_claude_processes()
+        .into_iter()
+        .filter_map(|p| p.session_id)
+        .collect();
+    let cutoff = SystemTime::now() - DAY * older_than_days;
+
+    let mut files = Vec::new();
+    collect_files(&projects_dir, &mut files);
+
+    let mut result = PurgeResult::default();
+    for (path, size, modified) in files {
+        if modified >= cutoff || path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        let session_id = path.file_stem().map(|s| s.to_string_lossy().to_string());
+        if session_id.is_some_and(|id| live_sessions.contains(&id)) {
+            continue;
+        }
+        if dry_run || fs::remove_file(&path).is_ok() {
+            result.files_removed += 1;
+            result.bytes_freed += size;
+        }
+    }
+
+    if !dry_run {
+        remove_empty_dirs(&projects_dir);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_claude_storage_usage() -> Result<ClaudeStorageReport, String> {
+    tauri::async_runtime::spawn_blocking(scan_claude_storage)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// With `dry_run` (the default) only reports what would be removed
+#[tauri::command]
+pub async fn trim_claude_transcripts(
+    older_than_days: u32,
+    dry_run: Option<bool>,
+) -> Result<PurgeResult, String> {
+    let dry_run = dry_run.unwrap_or(true);
+    tauri::async_runtime::spawn_blocking(move || trim_transcripts(older_than_days, dry_run))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -1,7 +1,27 @@
-use crate::metrics::{DashboardMetrics, ModelTokens, TimeSeriesPoint};
-use crate::prometheus::PrometheusClient;
+use crate::endpoints::{endpoint_urls, merge_dashboard_metrics};
+use crate::error::AppError;
+use crate::filters::{LabelFilters, FILTER_LABELS};
+use crate::metrics::{
+    compare_versions, output_throughput, rank_model_costs, DashboardMetrics, EndpointStatus,
+    LabeledSeries, MetricsSource, ModelCost, ModelTokens, OutdatedHost, TimeSeriesPoint, TokenType,
+    TokenTypeCost, TokenTypeSeries, UsageBreakdown, VersionBreakdown, VersionUsage,
+};
+use crate::offline::local_dashboard_metrics;
+use crate::pricing::resolve_profile;
+use crate::prometheus::{
+    ConnectionOptions, MetricMetadata, PrometheusClient, QueryResult, RuleGroup, ScrapeTarget,
+};
 use crate::prometheus_health::{fetch_prometheus_health, PrometheusHealthMetrics};
+use crate::query_tuning::{format_duration, load_query_tuning, QueryTuning};
+use crate::settings::frontend_pricing_provider;
+use crate::timezone;
+use crate::uptime::record_check;
+use chrono::Duration;
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
 
 fn time_range_to_seconds(range: &str) -> i64 {
     match range {
@@ -29,114 +49,436 @@ fn time_range_to_promql(range: &str) -> &str {
     }
 }
 
-#[tauri::command]
-pub async fn get_dashboard_metrics(
-    time_range: String,
-    prometheus_url: String,
+pub(crate) fn custom_bounds(
     custom_start: Option<i64>,
     custom_end: Option<i64>,
-) -> Result<DashboardMetrics, String> {
-    let client = PrometheusClient::new(&prometheus_url);
+) -> Result<(i64, i64), AppError> {
+    let start =
+        custom_start.ok_or_else(|| AppError::InvalidInput("Custom start time required".into()))?;
+    let end =
+        custom_end.ok_or_else(|| AppError::InvalidInput("Custom end time required".into()))?;
+    Ok((start, end))
+}
 
+/// Resolve a preset or custom time range into (start, end, PromQL range string)
+pub(crate) fn resolve_time_range(
+    time_range: &str,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<(i64, i64, String), AppError> {
     // Determine if we're using custom range or preset
-    let (start_time, end_time, range_str) = if time_range == "custom" {
-        let start = custom_start.ok_or("Custom start time required")?;
-        let end = custom_end.ok_or("Custom end time required")?;
-        let duration_secs = end - start;
-        // Create a range string for Prometheus (e.g., "86400s" for 1 day)
-        let range = format!("{}s", duration_secs);
-        (start, end, range)
+    if time_range == "custom" {
+        let (start, end) = custom_bounds(custom_start, custom_end)?;
+        if end <= start {
+            return Err(AppError::InvalidInput(
+                "Custom end time must be after the start time".to_string(),
+            ));
+        }
+        // Create a range string for Prometheus (e.g., "1d13h" for 37 hours)
+        Ok((start, end, format_duration(end - start)))
+    } else if time_range == "today" || time_range == "yesterday" {
+        // Calendar days start at midnight in the configured timezone rather than 24h before now
+        let today = timezone::today();
+        let today_start = timezone::start_of_day(today);
+        let (start, end) = if time_range == "today" {
+            (today_start, chrono::Utc::now().timestamp())
+        } else {
+            (
+                timezone::start_of_day(today - Duration::days(1)),
+                today_start,
+            )
+        };
+        Ok((start, end, format_duration(end - start)))
     } else {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
-        let duration = time_range_to_seconds(&time_range);
+        let duration = time_range_to_seconds(time_range);
         let start = now - duration;
-        (start, now, time_range_to_promql(&time_range).to_string())
-    };
+        Ok((start, now, time_range_to_promql(time_range).to_string()))
+    }
+}
 
-    let range = &range_str;
+/// Evaluation time for a range's totals; ranges ending now need no anchor
+pub(crate) fn range_anchor(time_range: &str, end_time: i64) -> Option<i64> {
+    matches!(time_range, "custom" | "yesterday").then_some(end_time)
+}
+
+/// Pick range-query resolution based on the time range
+/// 15m, 1h -> 1 minute intervals with 5m rate window
+/// 4h -> 5 minute intervals with 5m rate window
+/// 1d, today, yesterday -> 1 hour intervals with 1h rate window
+/// 7d -> 6 hour intervals with 6h rate window
+/// 30d -> 1 day intervals with 1d rate window
+/// 90d -> 3 day intervals with 3d rate window
+pub(crate) fn step_and_rate_window(
+    time_range: &str,
+    start_time: i64,
+    end_time: i64,
+) -> (&'static str, &'static str) {
+    match time_range {
+        "15m" => ("1m", "5m"),
+        "1h" => ("1m", "5m"),
+        "4h" => ("5m", "5m"),
+        "1d" | "today" | "yesterday" => ("1h", "1h"),
+        "7d" => ("6h", "6h"),
+        "30d" => ("1d", "1d"),
+        "90d" => ("3d", "3d"),
+        "custom" => {
+            // For custom ranges, choose step and rate window based on duration
+            let duration = end_time - start_time;
+            if duration <= 3600 {
+                ("1m", "5m")
+            } else if duration <= 4 * 3600 {
+                ("5m", "5m")
+            } else if duration <= 24 * 3600 {
+                ("1h", "1h")
+            } else if duration <= 7 * 24 * 3600 {
+                ("6h", "6h")
+            } else if duration <= 30 * 24 * 3600 {
+                ("1d", "1d")
+            } else {
+                ("3d", "3d")
+            }
+        }
+        _ => ("1m", "5m"),
+    }
+}
+
+/// Claude Code exports metrics every 60s and Prometheus scrapes on its own
+/// interval, so the most recent couple of minutes are always incomplete
+const INGESTION_LAG_SECONDS: i64 = 120;
+
+/// Window for the "live" burn rate on the dashboard
+const BURN_RATE_WINDOW: &str = "15m";
+
+/// Convert a range query result's values into time series points, marking the
+/// trailing buckets that haven't been fully ingested yet as partial
+pub(crate) fn to_time_series(result: &QueryResult) -> Vec<TimeSeriesPoint> {
+    let complete_before = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+        - INGESTION_LAG_SECONDS;
+
+    result
+        .values
+        .as_ref()
+        .map(|values| {
+            values
+                .iter()
+                .map(|(ts, v)| TimeSeriesPoint {
+                    timestamp: *ts as i64,
+                    value: v.parse::<f64>().unwrap_or(0.0),
+                    partial: *ts as i64 > complete_before,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn breakdown_entry<'a>(
+    breakdown: &'a mut HashMap<String, UsageBreakdown>,
+    label: &str,
+    r: &QueryResult,
+) -> &'a mut UsageBreakdown {
+    let key = r
+        .metric
+        .get(label)
+        .cloned()
+        .unwrap_or_else(|| "unknown".to_string());
+    breakdown
+        .entry(key.clone())
+        .or_insert_with(|| UsageBreakdown {
+            label: key,
+            ..Default::default()
+        })
+}
+
+/// Sum tokens, cost and sessions over a range, grouped by the given label
+pub(crate) async fn fetch_usage_breakdown(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    label: &str,
+    range: &str,
+    anchor: Option<i64>,
+    filters: &LabelFilters,
+) -> Result<Vec<UsageBreakdown>, AppError> {
+    let mut breakdown: HashMap<String, UsageBreakdown> = HashMap::new();
 
-    // Query for total tokens
     let tokens_query = format!(
-        "sum(increase(claude_code_token_usage_tokens_total[{}]))",
-        range
+        "sum by ({}) (increase({}{}))",
+        label,
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.selector("claude_code_token_usage_tokens_total", range, anchor)
     );
-    let total_tokens = client
-        .query(&tokens_query)
-        .await
-        .map_err(|e| e.to_string())?
-        .first()
-        .and_then(|r| r.value.as_ref())
-        .and_then(|(_, v)| v.parse::<f64>().ok())
-        .unwrap_or(0.0) as u64;
+    for r in client.query(&tokens_query).await? {
+        let tokens = r
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse::<f64>().ok())
+            .unwrap_or(0.0) as u64;
+        breakdown_entry(&mut breakdown, label, &r).tokens += tokens;
+    }
 
-    // Query for input tokens
-    let input_query = format!(
-        "sum(increase(claude_code_token_usage_tokens_total{{type=\"input\"}}[{}]))",
-        range
+    let cost_query = format!(
+        "sum by ({}) (increase({}{}))",
+        label,
+        filters.selector("claude_code_cost_usage_USD_total", &[]),
+        tuning.selector("claude_code_cost_usage_USD_total", range, anchor)
     );
-    let input_tokens = client
-        .query(&input_query)
-        .await
-        .map_err(|e| e.to_string())?
-        .first()
-        .and_then(|r| r.value.as_ref())
-        .and_then(|(_, v)| v.parse::<f64>().ok())
-        .unwrap_or(0.0) as u64;
+    for r in client.query(&cost_query).await? {
+        let cost_usd = r
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        breakdown_entry(&mut breakdown, label, &r).cost_usd += cost_usd;
+    }
 
-    // Query for output tokens
-    let output_query = format!(
-        "sum(increase(claude_code_token_usage_tokens_total{{type=\"output\"}}[{}]))",
-        range
+    let sessions_query = format!(
+        "sum by ({}) (increase({}{}))",
+        label,
+        filters.selector("claude_code_session_count_total", &[]),
+        tuning.selector("claude_code_session_count_total", range, anchor)
     );
-    let output_tokens = client
-        .query(&output_query)
-        .await
-        .map_err(|e| e.to_string())?
-        .first()
-        .and_then(|r| r.value.as_ref())
-        .and_then(|(_, v)| v.parse::<f64>().ok())
-        .unwrap_or(0.0) as u64;
+    for r in client.query(&sessions_query).await? {
+        let sessions = r
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse::<f64>().ok())
+            .unwrap_or(0.0)
+            .round() as u64;
+        breakdown_entry(&mut breakdown, label, &r).sessions += sessions;
+    }
 
-    // Query for cache read tokens (try both naming conventions)
-    let cache_read_query = format!(
-        "sum(increase(claude_code_token_usage_tokens_total{{type=~\"cache_read|cacheRead\"}}[{}]))",
-        range
+    let mut result: Vec<UsageBreakdown> = breakdown.into_values().collect();
+    result.sort_by_key(|b| std::cmp::Reverse(b.tokens));
+    Ok(result)
+}
+
+/// Generation of the latest `get_dashboard_metrics` call. Starting a new call
+/// cancels any older one still waiting on Prometheus.
+pub struct DashboardRequests {
+    latest: watch::Sender<u64>,
+}
+
+impl DashboardRequests {
+    pub fn new() -> Self {
+        Self {
+            latest: watch::channel(0).0,
+        }
+    }
+
+    fn begin(&self) -> (u64, watch::Receiver<u64>) {
+        let mut generation = 0;
+        self.latest.send_modify(|latest| {
+            *latest += 1;
+            generation = *latest;
+        });
+        (generation, self.latest.subscribe())
+    }
+}
+
+/// Dashboard metrics tagged with the request generation. Superseded calls fail
+/// so their stale results never reach the UI.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_dashboard_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+    additional_urls: Option<Vec<String>>,
+    max_points: Option<usize>,
+    filters: Option<LabelFilters>,
+) -> Result<DashboardMetrics, AppError> {
+    let (generation, mut latest) = app.state::<DashboardRequests>().begin();
+    let load = load_dashboard_metrics(
+        app.clone(),
+        time_range,
+        prometheus_url,
+        custom_start,
+        custom_end,
+        additional_urls,
+        filters.unwrap_or_default(),
     );
-    let cache_read_tokens = client
-        .query(&cache_read_query)
-        .await
-        .map_err(|e| e.to_string())?
-        .first()
-        .and_then(|r| r.value.as_ref())
-        .and_then(|(_, v)| v.parse::<f64>().ok())
-        .unwrap_or(0.0) as u64;
 
-    // Query for cache creation tokens (try both naming conventions)
-    let cache_creation_query = format!(
-        "sum(increase(claude_code_token_usage_tokens_total{{type=~\"cache_creation|cacheCreation\"}}[{}]))",
-        range
+    tokio::select! {
+        result = load => result.map(|mut metrics| {
+            if let Some(max_points) = max_points {
+                metrics.downsample(max_points);
+            }
+            DashboardMetrics {
+                generation,
+                ..metrics
+            }
+        }),
+        _ = latest.wait_for(|latest| *latest != generation) => {
+            Err(AppError::Cancelled("Superseded by a newer dashboard request".to_string()))
+        }
+    }
+}
+
+/// Query every configured endpoint concurrently and sum the results. When no
+/// endpoint can be reached, falls back to local stats (`source: "local"`).
+pub(crate) async fn load_dashboard_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+    additional_urls: Option<Vec<String>>,
+    filters: LabelFilters,
+) -> Result<DashboardMetrics, AppError> {
+    let urls = endpoint_urls(&prometheus_url, additional_urls);
+    // Dropping the set (e.g. when superseded) aborts queries still in flight
+    let mut tasks = JoinSet::new();
+    for (index, url) in urls.iter().enumerate() {
+        let task = fetch_dashboard_metrics(
+            app.clone(),
+            time_range.clone(),
+            url.clone(),
+            custom_start,
+            custom_end,
+            filters.clone(),
+        );
+        tasks.spawn(async move { (index, task.await) });
+    }
+
+    let mut results: Vec<Option<Result<DashboardMetrics, AppError>>> = vec![None; urls.len()];
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, result)) = joined {
+            results[index] = Some(result);
+        }
+    }
+
+    let mut merged = Vec::new();
+    let mut endpoints = Vec::new();
+    let mut first_error = None;
+    for (url, result) in urls.into_iter().zip(results) {
+        let result = result.unwrap_or(Err(AppError::Other("Request task failed".to_string())));
+        endpoints.push(EndpointStatus {
+            url,
+            connected: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        match result {
+            Ok(metrics) => merged.push(metrics),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    let mut metrics = match merge_dashboard_metrics(merged) {
+        Some(metrics) => metrics,
+        None => {
+            let error = first_error
+                .unwrap_or_else(|| AppError::InvalidInput("No Prometheus URL configured".into()));
+            // Prometheus being down shouldn't blank the dashboard; estimate from
+            // local data instead and keep the original error if that fails too
+            // Local data has no labels to filter on
+            let unreachable = matches!(error, AppError::ConnectionFailed(_) | AppError::Timeout(_));
+            if !unreachable || !filters.is_empty() {
+                return Err(error);
+            }
+            local_dashboard_metrics(app, &time_range, custom_start, custom_end)
+                .await
+                .map_err(|_| error)?
+        }
+    };
+    metrics.endpoints = endpoints;
+    Ok(metrics)
+}
+
+async fn fetch_dashboard_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+    filters: LabelFilters,
+) -> Result<DashboardMetrics, AppError> {
+    let tuning = load_query_tuning(&app);
+    let client =
+        PrometheusClient::new(&prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+
+    let (start_time, end_time, range_str) =
+        resolve_time_range(&time_range, custom_start, custom_end)?;
+
+    let range = &range_str;
+    let anchor = range_anchor(&time_range, end_time);
+
+    // Query for total tokens
+    let tokens_query = format!(
+        "sum(increase({}{}))",
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.selector("claude_code_token_usage_tokens_total", range, anchor)
     );
-    let cache_creation_tokens = client
-        .query(&cache_creation_query)
-        .await
-        .map_err(|e| e.to_string())?
+    let total_tokens = client
+        .query(&tokens_query)
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
         .unwrap_or(0.0) as u64;
 
+    // Query tokens by type and model in one go; the label values are normalized
+    // below and each model's share is priced at its own rates
+    let type_query = format!(
+        "sum by (type, model) (increase({}{}))",
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.selector("claude_code_token_usage_tokens_total", range, anchor)
+    );
+    let pricing = resolve_profile(&app, &frontend_pricing_provider(&app));
+    let (mut input_tokens, mut output_tokens) = (0, 0);
+    let (mut cache_read_tokens, mut cache_creation_tokens) = (0, 0);
+    let mut cost_by_token_type = TokenTypeCost::default();
+    for result in client.query(&type_query).await? {
+        let tokens = result
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        let Some(token_type) = result.metric.get("type").and_then(|t| TokenType::parse(t)) else {
+            continue;
+        };
+        let model = result.metric.get("model").cloned().unwrap_or_default();
+        cost_by_token_type.add(token_type, tokens, &pricing.rates_for(&model));
+        match token_type {
+            TokenType::Input => input_tokens += tokens as u64,
+            TokenType::Output => output_tokens += tokens as u64,
+            TokenType::CacheRead => cache_read_tokens += tokens as u64,
+            TokenType::CacheCreation => cache_creation_tokens += tokens as u64,
+        }
+    }
+
     // Query for total cost
     let cost_query = format!(
-        "sum(increase(claude_code_cost_usage_USD_total[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector("claude_code_cost_usage_USD_total", &[]),
+        tuning.selector("claude_code_cost_usage_USD_total", range, anchor)
     );
     let total_cost_usd = client
         .query(&cost_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
+        .first()
+        .and_then(|r| r.value.as_ref())
+        .and_then(|(_, v)| v.parse::<f64>().ok())
+        .unwrap_or(0.0);
+
+    // Query the current spend rate over a short window, in USD per hour
+    let burn_rate_query = format!(
+        "sum(rate({}[{}])) * 3600",
+        filters.selector("claude_code_cost_usage_USD_total", &[]),
+        tuning.rate_window("claude_code_cost_usage_USD_total", BURN_RATE_WINDOW)
+    );
+    let burn_rate_usd_per_hour = client
+        .query_at(&burn_rate_query, anchor)
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -144,13 +486,13 @@ pub async fn get_dashboard_metrics(
 
     // Query for active time
     let time_query = format!(
-        "sum(increase(claude_code_active_time_seconds_total[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector("claude_code_active_time_seconds_total", &[]),
+        tuning.selector("claude_code_active_time_seconds_total", range, anchor)
     );
     let active_time_seconds = client
         .query(&time_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -158,13 +500,13 @@ pub async fn get_dashboard_metrics(
 
     // Query for session count
     let session_query = format!(
-        "sum(increase(claude_code_session_count_total[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector("claude_code_session_count_total", &[]),
+        tuning.selector("claude_code_session_count_total", range, anchor)
     );
     let session_count = client
         .query(&session_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -172,13 +514,13 @@ pub async fn get_dashboard_metrics(
 
     // Query for lines added
     let lines_added_query = format!(
-        "sum(increase(claude_code_lines_of_code_count_total{{type=\"added\"}}[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector("claude_code_lines_of_code_count_total", &["type=\"added\""]),
+        tuning.selector("claude_code_lines_of_code_count_total", range, anchor)
     );
     let lines_added = client
         .query(&lines_added_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -186,13 +528,16 @@ pub async fn get_dashboard_metrics(
 
     // Query for lines removed
     let lines_removed_query = format!(
-        "sum(increase(claude_code_lines_of_code_count_total{{type=\"removed\"}}[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector(
+            "claude_code_lines_of_code_count_total",
+            &["type=\"removed\""]
+        ),
+        tuning.selector("claude_code_lines_of_code_count_total", range, anchor)
     );
     let lines_removed = client
         .query(&lines_removed_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -200,13 +545,13 @@ pub async fn get_dashboard_metrics(
 
     // Query for commit count
     let commit_query = format!(
-        "sum(increase(claude_code_commit_count_total[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector("claude_code_commit_count_total", &[]),
+        tuning.selector("claude_code_commit_count_total", range, anchor)
     );
     let commit_count = client
         .query(&commit_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -214,13 +559,13 @@ pub async fn get_dashboard_metrics(
 
     // Query for PR count
     let pr_query = format!(
-        "sum(increase(claude_code_pull_request_count_total[{}]))",
-        range
+        "sum(increase({}{}))",
+        filters.selector("claude_code_pull_request_count_total", &[]),
+        tuning.selector("claude_code_pull_request_count_total", range, anchor)
     );
     let pull_request_count = client
         .query(&pr_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .first()
         .and_then(|r| r.value.as_ref())
         .and_then(|(_, v)| v.parse::<f64>().ok())
@@ -228,13 +573,13 @@ pub async fn get_dashboard_metrics(
 
     // Query for tokens by model
     let model_query = format!(
-        "sum by (model) (increase(claude_code_token_usage_tokens_total[{}]))",
-        range
+        "sum by (model) (increase({}{}))",
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.selector("claude_code_token_usage_tokens_total", range, anchor)
     );
     let tokens_by_model: Vec<ModelTokens> = client
         .query(&model_query)
-        .await
-        .map_err(|e| e.to_string())?
+        .await?
         .iter()
         .filter_map(|r| {
             let model = r.metric.get("model")?.clone();
@@ -243,64 +588,176 @@ pub async fn get_dashboard_metrics(
         })
         .collect();
 
-    // Query for tokens over time with resolution based on time range
-    // 15m, 1h -> 1 minute intervals with 5m rate window
-    // 4h -> 5 minute intervals with 5m rate window
-    // 1d -> 1 hour intervals with 1h rate window
-    // 7d -> 6 hour intervals with 6h rate window
-    // 30d -> 1 day intervals with 1d rate window
-    // 90d -> 3 day intervals with 3d rate window
-    let (step, rate_window) = match time_range.as_str() {
-        "15m" => ("1m", "5m"),
-        "1h" => ("1m", "5m"),
-        "4h" => ("5m", "5m"),
-        "1d" => ("1h", "1h"),
-        "7d" => ("6h", "6h"),
-        "30d" => ("1d", "1d"),
-        "90d" => ("3d", "3d"),
-        "custom" => {
-            // For custom ranges, choose step and rate window based on duration
-            let duration = end_time - start_time;
-            if duration <= 3600 {
-                ("1m", "5m")
-            } else if duration <= 4 * 3600 {
-                ("5m", "5m")
-            } else if duration <= 24 * 3600 {
-                ("1h", "1h")
-            } else if duration <= 7 * 24 * 3600 {
-                ("6h", "6h")
-            } else if duration <= 30 * 24 * 3600 {
-                ("1d", "1d")
-            } else {
-                ("3d", "3d")
-            }
-        }
-        _ => ("1m", "5m"),
-    };
+    // Query for cost by model
+    let model_cost_query = format!(
+        "sum by (model) (increase({}{}))",
+        filters.selector("claude_code_cost_usage_USD_total", &[]),
+        tuning.selector("claude_code_cost_usage_USD_total", range, anchor)
+    );
+    let mut cost_by_model: Vec<ModelCost> = client
+        .query(&model_cost_query)
+        .await?
+        .iter()
+        .filter_map(|r| {
+            let model = r.metric.get("model")?.clone();
+            let cost_usd = r.value.as_ref()?.1.parse::<f64>().ok()?;
+            Some(ModelCost {
+                model,
+                cost_usd,
+                percentage: 0.0,
+            })
+        })
+        .collect();
+    rank_model_costs(&mut cost_by_model);
+
+    // Query usage by terminal/editor (VS Code, iTerm, etc.)
+    let usage_by_terminal =
+        fetch_usage_breakdown(&client, &tuning, "terminal_type", range, anchor, &filters).await?;
+
+    let (step, rate_window) = step_and_rate_window(&time_range, start_time, end_time);
 
     // Query rate per step interval using rate() with window matching step size
     // This gives us per-second rate, frontend does cumulative sum and scales to match total
-    let range_query = format!("sum(rate(claude_code_cost_usage_USD_total[{}]))", rate_window);
+    let cost_range_query = format!(
+        "sum(rate({}[{}]))",
+        filters.selector("claude_code_cost_usage_USD_total", &[]),
+        tuning.rate_window("claude_code_cost_usage_USD_total", rate_window)
+    );
+    let cost_over_time: Vec<TimeSeriesPoint> = client
+        .query_range(&cost_range_query, start_time, end_time, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
+    let burn_rate_over_time: Vec<TimeSeriesPoint> = cost_over_time
+        .iter()
+        .map(|p| TimeSeriesPoint {
+            value: p.value * 3600.0,
+            ..*p
+        })
+        .collect();
 
-    let query_result = client
-        .query_range(&range_query, start_time, end_time, step)
-        .await
-        .map_err(|e| e.to_string())?;
+    let tokens_range_query = format!(
+        "sum(rate({}[{}]))",
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.rate_window("claude_code_token_usage_tokens_total", rate_window)
+    );
+    let tokens_over_time: Vec<TimeSeriesPoint> = client
+        .query_range(&tokens_range_query, start_time, end_time, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
 
-    let tokens_over_time: Vec<TimeSeriesPoint> = query_result
+    // Query lines of code changed per bucket (increase over the step window)
+    let lines_added_range_query = format!(
+        "sum(increase({}[{}]))",
+        filters.selector("claude_code_lines_of_code_count_total", &["type=\"added\""]),
+        tuning.rate_window("claude_code_lines_of_code_count_total", rate_window)
+    );
+    let lines_added_over_time: Vec<TimeSeriesPoint> = client
+        .query_range(&lines_added_range_query, start_time, end_time, step)
+        .await?
         .first()
-        .and_then(|r| r.values.as_ref())
-        .map(|values| {
-            values
-                .iter()
-                .map(|(ts, v)| TimeSeriesPoint {
-                    timestamp: *ts as i64,
-                    value: v.parse::<f64>().unwrap_or(0.0),
-                })
-                .collect()
-        })
+        .map(to_time_series)
+        .unwrap_or_default();
+
+    let lines_removed_range_query = format!(
+        "sum(increase({}[{}]))",
+        filters.selector(
+            "claude_code_lines_of_code_count_total",
+            &["type=\"removed\""]
+        ),
+        tuning.rate_window("claude_code_lines_of_code_count_total", rate_window)
+    );
+    let lines_removed_over_time: Vec<TimeSeriesPoint> = client
+        .query_range(&lines_removed_range_query, start_time, end_time, step)
+        .await?
+        .first()
+        .map(to_time_series)
         .unwrap_or_default();
 
+    // Query sessions started per bucket
+    let sessions_range_query = format!(
+        "sum(increase({}[{}]))",
+        filters.selector("claude_code_session_count_total", &[]),
+        tuning.rate_window("claude_code_session_count_total", rate_window)
+    );
+    let sessions_over_time: Vec<TimeSeriesPoint> = client
+        .query_range(&sessions_range_query, start_time, end_time, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
+
+    // Query token usage per bucket grouped by Claude Code version
+    let version_range_query = format!(
+        "sum by (app_version) (increase({}[{}]))",
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.rate_window("claude_code_token_usage_tokens_total", rate_window)
+    );
+    let tokens_by_version_over_time: Vec<LabeledSeries> = client
+        .query_range(&version_range_query, start_time, end_time, step)
+        .await?
+        .iter()
+        .map(|r| LabeledSeries {
+            label: r
+                .metric
+                .get("app_version")
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string()),
+            points: to_time_series(r),
+        })
+        .collect();
+
+    // Query output tokens per active second per bucket, split by model. Active
+    // time carries no model label, so each model's output is divided by the
+    // total active time in the bucket.
+    let throughput_range_query = format!(
+        "sum by (model) (increase({}[{}])) / on() group_left() sum(increase({}[{}]))",
+        filters.selector("claude_code_token_usage_tokens_total", &["type=\"output\""]),
+        tuning.rate_window("claude_code_token_usage_tokens_total", rate_window),
+        filters.selector("claude_code_active_time_seconds_total", &[]),
+        tuning.rate_window("claude_code_active_time_seconds_total", rate_window)
+    );
+    let output_tokens_per_second_by_model: Vec<LabeledSeries> = client
+        .query_range(&throughput_range_query, start_time, end_time, step)
+        .await?
+        .iter()
+        .map(|r| LabeledSeries {
+            label: r
+                .metric
+                .get("model")
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string()),
+            points: to_time_series(r)
+                .into_iter()
+                .filter(|p| p.value.is_finite())
+                .collect(),
+        })
+        .collect();
+
+    // Query token rate split by type for the stacked composition chart
+    let type_range_query = format!(
+        "sum by (type) (rate({}[{}]))",
+        filters.selector("claude_code_token_usage_tokens_total", &[]),
+        tuning.rate_window("claude_code_token_usage_tokens_total", rate_window)
+    );
+    let mut tokens_by_type_over_time = TokenTypeSeries::default();
+    for result in client
+        .query_range(&type_range_query, start_time, end_time, step)
+        .await?
+    {
+        let series = to_time_series(&result);
+        match result.metric.get("type").and_then(|t| TokenType::parse(t)) {
+            Some(TokenType::Input) => tokens_by_type_over_time.input = series,
+            Some(TokenType::Output) => tokens_by_type_over_time.output = series,
+            Some(TokenType::CacheRead) => tokens_by_type_over_time.cache_read = series,
+            Some(TokenType::CacheCreation) => tokens_by_type_over_time.cache_creation = series,
+            None => {}
+        }
+    }
+
     Ok(DashboardMetrics {
         total_tokens,
         total_cost_usd,
@@ -311,35 +768,282 @@ pub async fn get_dashboard_metrics(
         commit_count,
         pull_request_count,
         tokens_by_model,
+        cost_by_model,
+        usage_by_terminal,
         tokens_over_time,
+        cost_over_time,
+        tokens_by_type_over_time,
+        lines_added_over_time,
+        lines_removed_over_time,
+        sessions_over_time,
+        tokens_by_version_over_time,
         input_tokens,
         output_tokens,
         cache_read_tokens,
         cache_creation_tokens,
+        cost_by_token_type,
+        output_tokens_per_second: output_throughput(output_tokens, active_time_seconds),
+        output_tokens_per_second_by_model,
+        burn_rate_usd_per_hour,
+        burn_rate_over_time,
+        endpoints: Vec::new(),
+        generation: 0,
+        source: MetricsSource::Prometheus,
+    })
+}
+
+/// Tokens, cost and sessions over the range grouped by `label`, largest first
+async fn label_breakdown(
+    app: &AppHandle,
+    label: &str,
+    time_range: &str,
+    prometheus_url: &str,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<Vec<UsageBreakdown>, AppError> {
+    let tuning = load_query_tuning(app);
+    let client =
+        PrometheusClient::new(prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let (_, end_time, range) = resolve_time_range(time_range, custom_start, custom_end)?;
+    let anchor = range_anchor(time_range, end_time);
+    fetch_usage_breakdown(
+        &client,
+        &tuning,
+        label,
+        &range,
+        anchor,
+        &LabelFilters::default(),
+    )
+    .await
+}
+
+/// Sessions, tokens and cost per terminal or editor (`terminal_type`, e.g.
+/// vscode, iTerm.app, tmux)
+#[tauri::command]
+pub async fn get_terminal_breakdown(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<Vec<UsageBreakdown>, AppError> {
+    label_breakdown(
+        &app,
+        "terminal_type",
+        &time_range,
+        &prometheus_url,
+        custom_start,
+        custom_end,
+    )
+    .await
+}
+
+/// Usage per Claude Code version (`app_version`), newest first, flagging hosts
+/// whose newest version in the range is behind the newest seen on any host
+#[tauri::command]
+pub async fn get_version_breakdown(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<VersionBreakdown, AppError> {
+    let tuning = load_query_tuning(&app);
+    let client =
+        PrometheusClient::new(&prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let (_, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let anchor = range_anchor(&time_range, end_time);
+
+    let usage = fetch_usage_breakdown(
+        &client,
+        &tuning,
+        "app_version",
+        &range,
+        anchor,
+        &LabelFilters::default(),
+    )
+    .await?;
+
+    // Hosts with any token usage on each version
+    let hosts_query = format!(
+        "sum by (app_version, host_name) (increase(claude_code_token_usage_tokens_total{})) > 0",
+        tuning.selector("claude_code_token_usage_tokens_total", &range, anchor)
+    );
+    let mut hosts_by_version: HashMap<String, Vec<String>> = HashMap::new();
+    let mut newest_by_host: HashMap<String, String> = HashMap::new();
+    for r in client.query(&hosts_query).await? {
+        let (Some(version), Some(host)) = (r.metric.get("app_version"), r.metric.get("host_name"))
+        else {
+            continue;
+        };
+        hosts_by_version
+            .entry(version.clone())
+            .or_default()
+            .push(host.clone());
+        let newest = newest_by_host
+            .entry(host.clone())
+            .or_insert_with(|| version.clone());
+        if compare_versions(version, newest).is_gt() {
+            *newest = version.clone();
+        }
+    }
+
+    let mut versions: Vec<VersionUsage> = usage
+        .into_iter()
+        .map(|u| {
+            let mut hosts = hosts_by_version.remove(&u.label).unwrap_or_default();
+            hosts.sort();
+            VersionUsage {
+                version: u.label,
+                tokens: u.tokens,
+                cost_usd: u.cost_usd,
+                sessions: u.sessions,
+                hosts,
+            }
+        })
+        .collect();
+    versions.sort_by(|a, b| compare_versions(&b.version, &a.version));
+
+    let latest_version = versions
+        .iter()
+        .map(|v| &v.version)
+        .find(|v| *v != "unknown")
+        .cloned();
+    let mut outdated_hosts: Vec<OutdatedHost> = newest_by_host
+        .into_iter()
+        .filter(|(_, version)| {
+            latest_version
+                .as_ref()
+                .is_some_and(|latest| compare_versions(version, latest).is_lt())
+        })
+        .map(|(host, version)| OutdatedHost { host, version })
+        .collect();
+    outdated_hosts
+        .sort_by(|a, b| compare_versions(&a.version, &b.version).then_with(|| a.host.cmp(&b.host)));
+
+    Ok(VersionBreakdown {
+        versions,
+        latest_version,
+        outdated_hosts,
     })
 }
 
+/// Usage per machine (`host_name`) for Prometheus fed by several hosts
 #[tauri::command]
-pub async fn test_connection(url: String) -> Result<bool, String> {
-    let client = PrometheusClient::new(&url);
-    client.test_connection().await.map_err(|e| e.to_string())
+pub async fn get_host_breakdown(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<Vec<UsageBreakdown>, AppError> {
+    label_breakdown(
+        &app,
+        "host_name",
+        &time_range,
+        &prometheus_url,
+        custom_start,
+        custom_end,
+    )
+    .await
 }
 
 #[tauri::command]
-pub async fn discover_metrics(url: String) -> Result<Vec<String>, String> {
-    let client = PrometheusClient::new(&url);
-    client.discover_metrics().await.map_err(|e| e.to_string())
+pub async fn test_connection(
+    app: AppHandle,
+    url: String,
+    connection: Option<ConnectionOptions>,
+) -> Result<bool, AppError> {
+    // Lets the settings page try connection options before saving them
+    let client = match connection {
+        Some(options) => PrometheusClient::with_options(&url, &options)?,
+        None => PrometheusClient::new(&url)?,
+    };
+    let result = client.test_connection().await.map_err(AppError::from);
+    record_check(
+        &app,
+        &url,
+        result.as_ref().is_ok_and(|up| *up),
+        result.as_ref().err().map(|e| e.to_string()),
+    );
+    result
+}
+
+#[tauri::command]
+pub async fn discover_metrics(url: String) -> Result<Vec<String>, AppError> {
+    let client = PrometheusClient::new(&url)?;
+    Ok(client.discover_metrics().await?)
+}
+
+/// Recording and alerting rule groups with their evaluation health. `rule_type`
+/// is "record" or "alert" to return only one kind.
+#[tauri::command]
+pub async fn get_prometheus_rules(
+    prometheus_url: String,
+    rule_type: Option<String>,
+) -> Result<Vec<RuleGroup>, AppError> {
+    let client = PrometheusClient::new(&prometheus_url)?;
+    Ok(client.rules(rule_type.as_deref()).await?)
+}
+
+/// Active scrape targets, unhealthy ones first
+#[tauri::command]
+pub async fn get_scrape_targets(prometheus_url: String) -> Result<Vec<ScrapeTarget>, AppError> {
+    let client = PrometheusClient::new(&prometheus_url)?;
+    let mut targets = client.scrape_targets().await?;
+    targets.sort_by(|a, b| {
+        (a.health == "up")
+            .cmp(&(b.health == "up"))
+            .then(a.scrape_pool.cmp(&b.scrape_pool))
+            .then(a.scrape_url.cmp(&b.scrape_url))
+    });
+    Ok(targets)
+}
+
+/// Values seen in the time range for a filter ("model", "userEmail", "host" or
+/// "project"), to populate the dashboard filter dropdowns
+#[tauri::command]
+pub async fn get_label_values(
+    prometheus_url: String,
+    filter: String,
+    time_range: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<Vec<String>, AppError> {
+    let label = FILTER_LABELS
+        .iter()
+        .find(|(name, _)| *name == filter)
+        .map(|(_, label)| *label)
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown filter: {}", filter)))?;
+    let (start_time, end_time, _) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let client = PrometheusClient::new(&prometheus_url)?;
+    Ok(client
+        .label_values(
+            label,
+            "claude_code_token_usage_tokens_total",
+            start_time,
+            end_time,
+        )
+        .await?)
+}
+
+#[tauri::command]
+pub async fn discover_metric_metadata(url: String) -> Result<Vec<MetricMetadata>, AppError> {
+    let client = PrometheusClient::new(&url)?;
+    Ok(client.discover_metric_metadata().await?)
 }
 
 #[tauri::command]
 pub async fn get_prometheus_health(
+    app: AppHandle,
     prometheus_url: String,
     time_range: Option<String>,
     custom_start: Option<i64>,
     custom_end: Option<i64>,
-) -> Result<PrometheusHealthMetrics, String> {
+    max_points: Option<usize>,
+) -> Result<PrometheusHealthMetrics, AppError> {
     println!("get_prometheus_health: starting");
-    let client = PrometheusClient::new(&prometheus_url);
+    let client = PrometheusClient::new(&prometheus_url)?;
 
     // Calculate time range for sparklines
     let now = std::time::SystemTime::now()
@@ -348,8 +1052,9 @@ pub async fn get_prometheus_health(
         .as_secs() as i64;
 
     let (start_time, end_time) = if time_range.as_deref() == Some("custom") {
-        let start = custom_start.ok_or("Custom start time required")?;
-        let end = custom_end.ok_or("Custom end time required")?;
+        custom_bounds(custom_start, custom_end)?
+    } else if let Some(range @ ("today" | "yesterday")) = time_range.as_deref() {
+        let (start, end, _) = resolve_time_range(range, None, None)?;
         (start, end)
     } else {
         let duration = match time_range.as_deref() {
@@ -366,7 +1071,18 @@ pub async fn get_prometheus_health(
     };
 
     println!("get_prometheus_health: calling fetch_prometheus_health");
-    let result = fetch_prometheus_health(&client, start_time, end_time).await;
+    let result = fetch_prometheus_health(&client, start_time, end_time)
+        .await
+        .map(|mut health| {
+            if let Some(max_points) = max_points {
+                health.downsample(max_points);
+            }
+            health
+        });
     println!("get_prometheus_health: fetch_prometheus_health returned");
+    match &result {
+        Ok(health) => record_check(&app, &prometheus_url, health.is_ready, None),
+        Err(e) => record_check(&app, &prometheus_url, false, Some(e.to_string())),
+    }
     result
 }
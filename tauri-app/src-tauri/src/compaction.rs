@@ -0,0 +1,182 @@
+// tauri-app/src-tauri/src/compaction.rs
+
+use crate::transcript_archive::{all_transcripts, find_transcript, TranscriptFile};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_SINCE_DAYS: u32 = 30;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompactMetadata {
+    trigger: Option<String>,
+    pre_tokens: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Usage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    usage: Option<Usage>,
+}
+
+/// The parts of a transcript line that matter for compaction tracking
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptLine {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    subtype: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    compact_metadata: Option<CompactMetadata>,
+    message: Option<Message>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactionEvent {
+    pub timestamp: Option<i64>,
+    /// "auto" or "manual"
+    pub trigger: Option<String>,
+    /// Context size just before compacting, when recorded
+    pub pre_tokens: Option<u64>,
+}
+
+/// Context size (input + cached prompt tokens) of one assistant turn
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextPoint {
+    pub timestamp: i64,
+    pub context_tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCompactions {
+    pub session_id: String,
+    pub project_folder: String,
+    pub compaction_count: u32,
+    pub auto_count: u32,
+    pub manual_count: u32,
+    pub peak_context_tokens: u64,
+    pub compactions: Vec<CompactionEvent>,
+    /// Empty in the summary listing
+    pub context_over_time: Vec<ContextPoint>,
+}
+
+fn scan_transcript(transcript: &TranscriptFile) -> Result<SessionCompactions, String> {
+    let reader = transcript.open()?;
+    let mut report = SessionCompactions {
+        session_id: transcript.session_id.clone(),
+        project_folder: transcript.project_folder.clone(),
+        compaction_count: 0,
+        auto_count: 0,
+        manual_count: 0,
+        peak_context_tokens: 0,
+        compactions: Vec::new(),
+        context_over_time: Vec::new(),
+    };
+
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<TranscriptLine>(&line) else {
+            continue;
+        };
+        let timestamp = entry.timestamp.map(|t| t.timestamp_millis());
+
+        // `type: "summary"` lines are session titles, not compactions
+        if entry.kind.as_deref() == Some("system")
+            && entry.subtype.as_deref() == Some("compact_boundary")
+        {
+            let metadata = entry.compact_metadata.unwrap_or_default();
+            match metadata.trigger.as_deref() {
+                Some("auto") => report.auto_count += 1,
+                Some("manual") => report.manual_count += 1,
+                _ => {}
+            }
+            report.compaction_count += 1;
+            report.compactions.push(CompactionEvent {
+                timestamp,
+                trigger: metadata.trigger,
+                pre_tokens: metadata.pre_tokens,
+            });
+            continue;
+        }
+
+        let usage = entry.message.and_then(|m| m.usage);
+        if let (Some(timestamp), Some(usage)) = (timestamp, usage) {
+            let context_tokens = usage.input_tokens
+                + usage.cache_read_input_tokens
+                + usage.cache_creation_input_tokens;
+            // Streamed messages repeat the same usage block
+            if report
+                .context_over_time
+                .last()
+                .is_some_and(|p| p.context_tokens == context_tokens)
+            {
+                continue;
+            }
+            report.peak_context_tokens = report.peak_context_tokens.max(context_tokens);
+            report.context_over_time.push(ContextPoint {
+                timestamp,
+                context_tokens,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Compactions and context size over time for one session
+#[tauri::command]
+pub async fn get_session_compactions(session_id: String) -> Result<SessionCompactions, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let transcript = find_transcript(&session_id)
+            .ok_or(format!("Transcript not found for session {}", session_id))?;
+        scan_transcript(&transcript)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Compaction counts for every session with transcript activity in the last
+/// `since_days`, most compacted first
+#[tauri::command]
+pub async fn get_compaction_summary(
+    since_days: Option<u32>,
+) -> Result<Vec<SessionCompactions>, String> {
+    let days = since_days.unwrap_or(DEFAULT_SINCE_DAYS).max(1);
+    tauri::async_runtime::spawn_blocking(move || {
+        let cutoff = SystemTime::now() - Duration::from_secs(days as u64 * 24 * 3600);
+        let mut sessions: Vec<SessionCompactions> = all_transcripts()
+            .iter()
+            .filter(|t| {
+                std::fs::metadata(&t.path)
+                    .and_then(|m| m.modified())
+                    .is_ok_and(|modified| modified >= cutoff)
+            })
+            .filter_map(|t| scan_transcript(t).ok())
+            .map(|mut report| {
+                report.context_over_time.clear();
+                report
+            })
+            .collect();
+        sessions.sort_by(|a, b| {
+            b.compaction_count
+                .cmp(&a.compaction_count)
+                .then(b.peak_context_tokens.cmp(&a.peak_context_tokens))
+        });
+        Ok(sessions)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
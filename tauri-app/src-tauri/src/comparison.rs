@@ -0,0 +1,351 @@
+// tauri-app/src-tauri/src/comparison.rs
+
+use crate::commands::{load_dashboard_metrics, resolve_time_range};
+use crate::filters::LabelFilters;
+use crate::insights::MetricComparison;
+use crate::metrics::{DashboardMetrics, TimeSeriesPoint};
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::load_query_tuning;
+use crate::sessions::{get_sessions_data, time_range_to_promql, SessionMetrics};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::AppHandle;
+
+/// A preset or custom range, as passed to `load_dashboard_metrics`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonRange {
+    pub time_range: String,
+    pub custom_start: Option<i64>,
+    pub custom_end: Option<i64>,
+}
+
+/// Each metric with `a` as the current period and `b` as the previous one
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardDeltas {
+    pub total_tokens: MetricComparison,
+    pub total_cost_usd: MetricComparison,
+    pub active_time_seconds: MetricComparison,
+    pub session_count: MetricComparison,
+    pub lines_added: MetricComparison,
+    pub lines_removed: MetricComparison,
+    pub commit_count: MetricComparison,
+    pub pull_request_count: MetricComparison,
+    pub input_tokens: MetricComparison,
+    pub output_tokens: MetricComparison,
+    pub cache_read_tokens: MetricComparison,
+    pub cache_creation_tokens: MetricComparison,
+}
+
+/// Values from both periods at the same distance from their start
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlignedPoint {
+    pub offset_seconds: i64,
+    pub a: Option<f64>,
+    pub b: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardComparison {
+    pub a_start: i64,
+    pub a_end: i64,
+    pub b_start: i64,
+    pub b_end: i64,
+    pub a: DashboardMetrics,
+    pub b: DashboardMetrics,
+    pub deltas: DashboardDeltas,
+    pub tokens_over_time: Vec<AlignedPoint>,
+    pub cost_over_time: Vec<AlignedPoint>,
+    pub sessions_over_time: Vec<AlignedPoint>,
+}
+
+/// Pair up two series by offset from their range start
+pub fn align_series(
+    a: &[TimeSeriesPoint],
+    a_start: i64,
+    b: &[TimeSeriesPoint],
+    b_start: i64,
+) -> Vec<AlignedPoint> {
+    let mut aligned: BTreeMap<i64, AlignedPoint> = BTreeMap::new();
+    for point in a {
+        let offset = point.timestamp - a_start;
+        aligned
+            .entry(offset)
+            .or_insert(AlignedPoint {
+                offset_seconds: offset,
+                a: None,
+                b: None,
+            })
+            .a = Some(point.value);
+    }
+    for point in b {
+        let offset = point.timestamp - b_start;
+        aligned
+            .entry(offset)
+            .or_insert(AlignedPoint {
+                offset_seconds: offset,
+                a: None,
+                b: None,
+            })
+            .b = Some(point.value);
+    }
+    aligned.into_values().collect()
+}
+
+fn dashboard_deltas(a: &DashboardMetrics, b: &DashboardMetrics) -> DashboardDeltas {
+    DashboardDeltas {
+        total_tokens: MetricComparison::new(a.total_tokens as f64, b.total_tokens as f64),
+        total_cost_usd: MetricComparison::new(a.total_cost_usd, b.total_cost_usd),
+        active_time_seconds: MetricComparison::new(a.active_time_seconds, b.active_time_seconds),
+        session_count: MetricComparison::new(a.session_count as f64, b.session_count as f64),
+        lines_added: MetricComparison::new(a.lines_added as f64, b.lines_added as f64),
+        lines_removed: MetricComparison::new(a.lines_removed as f64, b.lines_removed as f64),
+        commit_count: MetricComparison::new(a.commit_count as f64, b.commit_count as f64),
+        pull_request_count: MetricComparison::new(
+            a.pull_request_count as f64,
+            b.pull_request_count as f64,
+        ),
+        input_tokens: MetricComparison::new(a.input_tokens as f64, b.input_tokens as f64),
+        output_tokens: MetricComparison::new(a.output_tokens as f64, b.output_tokens as f64),
+        cache_read_tokens: MetricComparison::new(
+            a.cache_read_tokens as f64,
+            b.cache_read_tokens as f64,
+        ),
+        cache_creation_tokens: MetricComparison::new(
+            a.cache_creation_tokens as f64,
+            b.cache_creation_tokens as f64,
+        ),
+    }
+}
+
+/// Fetch two periods concurrently (e.g. this week vs last week) with deltas and
+/// series aligned for overlaying
+#[tauri::command]
+pub async fn get_dashboard_comparison(
+    app: AppHandle,
+    range_a: ComparisonRange,
+    range_b: ComparisonRange,
+    prometheus_url: String,
+) -> Result<DashboardComparison, String> {
+    let (a_start, a_end, _) = resolve_time_range(
+        &range_a.time_range,
+        range_a.custom_start,
+        range_a.custom_end,
+    )?;
+    let (b_start, b_end, _) = resolve_time_range(
+        &range_b.time_range,
+        range_b.custom_start,
+        range_b.custom_end,
+    )?;
+
+    let (a, b) = tokio::join!(
+        load_dashboard_metrics(
+            app.clone(),
+            range_a.time_range,
+            prometheus_url.clone(),
+            range_a.custom_start,
+            range_a.custom_end,
+            None,
+            LabelFilters::default(),
+        ),
+        load_dashboard_metrics(
+            app,
+            range_b.time_range,
+            prometheus_url,
+            range_b.custom_start,
+            range_b.custom_end,
+            None,
+            LabelFilters::default(),
+        ),
+    );
+    let (a, b) = (a?, b?);
+
+    Ok(DashboardComparison {
+        a_start,
+        a_end,
+        b_start,
+        b_end,
+        deltas: dashboard_deltas(&a, &b),
+        tokens_over_time: align_series(&a.tokens_over_time, a_start, &b.tokens_over_time, b_start),
+        cost_over_time: align_series(&a.cost_over_time, a_start, &b.cost_over_time, b_start),
+        sessions_over_time: align_series(
+            &a.sessions_over_time,
+            a_start,
+            &b.sessions_over_time,
+            b_start,
+        ),
+        a,
+        b,
+    })
+}
+
+/// One project's activity over the comparison range
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSummary {
+    pub project: String,
+    pub project_path: Option<String>,
+    pub session_count: u32,
+    pub total_cost_usd: f64,
+    pub total_tokens: u64,
+    pub active_time_seconds: f64,
+    pub lines_added: u64,
+    pub lines_removed: u64,
+    pub edits_accepted: u64,
+    pub edits_rejected: u64,
+    /// Share of edit/write tool decisions accepted (None without any decisions)
+    pub accept_rate: Option<f64>,
+    pub cost_per_session: Option<f64>,
+}
+
+/// Each metric with `a` as the current project and `b` as the one it's compared to
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDeltas {
+    pub total_cost_usd: MetricComparison,
+    pub total_tokens: MetricComparison,
+    pub session_count: MetricComparison,
+    pub active_time_seconds: MetricComparison,
+    pub lines_changed: MetricComparison,
+    pub accept_rate: MetricComparison,
+    pub cost_per_session: MetricComparison,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectComparison {
+    pub time_range: String,
+    pub a: ProjectSummary,
+    pub b: ProjectSummary,
+    pub deltas: ProjectDeltas,
+}
+
+/// Per-session values of `query`, keyed by (session id, value of `label`)
+async fn session_label_values(
+    client: &PrometheusClient,
+    query: &str,
+    label: &str,
+) -> HashMap<(String, String), f64> {
+    client
+        .query(query)
+        .await
+        .map(|results| {
+            results
+                .iter()
+                .filter_map(|r| {
+                    let session_id = r.metric.get("session_id")?.clone();
+                    let value = r.metric.get(label).cloned().unwrap_or_default();
+                    let v = r.value.as_ref()?.1.parse::<f64>().ok()?;
+                    Some(((session_id, value), v))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn summarize_project(
+    project: &str,
+    sessions: &[SessionMetrics],
+    lines: &HashMap<(String, String), f64>,
+    decisions: &HashMap<(String, String), f64>,
+) -> ProjectSummary {
+    let sessions: Vec<&SessionMetrics> = sessions
+        .iter()
+        .filter(|s| {
+            s.project_path.as_deref() == Some(project) || s.project.as_deref() == Some(project)
+        })
+        .collect();
+    let session_ids: HashSet<&str> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+    let sum_for = |values: &HashMap<(String, String), f64>, label: &str| -> u64 {
+        values
+            .iter()
+            .filter(|((id, value), _)| session_ids.contains(id.as_str()) && value == label)
+            .map(|(_, v)| *v)
+            .sum::<f64>()
+            .round() as u64
+    };
+
+    let mut summary = ProjectSummary {
+        project: sessions
+            .first()
+            .and_then(|s| s.project.clone())
+            .unwrap_or_else(|| project.to_string()),
+        project_path: sessions.first().and_then(|s| s.project_path.clone()),
+        session_count: sessions.len() as u32,
+        total_cost_usd: sessions.iter().map(|s| s.total_cost_usd).sum(),
+        total_tokens: sessions.iter().map(|s| s.total_tokens).sum(),
+        active_time_seconds: sessions.iter().map(|s| s.active_time_seconds).sum(),
+        lines_added: sum_for(lines, "added"),
+        lines_removed: sum_for(lines, "removed"),
+        edits_accepted: sum_for(decisions, "accept"),
+        edits_rejected: sum_for(decisions, "reject"),
+        ..Default::default()
+    };
+    let decided = summary.edits_accepted + summary.edits_rejected;
+    summary.accept_rate = (decided > 0).then(|| summary.edits_accepted as f64 / decided as f64);
+    summary.cost_per_session =
+        (summary.session_count > 0).then(|| summary.total_cost_usd / summary.session_count as f64);
+    summary
+}
+
+/// Compare two projects (by name or path) over a sessions time range
+#[tauri::command]
+pub async fn get_project_comparison(
+    app: AppHandle,
+    project_a: String,
+    project_b: String,
+    time_range: String,
+    prometheus_url: String,
+) -> Result<ProjectComparison, String> {
+    let tuning = load_query_tuning(&app);
+    let sessions =
+        get_sessions_data(app, time_range.clone(), prometheus_url.clone(), None, None).await?;
+
+    let client = PrometheusClient::new(&prometheus_url)
+        .map_err(|e| e.to_string())?
+        .with_lookback_delta(tuning.lookback_delta.clone());
+    let range = time_range_to_promql(&time_range);
+    let lines_query = format!(
+        "sum by (session_id, type) (increase(claude_code_lines_of_code_count_total[{}]))",
+        tuning.window("claude_code_lines_of_code_count_total", range)
+    );
+    let decisions_query = format!(
+        "sum by (session_id, decision) (increase(claude_code_code_edit_tool_decision_total[{}]))",
+        tuning.window("claude_code_code_edit_tool_decision_total", range)
+    );
+    let (lines, decisions) = tokio::join!(
+        session_label_values(&client, &lines_query, "type"),
+        session_label_values(&client, &decisions_query, "decision"),
+    );
+
+    let a = summarize_project(&project_a, &sessions.sessions, &lines, &decisions);
+    let b = summarize_project(&project_b, &sessions.sessions, &lines, &decisions);
+    let deltas = ProjectDeltas {
+        total_cost_usd: MetricComparison::new(a.total_cost_usd, b.total_cost_usd),
+        total_tokens: MetricComparison::new(a.total_tokens as f64, b.total_tokens as f64),
+        session_count: MetricComparison::new(a.session_count as f64, b.session_count as f64),
+        active_time_seconds: MetricComparison::new(a.active_time_seconds, b.active_time_seconds),
+        lines_changed: MetricComparison::new(
+            (a.lines_added + a.lines_removed) as f64,
+            (b.lines_added + b.lines_removed) as f64,
+        ),
+        accept_rate: MetricComparison::new(
+            a.accept_rate.unwrap_or(0.0),
+            b.accept_rate.unwrap_or(0.0),
+        ),
+        cost_per_session: MetricComparison::new(
+            a.cost_per_session.unwrap_or(0.0),
+            b.cost_per_session.unwrap_or(0.0),
+        ),
+    };
+
+    Ok(ProjectComparison {
+        time_range,
+        a,
+        b,
+        deltas,
+    })
+}
@@ -0,0 +1,101 @@
+// tauri-app/src-tauri/src/data_sources.rs
+
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+const DATA_SOURCES_KEY: &str = "extraDataSources";
+
+/// Id of the implicit source for this machine's ~/.claude directory
+pub const LOCAL_SOURCE_ID: &str = "local";
+
+/// A directory holding a copy of another machine's history.jsonl and/or
+/// stats-cache.json (e.g. a Dropbox or Syncthing folder)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataSource {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl DataSource {
+    pub fn is_local(&self) -> bool {
+        self.id == LOCAL_SOURCE_ID
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        Path::new(&self.path).join("history.jsonl")
+    }
+
+    pub fn stats_cache_path(&self) -> PathBuf {
+        Path::new(&self.path).join("stats-cache.json")
+    }
+}
+
+fn local_source() -> Option<DataSource> {
+    let claude_dir = dirs::home_dir()?.join(".claude");
+    Some(DataSource {
+        id: LOCAL_SOURCE_ID.to_string(),
+        name: "This machine".to_string(),
+        path: claude_dir.to_string_lossy().to_string(),
+        enabled: true,
+    })
+}
+
+fn load_extra_sources(app: &AppHandle) -> Vec<DataSource> {
+    load_setting(app, DATA_SOURCES_KEY)
+}
+
+/// The local ~/.claude source followed by every enabled extra source
+pub fn enabled_sources(app: &AppHandle) -> Vec<DataSource> {
+    local_source()
+        .into_iter()
+        .chain(load_extra_sources(app).into_iter().filter(|s| s.enabled))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_data_sources(app: AppHandle) -> Result<Vec<DataSource>, String> {
+    Ok(load_extra_sources(&app))
+}
+
+#[tauri::command]
+pub async fn save_data_source(app: AppHandle, source: DataSource) -> Result<DataSource, String> {
+    if source.name.trim().is_empty() {
+        return Err("Data source name cannot be empty".to_string());
+    }
+    if !Path::new(&source.path).is_dir() {
+        return Err(format!("Directory not found: {}", source.path));
+    }
+
+    let mut source = source;
+    if source.id.is_empty() {
+        source.id = format!("source-{}", chrono::Utc::now().timestamp_millis());
+    }
+    if source.is_local() {
+        return Err("The local data source cannot be changed".to_string());
+    }
+
+    let mut sources = load_extra_sources(&app);
+    match sources.iter_mut().find(|s| s.id == source.id) {
+        Some(existing) => *existing = source.clone(),
+        None => sources.push(source.clone()),
+    }
+    save_setting(&app, DATA_SOURCES_KEY, &sources)?;
+    Ok(source)
+}
+
+#[tauri::command]
+pub async fn delete_data_source(app: AppHandle, source_id: String) -> Result<(), String> {
+    let mut sources = load_extra_sources(&app);
+    sources.retain(|s| s.id != source_id);
+    save_setting(&app, DATA_SOURCES_KEY, &sources)
+}
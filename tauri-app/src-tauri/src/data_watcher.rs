@@ -0,0 +1,128 @@
+// tauri-app/src-tauri/src/data_watcher.rs
+
+use crate::active_sessions::refresh_active_sessions;
+use crate::history_index::{get_history_path, with_history_index};
+use crate::insights::get_stats_cache_path;
+use crate::transcript_archive::projects_dir;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Changes arriving within this window are reported as one event
+const DEBOUNCE: Duration = Duration::from_millis(750);
+/// A batch is emitted after this long even if changes keep arriving, so a
+/// session writing continuously doesn't hold back updates indefinitely
+const MAX_BATCH: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClaudeData {
+    History,
+    StatsCache,
+    Transcripts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeDataChanged {
+    pub changed: Vec<ClaudeData>,
+    pub timestamp: i64,
+}
+
+fn classify(path: &Path) -> Option<ClaudeData> {
+    if get_history_path().is_some_and(|p| p == path) {
+        Some(ClaudeData::History)
+    } else if get_stats_cache_path().is_some_and(|p| p == path) {
+        Some(ClaudeData::StatsCache)
+    } else if projects_dir().is_some_and(|p| path.starts_with(p)) {
+        Some(ClaudeData::Transcripts)
+    } else {
+        None
+    }
+}
+
+fn changed_data(event: &Event) -> impl Iterator<Item = ClaudeData> + '_ {
+    let relevant = !matches!(event.kind, EventKind::Access(_));
+    event
+        .paths
+        .iter()
+        .filter(move |_| relevant)
+        .filter_map(|p| classify(p))
+}
+
+/// Bring caches over the changed files up to date before the frontend asks for them
+fn invalidate(app: &AppHandle, changed: &BTreeSet<ClaudeData>) {
+    if changed.contains(&ClaudeData::History) {
+        if let Some(path) = get_history_path() {
+            let _ = with_history_index(&path, |_| ());
+        }
+    }
+    if changed.contains(&ClaudeData::History) || changed.contains(&ClaudeData::Transcripts) {
+        refresh_active_sessions(app);
+    }
+}
+
+/// Watch history.jsonl, stats-cache.json and the projects directory, emitting
+/// `claude-data-changed` shortly after Claude Code writes to any of them
+pub fn start_data_watcher(app: AppHandle) {
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Failed to start the Claude data watcher: {}", e);
+                return;
+            }
+        };
+
+        // The files are watched through their directory so replacing them
+        // (write to temp file, then rename) doesn't end the watch
+        let claude_dir = get_history_path().and_then(|p| p.parent().map(Path::to_path_buf));
+        if let Some(dir) = claude_dir.filter(|d| d.exists()) {
+            if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                eprintln!("Failed to watch {}: {}", dir.display(), e);
+            }
+        }
+        if let Some(dir) = projects_dir().filter(|d| d.exists()) {
+            if let Err(e) = watcher.watch(&dir, RecursiveMode::Recursive) {
+                eprintln!("Failed to watch {}: {}", dir.display(), e);
+            }
+        }
+
+        while let Ok(first) = rx.recv() {
+            let deadline = Instant::now() + MAX_BATCH;
+            let mut changed = BTreeSet::new();
+            if let Ok(event) = first {
+                changed.extend(changed_data(&event));
+            }
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                let Ok(next) = rx.recv_timeout(DEBOUNCE.min(remaining)) else {
+                    break;
+                };
+                if let Ok(event) = next {
+                    changed.extend(changed_data(&event));
+                }
+            }
+            if changed.is_empty() {
+                continue;
+            }
+
+            invalidate(&app, &changed);
+            let _ = app.emit(
+                "claude-data-changed",
+                ClaudeDataChanged {
+                    changed: changed.into_iter().collect(),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                },
+            );
+        }
+    });
+}
@@ -0,0 +1,135 @@
+// tauri-app/src-tauri/src/endpoints.rs
+
+use crate::metrics::{
+    output_throughput, rank_model_costs, DashboardMetrics, LabeledSeries, ModelCost, ModelTokens,
+    TimeSeriesPoint, UsageBreakdown,
+};
+use std::collections::BTreeMap;
+
+/// The primary URL followed by any additional ones, trimmed and without duplicates
+pub fn endpoint_urls(primary: &str, additional: Option<Vec<String>>) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for url in std::iter::once(primary.to_string()).chain(additional.unwrap_or_default()) {
+        let url = url.trim().trim_end_matches('/').to_string();
+        if !url.is_empty() && !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// Sum two series point by point. Endpoints share the same start, end and step,
+/// so their timestamps line up.
+pub fn merge_series(target: &mut Vec<TimeSeriesPoint>, series: Vec<TimeSeriesPoint>) {
+    let mut points: BTreeMap<i64, TimeSeriesPoint> =
+        target.drain(..).map(|p| (p.timestamp, p)).collect();
+    for point in series {
+        points
+            .entry(point.timestamp)
+            .and_modify(|p| {
+                p.value += point.value;
+                p.partial |= point.partial;
+            })
+            .or_insert(point);
+    }
+    *target = points.into_values().collect();
+}
+
+fn merge_labeled(target: &mut Vec<LabeledSeries>, series: Vec<LabeledSeries>) {
+    for labeled in series {
+        match target.iter_mut().find(|s| s.label == labeled.label) {
+            Some(existing) => merge_series(&mut existing.points, labeled.points),
+            None => target.push(labeled),
+        }
+    }
+}
+
+fn merge_model_tokens(target: &mut Vec<ModelTokens>, models: Vec<ModelTokens>) {
+    for model in models {
+        match target.iter_mut().find(|m| m.model == model.model) {
+            Some(existing) => existing.tokens += model.tokens,
+            None => target.push(model),
+        }
+    }
+    target.sort_by_key(|m| std::cmp::Reverse(m.tokens));
+}
+
+fn merge_model_costs(target: &mut Vec<ModelCost>, models: Vec<ModelCost>) {
+    for model in models {
+        match target.iter_mut().find(|m| m.model == model.model) {
+            Some(existing) => existing.cost_usd += model.cost_usd,
+            None => target.push(model),
+        }
+    }
+    rank_model_costs(target);
+}
+
+fn merge_breakdown(target: &mut Vec<UsageBreakdown>, breakdown: Vec<UsageBreakdown>) {
+    for usage in breakdown {
+        match target.iter_mut().find(|u| u.label == usage.label) {
+            Some(existing) => {
+                existing.tokens += usage.tokens;
+                existing.cost_usd += usage.cost_usd;
+                existing.sessions += usage.sessions;
+            }
+            None => target.push(usage),
+        }
+    }
+    target.sort_by_key(|u| std::cmp::Reverse(u.tokens));
+}
+
+/// Sum dashboard metrics fetched from several Prometheus endpoints
+pub fn merge_dashboard_metrics(metrics: Vec<DashboardMetrics>) -> Option<DashboardMetrics> {
+    let mut metrics = metrics.into_iter();
+    let mut merged = metrics.next()?;
+
+    for m in metrics {
+        merged.total_tokens += m.total_tokens;
+        merged.total_cost_usd += m.total_cost_usd;
+        merged.active_time_seconds += m.active_time_seconds;
+        merged.session_count += m.session_count;
+        merged.lines_added += m.lines_added;
+        merged.lines_removed += m.lines_removed;
+        merged.commit_count += m.commit_count;
+        merged.pull_request_count += m.pull_request_count;
+        merged.input_tokens += m.input_tokens;
+        merged.output_tokens += m.output_tokens;
+        merged.cache_read_tokens += m.cache_read_tokens;
+        merged.cache_creation_tokens += m.cache_creation_tokens;
+        merged.cost_by_token_type.merge(&m.cost_by_token_type);
+
+        merge_model_tokens(&mut merged.tokens_by_model, m.tokens_by_model);
+        merge_model_costs(&mut merged.cost_by_model, m.cost_by_model);
+        merge_breakdown(&mut merged.usage_by_terminal, m.usage_by_terminal);
+
+        merge_series(&mut merged.tokens_over_time, m.tokens_over_time);
+        merge_series(&mut merged.cost_over_time, m.cost_over_time);
+        merge_series(&mut merged.lines_added_over_time, m.lines_added_over_time);
+        merge_series(
+            &mut merged.lines_removed_over_time,
+            m.lines_removed_over_time,
+        );
+        merge_series(&mut merged.sessions_over_time, m.sessions_over_time);
+        merge_series(&mut merged.burn_rate_over_time, m.burn_rate_over_time);
+        merged.burn_rate_usd_per_hour += m.burn_rate_usd_per_hour;
+        merge_labeled(
+            &mut merged.tokens_by_version_over_time,
+            m.tokens_by_version_over_time,
+        );
+        // Per-model throughput is a ratio, so the first endpoint's series is kept
+        // rather than summed
+
+        let types = &mut merged.tokens_by_type_over_time;
+        merge_series(&mut types.input, m.tokens_by_type_over_time.input);
+        merge_series(&mut types.output, m.tokens_by_type_over_time.output);
+        merge_series(&mut types.cache_read, m.tokens_by_type_over_time.cache_read);
+        merge_series(
+            &mut types.cache_creation,
+            m.tokens_by_type_over_time.cache_creation,
+        );
+    }
+
+    merged.output_tokens_per_second =
+        output_throughput(merged.output_tokens, merged.active_time_seconds);
+    Some(merged)
+}
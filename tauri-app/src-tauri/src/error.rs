@@ -0,0 +1,68 @@
+// tauri-app/src-tauri/src/error.rs
+
+use crate::prometheus::PrometheusError;
+use serde::Serialize;
+
+/// Errors returned to the frontend as `{ kind, message }` so views can show an
+/// actionable state (check the URL, widen the range, run Claude Code, ...)
+/// instead of a raw string. Messages are passed through unchanged.
+#[derive(Debug, Clone, thiserror::Error, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum AppError {
+    /// Prometheus or another endpoint could not be reached
+    #[error("{0}")]
+    ConnectionFailed(String),
+    #[error("{0}")]
+    Timeout(String),
+    /// The server answered but rejected the query
+    #[error("{0}")]
+    QueryFailed(String),
+    /// Nothing was recorded for the requested range
+    #[error("{0}")]
+    NoData(String),
+    #[error("{0}")]
+    ParseError(String),
+    #[error("{0}")]
+    FileNotFound(String),
+    /// Bad arguments, e.g. a custom range that ends before it starts
+    #[error("{0}")]
+    InvalidInput(String),
+    /// Superseded by a newer request
+    #[error("{0}")]
+    Cancelled(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<PrometheusError> for AppError {
+    fn from(error: PrometheusError) -> Self {
+        let message = error.to_string();
+        match &error {
+            PrometheusError::Request(e) if e.is_timeout() => AppError::Timeout(message),
+            PrometheusError::Request(e) if e.is_connect() => AppError::ConnectionFailed(message),
+            PrometheusError::Request(e) if e.is_decode() => AppError::ParseError(message),
+            PrometheusError::Request(_) => AppError::ConnectionFailed(message),
+            PrometheusError::InvalidResponse(_) => AppError::QueryFailed(message),
+            PrometheusError::Tls(_) | PrometheusError::Header(_) => AppError::InvalidInput(message),
+        }
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Other(message.to_string())
+    }
+}
+
+/// Lets commands that still return `String` errors call ones that return `AppError`
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.to_string()
+    }
+}
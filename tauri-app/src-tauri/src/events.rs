@@ -0,0 +1,52 @@
+// tauri-app/src-tauri/src/events.rs
+
+use crate::{alerts, hooks, local_api, notifications, plugins, timezone, webhooks};
+use chrono::Duration;
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+
+/// Emit an app event to the frontend and forward it to every external consumer
+/// (webhooks, command hooks, exporter plugins, /ws clients, OS notifications)
+/// subscribed to `name`
+pub fn publish<P: Serialize + Clone>(app: &AppHandle, name: &str, payload: P) {
+    let _ = app.emit(name, payload.clone());
+
+    let Ok(payload) = serde_json::to_value(payload) else {
+        return;
+    };
+    // Alerts are logged even during quiet hours, when the notifications and chat
+    // messages for them are held back
+    let notify = alerts::record_alert(app, name, &payload);
+    webhooks::dispatch(app, name, &payload, notify);
+    hooks::dispatch(app, name, &payload);
+    if notify {
+        notifications::dispatch(app, name, &payload);
+    }
+    plugins::dispatch(name, &payload);
+    local_api::broadcast(name, &payload);
+}
+
+/// Publish `daily-rollover` at each midnight in the configured timezone with the
+/// day that just ended
+pub fn start_daily_rollover(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let now = chrono::Utc::now().timestamp();
+            let next_midnight = timezone::start_of_day(timezone::today() + Duration::days(1));
+            // Re-check hourly so a timezone change takes effect
+            let wait = (next_midnight - now).clamp(0, 3600) as u64;
+            tokio::time::sleep(std::time::Duration::from_secs(wait + 1)).await;
+            if chrono::Utc::now().timestamp() < next_midnight {
+                continue;
+            }
+
+            let previous_day = timezone::today() - Duration::days(1);
+            publish(
+                &app,
+                "daily-rollover",
+                json!({ "date": previous_day.format("%Y-%m-%d").to_string() }),
+            );
+        }
+    });
+}
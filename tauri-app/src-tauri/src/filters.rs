@@ -0,0 +1,99 @@
+// tauri-app/src-tauri/src/filters.rs
+
+use serde::{Deserialize, Serialize};
+
+/// Dashboard filters, each added as a PromQL label matcher to every query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LabelFilters {
+    pub model: Option<String>,
+    pub user_email: Option<String>,
+    /// Matched against the `host_name` label
+    pub host: Option<String>,
+    /// Only set when the collector adds a `project` resource attribute
+    pub project: Option<String>,
+}
+
+/// Filter names accepted by `get_label_values`, with the label each one matches
+pub const FILTER_LABELS: &[(&str, &str)] = &[
+    ("model", "model"),
+    ("userEmail", "user_email"),
+    ("host", "host_name"),
+    ("project", "project"),
+];
+
+/// Escape a value for use inside a double-quoted PromQL string
+pub(crate) fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl LabelFilters {
+    fn matchers(&self) -> Vec<String> {
+        [
+            ("model", &self.model),
+            ("user_email", &self.user_email),
+            ("host_name", &self.host),
+            ("project", &self.project),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| {
+            let value = value.as_deref().filter(|v| !v.is_empty())?;
+            Some(format!("{}=\"{}\"", label, escape(value)))
+        })
+        .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matchers().is_empty()
+    }
+
+    /// `metric{...}` with `extra` matchers (e.g. `type="added"`) followed by the filters
+    pub fn selector(&self, metric: &str, extra: &[&str]) -> String {
+        let matchers: Vec<String> = extra
+            .iter()
+            .map(|m| m.to_string())
+            .chain(self.matchers())
+            .collect();
+        if matchers.is_empty() {
+            metric.to_string()
+        } else {
+            format!("{}{{{}}}", metric, matchers.join(","))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_handles_quotes_backslashes_and_newlines() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape(r#"a"b"#), r#"a\"b"#);
+        assert_eq!(escape(r"C:\work"), r"C:\\work");
+        assert_eq!(escape("a\nb"), r"a\nb");
+    }
+
+    #[test]
+    fn selector_adds_non_empty_filters_after_extra_matchers() {
+        let filters = LabelFilters {
+            model: Some("claude-\"x\"".to_string()),
+            host: Some(String::new()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            filters.selector("metric", &["type=\"added\""]),
+            r#"metric{type="added",model="claude-\"x\""}"#
+        );
+        assert_eq!(LabelFilters::default().selector("metric", &[]), "metric");
+        assert!(LabelFilters {
+            host: Some(String::new()),
+            ..Default::default()
+        }
+        .is_empty());
+    }
+}
@@ -0,0 +1,314 @@
+// tauri-app/src-tauri/src/forecast.rs
+
+use crate::data_sources::enabled_sources;
+use crate::history_index::with_history_index;
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::format_duration;
+use crate::timezone;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
+
+const DEFAULT_LOOKBACK_DAYS: u32 = 30;
+/// Weight of the most recent day in the smoothed daily rate
+const SMOOTHING: f64 = 0.3;
+const UNKNOWN_PROJECT: &str = "Unknown";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyCost {
+    pub date: String,
+    pub cost_usd: f64,
+}
+
+/// Month-end spend projection, overall or for a single project
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostForecast {
+    /// None for the all-projects forecast
+    pub project: Option<String>,
+    pub project_path: Option<String>,
+    /// e.g. "2026-10"
+    pub month: String,
+    pub days_in_month: u32,
+    pub days_elapsed: u32,
+    pub month_to_date_usd: f64,
+    /// Smoothed spend per day from completed days, weighted toward recent ones
+    pub daily_rate_usd: f64,
+    pub projected_remaining_usd: f64,
+    pub projected_month_total_usd: f64,
+    /// One standard deviation of daily spend carried over the remaining days
+    pub projected_low_usd: f64,
+    pub projected_high_usd: f64,
+    pub history: Vec<DailyCost>,
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        m => (date.year(), m + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .map(|next| (next - Duration::days(1)).day())
+        .unwrap_or(30)
+}
+
+/// Project path for every session id known to any data source's history
+fn session_projects(app: &AppHandle) -> HashMap<String, String> {
+    let mut projects = HashMap::new();
+    for source in enabled_sources(app) {
+        let _ = with_history_index(&source.history_path(), |index| {
+            for session in &index.sessions {
+                projects
+                    .entry(session.session_id.clone())
+                    .or_insert_with(|| session.project_path.clone());
+            }
+        });
+    }
+    projects
+}
+
+/// Daily cost per project path for local days `first_day..=today`
+async fn project_daily_costs(
+    app: &AppHandle,
+    prometheus_url: &str,
+    first_day: NaiveDate,
+) -> Result<HashMap<String, BTreeMap<NaiveDate, f64>>, String> {
+    let today = timezone::today();
+    let client = PrometheusClient::new(prometheus_url).map_err(|e| e.to_string())?;
+
+    // One point per local day end; today's point is "so far"
+    let start = timezone::start_of_day(first_day + Duration::days(1));
+    let end = chrono::Utc::now().timestamp();
+    let query = "sum by (session_id) (increase(claude_code_cost_usage_USD_total[1d]))";
+    let results = client
+        .query_range(query, start, end, "1d")
+        .await
+        .map_err(|e| e.to_string())?;
+    let today_results = client
+        .query(&format!(
+            "sum by (session_id) (increase(claude_code_cost_usage_USD_total[{}]))",
+            format_duration(end - timezone::start_of_day(today))
+        ))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let projects = session_projects(app);
+    let mut daily: HashMap<String, BTreeMap<NaiveDate, f64>> = HashMap::new();
+    let mut add = |session_id: Option<&String>, date: NaiveDate, cost: f64| {
+        if cost <= 0.0 {
+            return;
+        }
+        let project = session_id
+            .and_then(|id| projects.get(id))
+            .cloned()
+            .unwrap_or_else(|| UNKNOWN_PROJECT.to_string());
+        *daily.entry(project).or_default().entry(date).or_default() += cost;
+    };
+
+    for result in &results {
+        for (ts, value) in result.values.as_deref().unwrap_or_default() {
+            // A point at midnight covers the day before it
+            let date = timezone::date_of(*ts as i64 - 1);
+            if date >= today {
+                continue;
+            }
+            add(
+                result.metric.get("session_id"),
+                date,
+                value.parse().unwrap_or(0.0),
+            );
+        }
+    }
+    for result in &today_results {
+        let cost = result
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse().ok())
+            .unwrap_or(0.0);
+        add(result.metric.get("session_id"), today, cost);
+    }
+
+    Ok(daily)
+}
+
+fn forecast(
+    project: Option<String>,
+    daily: &BTreeMap<NaiveDate, f64>,
+    first_day: NaiveDate,
+    now: i64,
+) -> CostForecast {
+    let today = timezone::date_of(now);
+    let month_start = today.with_day(1).unwrap_or(today);
+    let total_days = days_in_month(today);
+
+    // Completed days in the lookback, including zero-spend days
+    let completed: Vec<f64> = first_day
+        .iter_days()
+        .take_while(|d| *d < today)
+        .map(|d| daily.get(&d).copied().unwrap_or(0.0))
+        .collect();
+    let daily_rate = completed
+        .iter()
+        .fold(None, |rate: Option<f64>, cost| {
+            Some(rate.map_or(*cost, |r| SMOOTHING * cost + (1.0 - SMOOTHING) * r))
+        })
+        .unwrap_or(0.0);
+    let mean = completed.iter().sum::<f64>() / completed.len().max(1) as f64;
+    let std_dev = (completed.iter().map(|c| (c - mean).powi(2)).sum::<f64>()
+        / completed.len().max(1) as f64)
+        .sqrt();
+
+    let month_to_date: f64 = daily.range(month_start..=today).map(|(_, cost)| cost).sum();
+    let elapsed_today = (now - timezone::start_of_day(today)) as f64 / 86_400.0;
+    let remaining_days = (total_days - today.day()) as f64 + (1.0 - elapsed_today).max(0.0);
+    let projected_remaining = daily_rate * remaining_days;
+    let spread = std_dev * remaining_days.sqrt();
+
+    CostForecast {
+        project_path: project.clone().filter(|p| p != UNKNOWN_PROJECT),
+        project: project.map(|p| {
+            std::path::Path::new(&p)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or(p)
+        }),
+        month: today.format("%Y-%m").to_string(),
+        days_in_month: total_days,
+        days_elapsed: today.day(),
+        month_to_date_usd: month_to_date,
+        daily_rate_usd: daily_rate,
+        projected_remaining_usd: projected_remaining,
+        projected_month_total_usd: month_to_date + projected_remaining,
+        projected_low_usd: month_to_date + (projected_remaining - spread).max(0.0),
+        projected_high_usd: month_to_date + projected_remaining + spread,
+        history: daily
+            .iter()
+            .map(|(date, cost)| DailyCost {
+                date: date.format("%Y-%m-%d").to_string(),
+                cost_usd: *cost,
+            })
+            .collect(),
+    }
+}
+
+fn first_day(lookback_days: Option<u32>) -> NaiveDate {
+    let today = timezone::today();
+    let lookback = lookback_days.unwrap_or(DEFAULT_LOOKBACK_DAYS).max(1);
+    // Always cover the whole month so month-to-date is complete
+    (today - Duration::days(lookback as i64)).min(today.with_day(1).unwrap_or(today))
+}
+
+/// Month-end projection for all projects combined, or one project by path or name
+#[tauri::command]
+pub async fn get_cost_forecast(
+    app: AppHandle,
+    prometheus_url: String,
+    project: Option<String>,
+    lookback_days: Option<u32>,
+) -> Result<CostForecast, String> {
+    let first_day = first_day(lookback_days);
+    let daily = project_daily_costs(&app, &prometheus_url, first_day).await?;
+    let now = chrono::Utc::now().timestamp();
+
+    let Some(project) = project else {
+        let mut combined: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        for (date, cost) in daily.values().flatten() {
+            *combined.entry(*date).or_default() += cost;
+        }
+        return Ok(forecast(None, &combined, first_day, now));
+    };
+
+    let (path, costs) = daily
+        .into_iter()
+        .find(|(path, _)| {
+            *path == project
+                || std::path::Path::new(path)
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy() == project)
+        })
+        .unwrap_or((project, BTreeMap::new()));
+    Ok(forecast(Some(path), &costs, first_day, now))
+}
+
+/// Month-end projection for every project with spend in the lookback window,
+/// highest projected total first
+#[tauri::command]
+pub async fn get_project_forecasts(
+    app: AppHandle,
+    prometheus_url: String,
+    lookback_days: Option<u32>,
+) -> Result<Vec<CostForecast>, String> {
+    let first_day = first_day(lookback_days);
+    let daily = project_daily_costs(&app, &prometheus_url, first_day).await?;
+    let now = chrono::Utc::now().timestamp();
+
+    let mut forecasts: Vec<CostForecast> = daily
+        .into_iter()
+        .map(|(path, costs)| forecast(Some(path), &costs, first_day, now))
+        .collect();
+    forecasts.sort_by(|a, b| {
+        b.projected_month_total_usd
+            .partial_cmp(&a.projected_month_total_usd)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(forecasts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 6, day).unwrap()
+    }
+
+    #[test]
+    fn days_in_month_handles_december_and_leap_years() {
+        assert_eq!(
+            days_in_month(NaiveDate::from_ymd_opt(2026, 12, 5).unwrap()),
+            31
+        );
+        assert_eq!(
+            days_in_month(NaiveDate::from_ymd_opt(2028, 2, 1).unwrap()),
+            29
+        );
+        assert_eq!(days_in_month(date(30)), 30);
+    }
+
+    #[test]
+    fn forecast_smooths_completed_days_and_projects_the_rest_of_the_month() {
+        // Midday on June 15th, so half of today is still to come
+        let now = timezone::start_of_day(date(15)) + 12 * 3600;
+        let daily: BTreeMap<NaiveDate, f64> =
+            [(10, 1.0), (11, 2.0), (12, 3.0), (14, 4.0), (15, 5.0)]
+                .into_iter()
+                .map(|(day, cost)| (date(day), cost))
+                .collect();
+
+        let forecast = forecast(None, &daily, date(10), now);
+
+        // Completed days 1, 2, 3, 0, 4 smoothed toward the most recent
+        let rate = [2.0, 3.0, 0.0, 4.0]
+            .iter()
+            .fold(1.0, |r, c| SMOOTHING * c + (1.0 - SMOOTHING) * r);
+        let remaining_days = 15.5;
+        assert_eq!(forecast.month, "2026-06");
+        assert_eq!(forecast.days_in_month, 30);
+        assert_eq!(forecast.days_elapsed, 15);
+        assert!((forecast.month_to_date_usd - 15.0).abs() < 1e-9);
+        assert!((forecast.daily_rate_usd - rate).abs() < 1e-9);
+        assert!((forecast.projected_remaining_usd - rate * remaining_days).abs() < 1e-6);
+        assert!((forecast.projected_month_total_usd - (15.0 + rate * remaining_days)).abs() < 1e-6);
+
+        // Population standard deviation of the completed days, over the remaining days
+        let spread = 2.0_f64.sqrt() * remaining_days.sqrt();
+        assert!(
+            (forecast.projected_high_usd - forecast.projected_month_total_usd - spread).abs()
+                < 1e-6
+        );
+        assert!(forecast.projected_low_usd <= forecast.projected_month_total_usd);
+        assert!(forecast.projected_low_usd >= forecast.month_to_date_usd);
+    }
+}
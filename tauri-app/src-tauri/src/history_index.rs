@@ -0,0 +1,628 @@
+// tauri-app/src-tauri/src/history_index.rs
+
+use crate::error::AppError;
+use crate::processes::scan_claude_processes;
+use crate::timezone;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Bytes from the start of the file used to detect rewrites/rotation
+const HEAD_FINGERPRINT_LEN: usize = 256;
+
+/// Chunk size used when reading history.jsonl backwards
+const TAIL_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Characters of a malformed line included in diagnostics
+const MALFORMED_PREVIEW_LEN: usize = 120;
+
+/// Entry from ~/.claude/history.jsonl
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryEntry {
+    timestamp: i64,
+    project: String,
+    session_id: String,
+}
+
+/// One history line, reduced to what the sessions view needs
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryRecord {
+    pub timestamp: i64,
+    /// Index into `HistoryIndex::sessions`
+    pub session: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub project_path: String,
+    /// Timestamp of the session's earliest entry
+    pub first_seen: i64,
+}
+
+#[derive(Debug, Default)]
+struct DayStats {
+    message_count: u32,
+    sessions: HashSet<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaySummary {
+    pub date: String,
+    pub message_count: u32,
+    pub session_count: u32,
+}
+
+/// Byte range of a history line that could not be parsed (or, for
+/// `unrecognized_lines`, is valid JSON in a shape the app doesn't read)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MalformedLine {
+    pub start: u64,
+    pub end: u64,
+    pub preview: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryDiagnostics {
+    pub file_size: u64,
+    pub valid_lines: usize,
+    pub malformed_count: usize,
+    pub malformed_lines: Vec<MalformedLine>,
+    /// Valid JSON without the fields sessions are built from, e.g. entries
+    /// written by older Claude Code versions. Left alone by repair.
+    pub unrecognized_count: usize,
+    pub unrecognized_lines: Vec<MalformedLine>,
+    /// Bytes after the last newline (an in-progress or interrupted write)
+    pub trailing_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRepairReport {
+    pub lines_kept: usize,
+    /// Kept lines that are valid JSON but not entries the app reads
+    pub lines_unrecognized: usize,
+    pub lines_removed: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub backup_path: Option<String>,
+}
+
+/// Incrementally maintained index over history.jsonl.
+/// `offset` is the byte position after the last complete line parsed, so a
+/// refresh only reads lines appended since the previous call.
+#[derive(Debug, Default)]
+pub struct HistoryIndex {
+    path: PathBuf,
+    offset: u64,
+    head: Vec<u8>,
+    pub records: Vec<HistoryRecord>,
+    pub sessions: Vec<SessionInfo>,
+    session_lookup: HashMap<String, u32>,
+    days: BTreeMap<NaiveDate, DayStats>,
+    malformed: Vec<MalformedLine>,
+    unrecognized: Vec<MalformedLine>,
+}
+
+/// One index per history file (the local one plus any extra data sources)
+static HISTORY_INDEXES: Mutex<Vec<HistoryIndex>> = Mutex::new(Vec::new());
+
+/// Earliest timestamp of every session in a history file, maintained like the
+/// index but without per-line records, so reading only the tail of the file
+/// can still tell when the sessions in it began
+#[derive(Debug, Default)]
+struct FirstSeenLookup {
+    path: PathBuf,
+    offset: u64,
+    head: Vec<u8>,
+    sessions: HashMap<String, i64>,
+}
+
+static FIRST_SEEN: Mutex<Vec<FirstSeenLookup>> = Mutex::new(Vec::new());
+
+pub fn get_history_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".claude").join("history.jsonl"))
+}
+
+fn read_head(file: &mut File) -> std::io::Result<Vec<u8>> {
+    let mut head = vec![0u8; HEAD_FINGERPRINT_LEN];
+    file.seek(SeekFrom::Start(0))?;
+    let n = file.read(&mut head)?;
+    head.truncate(n);
+    Ok(head)
+}
+
+/// Whether a line is valid JSON at all, whatever its shape
+fn is_json(line: &[u8]) -> bool {
+    serde_json::from_slice::<serde_json::Value>(line).is_ok()
+}
+
+fn open_history(path: &Path) -> Result<(File, u64), String> {
+    let file = File::open(path)
+        .map_err(|_| "History file not found. Use Claude Code to generate usage data.")?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    Ok((file, len))
+}
+
+/// Whether the file was truncated or replaced since `offset` bytes with the
+/// given head were read. The head only grows until it reaches the fingerprint
+/// length, so only the overlap is compared.
+fn was_replaced(previous_head: &[u8], head: &[u8], len: u64, offset: u64) -> bool {
+    let overlap = previous_head.len().min(head.len());
+    len < offset || head[..overlap] != previous_head[..overlap]
+}
+
+/// Complete lines written after `offset`, without the final newline, and the
+/// offset just past them. A partially written trailing line is left for next time.
+fn read_appended(file: &mut File, offset: u64, len: u64) -> Result<(Vec<u8>, u64), String> {
+    if len <= offset {
+        return Ok((Vec::new(), offset));
+    }
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| e.to_string())?;
+    let mut buf = Vec::with_capacity((len - offset) as usize);
+    file.read_to_end(&mut buf)
+        .map_err(|e| format!("Failed to read history: {}", e))?;
+
+    let Some(last_newline) = buf.iter().rposition(|&b| b == b'\n') else {
+        return Ok((Vec::new(), offset));
+    };
+    buf.truncate(last_newline);
+    Ok((buf, offset + last_newline as u64 + 1))
+}
+
+impl HistoryIndex {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    fn add_entry(&mut self, entry: HistoryEntry) {
+        let session = match self.session_lookup.get(&entry.session_id) {
+            Some(&idx) => {
+                let info = &mut self.sessions[idx as usize];
+                info.first_seen = info.first_seen.min(entry.timestamp);
+                idx
+            }
+            None => {
+                let idx = self.sessions.len() as u32;
+                self.session_lookup.insert(entry.session_id.clone(), idx);
+                self.sessions.push(SessionInfo {
+                    session_id: entry.session_id,
+                    project_path: entry.project,
+                    first_seen: entry.timestamp,
+                });
+                idx
+            }
+        };
+
+        let day = self
+            .days
+            .entry(timezone::date_of(entry.timestamp.div_euclid(1000)))
+            .or_default();
+        day.message_count += 1;
+        day.sessions.insert(session);
+
+        self.records.push(HistoryRecord {
+            timestamp: entry.timestamp,
+            session,
+        });
+    }
+
+    /// Parse lines appended since the last refresh (rebuilding if the file was replaced)
+    fn refresh(&mut self) -> Result<(), String> {
+        let (mut file, len) = open_history(&self.path)?;
+        let head = read_head(&mut file).map_err(|e| e.to_string())?;
+        if was_replaced(&self.head, &head, len, self.offset) {
+            *self = HistoryIndex::new(&self.path);
+        }
+        self.head = head;
+
+        let (buf, offset) = read_appended(&mut file, self.offset, len)?;
+        let mut line_start = self.offset;
+        for line in buf.split(|&b| b == b'\n') {
+            let line_end = line_start + line.len() as u64;
+            if !line.iter().all(|b| b.is_ascii_whitespace()) {
+                if let Ok(entry) = serde_json::from_slice::<HistoryEntry>(line) {
+                    self.add_entry(entry);
+                } else {
+                    let problem = MalformedLine {
+                        start: line_start,
+                        end: line_end,
+                        preview: String::from_utf8_lossy(line)
+                            .chars()
+                            .take(MALFORMED_PREVIEW_LEN)
+                            .collect(),
+                    };
+                    if is_json(line) {
+                        self.unrecognized.push(problem);
+                    } else {
+                        self.malformed.push(problem);
+                    }
+                }
+            }
+            line_start = line_end + 1;
+        }
+
+        self.offset = offset;
+        Ok(())
+    }
+
+    /// Most recent session with activity in `project_path` at or after `since`
+    pub fn latest_session_in_project(
+        &self,
+        project_path: &str,
+        since: i64,
+    ) -> Option<&SessionInfo> {
+        self.records
+            .iter()
+            .rev()
+            .take_while(|r| r.timestamp >= since)
+            .map(|r| &self.sessions[r.session as usize])
+            .find(|s| s.project_path == project_path)
+    }
+
+    pub fn day_summaries(&self, since: Option<NaiveDate>) -> Vec<DaySummary> {
+        self.days
+            .iter()
+            .filter(|(date, _)| since.is_none_or(|since| **date >= since))
+            .map(|(date, stats)| DaySummary {
+                date: date.format("%Y-%m-%d").to_string(),
+                message_count: stats.message_count,
+                session_count: stats.sessions.len() as u32,
+            })
+            .collect()
+    }
+}
+
+impl FirstSeenLookup {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    /// Read lines appended since the last refresh (starting over if the file was replaced)
+    fn refresh(&mut self) -> Result<(), String> {
+        let (mut file, len) = open_history(&self.path)?;
+        let head = read_head(&mut file).map_err(|e| e.to_string())?;
+        if was_replaced(&self.head, &head, len, self.offset) {
+            *self = FirstSeenLookup::new(&self.path);
+        }
+        self.head = head;
+
+        let (buf, offset) = read_appended(&mut file, self.offset, len)?;
+        for line in buf.split(|&b| b == b'\n') {
+            if let Ok(entry) = serde_json::from_slice::<HistoryEntry>(line) {
+                self.sessions
+                    .entry(entry.session_id)
+                    .and_modify(|first| *first = (*first).min(entry.timestamp))
+                    .or_insert(entry.timestamp);
+            }
+        }
+        self.offset = offset;
+        Ok(())
+    }
+}
+
+/// Build a throwaway index from the entries at or after `cutoff` by reading the
+/// file backwards from the end, stopping once a chunk reaches older entries
+fn read_tail(path: &Path, cutoff: i64) -> Result<HistoryIndex, String> {
+    let (mut file, len) = open_history(path)?;
+
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    let mut carry: Vec<u8> = Vec::new();
+    let mut pos = len;
+    let mut first_chunk = true;
+
+    while pos > 0 {
+        let start = pos.saturating_sub(TAIL_CHUNK_SIZE);
+        let mut chunk = vec![0u8; (pos - start) as usize];
+        file.seek(SeekFrom::Start(start))
+            .map_err(|e| e.to_string())?;
+        file.read_exact(&mut chunk)
+            .map_err(|e| format!("Failed to read history: {}", e))?;
+        chunk.extend_from_slice(&carry);
+        pos = start;
+
+        // Bytes before the first newline belong to a line that starts in an earlier chunk
+        let complete = if pos == 0 {
+            carry.clear();
+            &chunk[..]
+        } else {
+            match chunk.iter().position(|&b| b == b'\n') {
+                Some(i) => {
+                    carry = chunk[..i].to_vec();
+                    &chunk[i + 1..]
+                }
+                None => {
+                    carry = chunk;
+                    continue;
+                }
+            }
+        };
+
+        let mut lines: Vec<&[u8]> = complete.split(|&b| b == b'\n').collect();
+        if first_chunk {
+            // Whatever follows the final newline is empty or still being written
+            lines.pop();
+            first_chunk = false;
+        }
+
+        let mut reached_cutoff = false;
+        for line in lines.into_iter().rev() {
+            if line.iter().all(|b| b.is_ascii_whitespace()) {
+                continue;
+            }
+            if let Ok(entry) = serde_json::from_slice::<HistoryEntry>(line) {
+                if entry.timestamp >= cutoff {
+                    entries.push(entry);
+                } else {
+                    reached_cutoff = true;
+                }
+            }
+        }
+
+        if reached_cutoff {
+            break;
+        }
+    }
+
+    let mut index = HistoryIndex::new(path);
+    for entry in entries.into_iter().rev() {
+        index.add_entry(entry);
+    }
+    Ok(index)
+}
+
+/// Move each session's `first_seen` back to its earliest entry in the whole file
+fn apply_first_seen(path: &Path, index: &mut HistoryIndex) -> Result<(), String> {
+    let mut guard = FIRST_SEEN.lock().map_err(|e| e.to_string())?;
+    let position = match guard.iter().position(|lookup| lookup.path == path) {
+        Some(position) => position,
+        None => {
+            guard.push(FirstSeenLookup::new(path));
+            guard.len() - 1
+        }
+    };
+    let lookup = &mut guard[position];
+    lookup.refresh()?;
+    for info in &mut index.sessions {
+        if let Some(&first) = lookup.sessions.get(&info.session_id) {
+            info.first_seen = info.first_seen.min(first);
+        }
+    }
+    Ok(())
+}
+
+/// Run `f` against entries of the history file at `path` since `cutoff`, using the
+/// shared index when it is already built and otherwise reading only the tail of the file
+pub fn with_recent_history<R>(
+    path: &Path,
+    cutoff: i64,
+    f: impl FnOnce(&HistoryIndex) -> R,
+) -> Result<R, String> {
+    let mut guard = HISTORY_INDEXES
+        .lock()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+
+    if let Some(index) = guard.iter_mut().find(|index| index.path == path) {
+        index.refresh()?;
+        return Ok(f(index));
+    }
+    drop(guard);
+
+    let mut tail = read_tail(path, cutoff)?;
+    // Sessions running across the cutoff began before the tail
+    apply_first_seen(path, &mut tail)?;
+    Ok(f(&tail))
+}
+
+/// Bring the shared index for the history file at `path` up to date and run `f` against it
+pub fn with_history_index<R>(path: &Path, f: impl FnOnce(&HistoryIndex) -> R) -> Result<R, String> {
+    let mut guard = HISTORY_INDEXES
+        .lock()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+
+    let position = match guard.iter().position(|index| index.path == path) {
+        Some(position) => position,
+        None => {
+            guard.push(HistoryIndex::new(path));
+            guard.len() - 1
+        }
+    };
+    let index = &mut guard[position];
+    index.refresh()?;
+    Ok(f(index))
+}
+
+/// Drop every cached index so days are bucketed again on next use (after a timezone change)
+pub fn clear_history_indexes() {
+    if let Ok(mut guard) = HISTORY_INDEXES.lock() {
+        guard.clear();
+    }
+}
+
+/// Rewrite history.jsonl dropping blank lines and lines that aren't valid JSON (e.g.
+/// cut short by a crash). Valid JSON is kept even when the app can't read it.
+/// A trailing line without a newline may still be being written, so it is kept as is.
+/// `before_replace` runs once the cleaned copy is written, just before bytes appended
+/// since the read are carried over and the copy replaces the original.
+fn repair_history(
+    path: &Path,
+    backup: bool,
+    before_replace: impl FnOnce(),
+) -> Result<HistoryRepairReport, String> {
+    let contents = std::fs::read(path).map_err(|e| format!("Failed to read history: {}", e))?;
+    let complete_len = contents
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+
+    let mut clean = Vec::with_capacity(contents.len());
+    let mut lines_kept = 0;
+    let mut lines_unrecognized = 0;
+    let mut lines_removed = 0;
+    for line in contents[..complete_len].split(|&b| b == b'\n') {
+        if line.iter().all(|b| b.is_ascii_whitespace()) {
+            continue;
+        }
+        if is_json(line) {
+            clean.extend_from_slice(line);
+            clean.push(b'\n');
+            lines_kept += 1;
+            if serde_json::from_slice::<HistoryEntry>(line).is_err() {
+                lines_unrecognized += 1;
+            }
+        } else {
+            lines_removed += 1;
+        }
+    }
+    clean.extend_from_slice(&contents[complete_len..]);
+
+    let backup_path = if backup {
+        let backup_path = path.with_extension("jsonl.bak");
+        std::fs::copy(path, &backup_path)
+            .map_err(|e| format!("Failed to back up history: {}", e))?;
+        Some(backup_path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    // Write alongside and rename so an interrupted repair never leaves a half-written file
+    let tmp_path = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp_path, &clean).map_err(|e| format!("Failed to write history: {}", e))?;
+    before_replace();
+
+    // Carry over anything appended while the repair ran so it isn't lost in the rename
+    let mut appended = Vec::new();
+    let mut original = File::open(path).map_err(|e| format!("Failed to read history: {}", e))?;
+    original
+        .seek(SeekFrom::Start(contents.len() as u64))
+        .and_then(|_| original.read_to_end(&mut appended))
+        .map_err(|e| format!("Failed to read history: {}", e))?;
+    if !appended.is_empty() {
+        let mut tmp = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&tmp_path)
+            .map_err(|e| format!("Failed to write history: {}", e))?;
+        tmp.write_all(&appended)
+            .map_err(|e| format!("Failed to write history: {}", e))?;
+        clean.extend_from_slice(&appended);
+    }
+    std::fs::rename(&tmp_path, path).map_err(|e| format!("Failed to replace history: {}", e))?;
+
+    Ok(HistoryRepairReport {
+        lines_kept,
+        lines_unrecognized,
+        lines_removed,
+        bytes_before: contents.len() as u64,
+        bytes_after: clean.len() as u64,
+        backup_path,
+    })
+}
+
+#[tauri::command]
+pub async fn get_history_diagnostics() -> Result<HistoryDiagnostics, AppError> {
+    let path = get_history_path().ok_or("Could not find home directory")?;
+    let file_size = std::fs::metadata(&path)
+        .map_err(|_| {
+            AppError::FileNotFound(
+                "History file not found. Use Claude Code to generate usage data.".to_string(),
+            )
+        })?
+        .len();
+
+    Ok(with_history_index(&path, |index| HistoryDiagnostics {
+        file_size,
+        valid_lines: index.records.len(),
+        malformed_count: index.malformed.len(),
+        malformed_lines: index.malformed.clone(),
+        unrecognized_count: index.unrecognized.len(),
+        unrecognized_lines: index.unrecognized.clone(),
+        trailing_bytes: file_size.saturating_sub(index.offset),
+    })?)
+}
+
+#[tauri::command]
+pub async fn repair_history_file(backup: Option<bool>) -> Result<HistoryRepairReport, AppError> {
+    let path = get_history_path().ok_or("Could not find home directory")?;
+
+    // Claude Code appends to the file while it runs; rewriting it then risks losing entries
+    let running = scan_claude_processes().len();
+    if running > 0 {
+        return Err(AppError::Other(format!(
+            "Close Claude Code before repairing history ({} process{} running)",
+            running,
+            if running == 1 { "" } else { "es" }
+        )));
+    }
+
+    // Hold the index lock so no refresh reads the file mid-rewrite, then drop the stale index
+    let mut guard = HISTORY_INDEXES
+        .lock()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let report = repair_history(&path, backup.unwrap_or(true), || {})?;
+    guard.retain(|index| index.path != path);
+    if let Ok(mut lookups) = FIRST_SEEN.lock() {
+        lookups.retain(|lookup| lookup.path != path);
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn get_history_daily_summaries(since: Option<String>) -> Result<Vec<DaySummary>, String> {
+    let since = since
+        .as_deref()
+        .map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d"))
+        .transpose()
+        .map_err(|e| format!("Invalid date: {}", e))?;
+    let path = get_history_path().ok_or("Could not find home directory")?;
+    with_history_index(&path, |index| index.day_summaries(since))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{history_entry as entry, TempDir};
+
+    #[test]
+    fn repair_keeps_lines_appended_during_repair() {
+        let dir = TempDir::new("repair");
+        let path = dir.path().join("history.jsonl");
+        let legacy = "{\"display\":\"old\",\"timestamp\":0,\"project\":\"/work/app\"}\n";
+        let original = format!("{}{}{{broken\n\n{}", legacy, entry("a", 1), entry("a", 2));
+        std::fs::write(&path, &original).unwrap();
+
+        let appended = entry("b", 3);
+        let report = repair_history(&path, false, || {
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(appended.as_bytes()).unwrap();
+        })
+        .unwrap();
+
+        let repaired = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            repaired,
+            format!("{}{}{}{}", legacy, entry("a", 1), entry("a", 2), appended)
+        );
+        assert_eq!(report.lines_kept, 3);
+        assert_eq!(report.lines_unrecognized, 1);
+        assert_eq!(report.lines_removed, 1);
+        assert_eq!(report.bytes_after, repaired.len() as u64);
+    }
+}
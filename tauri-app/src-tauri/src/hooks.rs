@@ -0,0 +1,198 @@
+// tauri-app/src-tauri/src/hooks.rs
+
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+const HOOKS_KEY: &str = "commandHooks";
+const DEFAULT_TIMEOUT_SECONDS: u32 = 30;
+
+/// Events a hook can run on
+pub const HOOK_EVENTS: [&str; 10] = [
+    "session-started",
+    "session-idle",
+    "session-resumed",
+    "session-ended",
+    "session-cost-threshold",
+    "session-runaway",
+    "budget-exceeded",
+    "daily-rollover",
+    "prometheus-down",
+    "prometheus-up",
+];
+
+/// A shell command run when `event` fires, receiving the event as JSON on stdin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandHook {
+    pub id: String,
+    pub name: String,
+    pub event: String,
+    pub command: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_timeout")]
+    pub timeout_seconds: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_timeout() -> u32 {
+    DEFAULT_TIMEOUT_SECONDS
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookRunResult {
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub timed_out: bool,
+}
+
+fn load_hooks(app: &AppHandle) -> Vec<CommandHook> {
+    load_setting(app, HOOKS_KEY)
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+async fn run_hook(hook: &CommandHook, input: &Value) -> Result<HookRunResult, String> {
+    let mut child = shell_command(&hook.command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start hook {}: {}", hook.name, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let body = serde_json::to_vec(input).map_err(|e| e.to_string())?;
+        // A hook that ignores stdin may close it early; that's not an error
+        let _ = stdin.write_all(&body).await;
+    }
+
+    let timeout = Duration::from_secs(hook.timeout_seconds.max(1) as u64);
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(output) => {
+            let output = output.map_err(|e| e.to_string())?;
+            Ok(HookRunResult {
+                exit_code: output.status.code(),
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                timed_out: false,
+            })
+        }
+        // Dropping the future drops the child, which kills it
+        Err(_) => Ok(HookRunResult {
+            exit_code: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            timed_out: true,
+        }),
+    }
+}
+
+fn hook_input(event: &str, payload: &Value) -> Value {
+    json!({
+        "event": event,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "data": payload,
+    })
+}
+
+/// Run every enabled hook registered for `event` in the background
+pub fn dispatch(app: &AppHandle, event: &str, payload: &Value) {
+    let hooks: Vec<CommandHook> = load_hooks(app)
+        .into_iter()
+        .filter(|h| h.enabled && h.event == event)
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+
+    let input = hook_input(event, payload);
+    for hook in hooks {
+        let input = input.clone();
+        tauri::async_runtime::spawn(async move {
+            match run_hook(&hook, &input).await {
+                Ok(result) if result.timed_out => {
+                    eprintln!(
+                        "Hook {} timed out after {}s",
+                        hook.name, hook.timeout_seconds
+                    )
+                }
+                Ok(result) if result.exit_code != Some(0) => {
+                    eprintln!(
+                        "Hook {} exited with {:?}: {}",
+                        hook.name, result.exit_code, result.stderr
+                    )
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("{}", e),
+            }
+        });
+    }
+}
+
+#[tauri::command]
+pub async fn get_command_hooks(app: AppHandle) -> Result<Vec<CommandHook>, String> {
+    Ok(load_hooks(&app))
+}
+
+#[tauri::command]
+pub async fn save_command_hook(app: AppHandle, hook: CommandHook) -> Result<CommandHook, String> {
+    if !HOOK_EVENTS.contains(&hook.event.as_str()) {
+        return Err(format!("Unknown hook event: {}", hook.event));
+    }
+    if hook.command.trim().is_empty() {
+        return Err("Hook command cannot be empty".to_string());
+    }
+
+    let mut hook = hook;
+    if hook.id.is_empty() {
+        hook.id = format!("hook-{}", chrono::Utc::now().timestamp_millis());
+    }
+
+    let mut hooks = load_hooks(&app);
+    match hooks.iter_mut().find(|h| h.id == hook.id) {
+        Some(existing) => *existing = hook.clone(),
+        None => hooks.push(hook.clone()),
+    }
+    save_setting(&app, HOOKS_KEY, &hooks)?;
+    Ok(hook)
+}
+
+#[tauri::command]
+pub async fn delete_command_hook(app: AppHandle, hook_id: String) -> Result<(), String> {
+    let mut hooks = load_hooks(&app);
+    hooks.retain(|h| h.id != hook_id);
+    save_setting(&app, HOOKS_KEY, &hooks)
+}
+
+/// Run a hook once with a sample payload and return its output
+#[tauri::command]
+pub async fn test_command_hook(app: AppHandle, hook_id: String) -> Result<HookRunResult, String> {
+    let hook = load_hooks(&app)
+        .into_iter()
+        .find(|h| h.id == hook_id)
+        .ok_or(format!("Hook not found: {}", hook_id))?;
+    let input = hook_input(&hook.event, &json!({ "test": true }));
+    run_hook(&hook, &input).await
+}
@@ -1,14 +1,27 @@
 // tauri-app/src-tauri/src/insights.rs
 
-use chrono::{Datelike, Duration, Local, NaiveDate};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::commands::custom_bounds;
+use crate::data_sources::enabled_sources;
+use crate::error::AppError;
+use crate::metrics::MetricsSource;
+use crate::pricing::{resolve_profile, PricingProfile};
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::load_query_tuning;
+use crate::settings::{frontend_pricing_provider, frontend_prometheus_url};
+use crate::stats_builder::local_stats_contents;
+use crate::timezone;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
+use tauri::AppHandle;
 
 /// Raw stats cache from ~/.claude/stats-cache.json
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Built section by section (see `parse_stats_cache`) so one unreadable section
+/// doesn't discard the rest of the file.
+#[derive(Debug, Default)]
 pub struct StatsCache {
     pub daily_activity: Vec<DailyActivity>,
     pub daily_model_tokens: Option<Vec<DailyModelTokens>>,
@@ -19,15 +32,19 @@ pub struct StatsCache {
     pub longest_session: Option<LongestSession>,
     pub first_session_date: Option<String>,
     pub hour_counts: Option<HashMap<String, u32>>,
+    /// Sections that were present but could not be read (fell back to defaults)
+    pub unreadable_sections: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DailyActivity {
     pub date: String,
+    #[serde(default)]
     pub message_count: u32,
+    #[serde(default)]
     pub session_count: u32,
-    #[allow(dead_code)]
+    #[serde(default)]
     pub tool_call_count: u32,
 }
 
@@ -38,12 +55,16 @@ pub struct DailyModelTokens {
     pub tokens_by_model: HashMap<String, u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelUsage {
+    #[serde(default)]
     pub input_tokens: u64,
+    #[serde(default)]
     pub output_tokens: u64,
+    #[serde(default)]
     pub cache_read_input_tokens: u64,
+    #[serde(default)]
     pub cache_creation_input_tokens: u64,
 }
 
@@ -52,6 +73,7 @@ pub struct ModelUsage {
 pub struct LongestSession {
     pub duration: u64,
     #[allow(dead_code)]
+    #[serde(default)]
     pub message_count: u32,
 }
 
@@ -60,10 +82,18 @@ pub struct LongestSession {
 #[serde(rename_all = "camelCase")]
 pub struct InsightsData {
     pub period: String,
+    /// First and last day of the current period, inclusive
+    pub start_date: String,
+    pub end_date: String,
     pub comparison: PeriodComparison,
     pub daily_activity: Vec<DailyActivityPoint>,
     pub sessions_per_day: Vec<DailyActivityPoint>,
+    pub tool_calls_per_day: Vec<DailyActivityPoint>,
     pub peak_activity: PeakActivity,
+    pub unreadable_sections: Vec<String>,
+    /// `Prometheus` when sessions, tokens and cost came from Prometheus (messages
+    /// and tool calls are only recorded locally)
+    pub source: MetricsSource,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -71,6 +101,7 @@ pub struct InsightsData {
 pub struct PeriodComparison {
     pub messages: MetricComparison,
     pub sessions: MetricComparison,
+    pub tool_calls: MetricComparison,
     pub tokens: MetricComparison,
     pub estimated_cost: MetricComparison,
 }
@@ -120,20 +151,224 @@ pub fn get_stats_cache_path() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(".claude").join("stats-cache.json"))
 }
 
-pub fn load_stats_cache() -> Result<StatsCache, String> {
-    let path = get_stats_cache_path().ok_or("Could not find home directory")?;
-    let contents = fs::read_to_string(&path)
-        .map_err(|_| "Stats cache file not found. Use Claude Code to generate usage data.")?;
-    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse stats cache: {}", e))
+const STATS_CACHE_MISSING: &str =
+    "Stats cache file not found. Use Claude Code to generate usage data.";
+
+/// Load the local stats cache merged with the stats caches of any extra data sources
+pub fn load_merged_stats_cache(app: &AppHandle) -> Result<StatsCache, AppError> {
+    let mut merged: Option<StatsCache> = None;
+    let mut seen_contents = Vec::new();
+
+    for source in enabled_sources(app) {
+        // Locally, fall back to a cache built from transcripts when Claude's is
+        // missing or stale
+        let contents = if source.is_local() {
+            local_stats_contents(&source.stats_cache_path())
+        } else {
+            fs::read_to_string(source.stats_cache_path()).ok()
+        };
+        let Some(contents) = contents else {
+            continue;
+        };
+        // A source pointing at an identical copy would double every count
+        if seen_contents.contains(&contents) {
+            continue;
+        }
+
+        let mut cache = parse_stats_cache(&contents)?;
+        if !source.is_local() {
+            for section in &mut cache.unreadable_sections {
+                *section = format!("{}: {}", source.name, section);
+            }
+        }
+        seen_contents.push(contents);
+
+        match merged.as_mut() {
+            Some(merged) => merged.merge(cache),
+            None => merged = Some(cache),
+        }
+    }
+
+    merged.ok_or_else(|| AppError::FileNotFound(STATS_CACHE_MISSING.to_string()))
+}
+
+impl StatsCache {
+    /// Fold another machine's stats cache into this one, summing per-day and per-model counts
+    fn merge(&mut self, other: StatsCache) {
+        for activity in other.daily_activity {
+            match self
+                .daily_activity
+                .iter_mut()
+                .find(|a| a.date == activity.date)
+            {
+                Some(existing) => {
+                    existing.message_count += activity.message_count;
+                    existing.session_count += activity.session_count;
+                    existing.tool_call_count += activity.tool_call_count;
+                }
+                None => self.daily_activity.push(activity),
+            }
+        }
+        self.daily_activity.sort_by(|a, b| a.date.cmp(&b.date));
+
+        if let Some(other_tokens) = other.daily_model_tokens {
+            let daily_tokens = self.daily_model_tokens.get_or_insert_with(Vec::new);
+            for day in other_tokens {
+                match daily_tokens.iter_mut().find(|d| d.date == day.date) {
+                    Some(existing) => {
+                        for (model, tokens) in day.tokens_by_model {
+                            *existing.tokens_by_model.entry(model).or_insert(0) += tokens;
+                        }
+                    }
+                    None => daily_tokens.push(day),
+                }
+            }
+            daily_tokens.sort_by(|a, b| a.date.cmp(&b.date));
+        }
+
+        for (model, usage) in other.model_usage {
+            let existing = self.model_usage.entry(model).or_default();
+            existing.input_tokens += usage.input_tokens;
+            existing.output_tokens += usage.output_tokens;
+            existing.cache_read_input_tokens += usage.cache_read_input_tokens;
+            existing.cache_creation_input_tokens += usage.cache_creation_input_tokens;
+        }
+
+        self.total_sessions += other.total_sessions;
+        self.total_messages += other.total_messages;
+
+        if other.longest_session.as_ref().is_some_and(|o| {
+            self.longest_session
+                .as_ref()
+                .is_none_or(|s| o.duration > s.duration)
+        }) {
+            self.longest_session = other.longest_session;
+        }
+
+        self.first_session_date = match (self.first_session_date.take(), other.first_session_date) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        if let Some(other_hours) = other.hour_counts {
+            let hour_counts = self.hour_counts.get_or_insert_with(HashMap::new);
+            for (hour, count) in other_hours {
+                *hour_counts.entry(hour).or_insert(0) += count;
+            }
+        }
+
+        self.unreadable_sections.extend(other.unreadable_sections);
+    }
+}
+
+/// Deserialize one top-level section, recording it as unreadable on failure.
+/// Missing sections are not an error (older files omit some of them).
+fn read_section<T: DeserializeOwned + Default>(
+    root: &Map<String, Value>,
+    key: &str,
+    unreadable: &mut Vec<String>,
+) -> T {
+    match root.get(key) {
+        None | Some(Value::Null) => T::default(),
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|_| {
+            unreadable.push(key.to_string());
+            T::default()
+        }),
+    }
+}
+
+/// Deserialize an array section entry by entry, keeping the readable entries
+fn read_list_section<T: DeserializeOwned>(
+    root: &Map<String, Value>,
+    key: &str,
+    unreadable: &mut Vec<String>,
+) -> Option<Vec<T>> {
+    let Some(Value::Array(items)) = root.get(key) else {
+        if root.get(key).is_some_and(|v| !v.is_null()) {
+            unreadable.push(key.to_string());
+        }
+        return None;
+    };
+
+    let parsed: Vec<T> = items
+        .iter()
+        .filter_map(|item| serde_json::from_value(item.clone()).ok())
+        .collect();
+    if parsed.len() < items.len() {
+        unreadable.push(format!(
+            "{} ({} of {} entries)",
+            key,
+            items.len() - parsed.len(),
+            items.len()
+        ));
+    }
+    Some(parsed)
 }
 
-fn get_period_dates(period: &str) -> (NaiveDate, NaiveDate, NaiveDate, NaiveDate) {
-    let today = Local::now().date_naive();
+pub fn parse_stats_cache(contents: &str) -> Result<StatsCache, AppError> {
+    let root: Map<String, Value> = serde_json::from_str(contents)
+        .map_err(|e| AppError::ParseError(format!("Failed to parse stats cache: {}", e)))?;
+    let mut unreadable = Vec::new();
+
+    // Known schema versions share the same section layout; anything newer is
+    // read best-effort and flagged so the UI can suggest updating the app
+    match root.get("version").and_then(|v| v.as_u64()) {
+        None | Some(1) => {}
+        Some(version) => unreadable.push(format!("version (unknown schema v{})", version)),
+    }
+
+    Ok(StatsCache {
+        daily_activity: read_list_section(&root, "dailyActivity", &mut unreadable)
+            .unwrap_or_default(),
+        daily_model_tokens: read_list_section(&root, "dailyModelTokens", &mut unreadable),
+        model_usage: read_section(&root, "modelUsage", &mut unreadable),
+        total_sessions: read_section(&root, "totalSessions", &mut unreadable),
+        total_messages: read_section(&root, "totalMessages", &mut unreadable),
+        longest_session: read_section(&root, "longestSession", &mut unreadable),
+        first_session_date: read_section(&root, "firstSessionDate", &mut unreadable),
+        hour_counts: read_section(&root, "hourCounts", &mut unreadable),
+        unreadable_sections: unreadable,
+    })
+}
+
+/// No Claude Code usage predates its release, so `all_time` starts here
+fn claude_code_release() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 2, 24).unwrap_or_default()
+}
+
+/// Week count of a `last_N_weeks` period
+fn full_weeks(period: &str) -> Option<i64> {
+    period
+        .strip_prefix("last_")?
+        .strip_suffix("_weeks")?
+        .parse()
+        .ok()
+        .filter(|weeks| (1..=52).contains(weeks))
+}
+
+/// Current and previous period as inclusive (start, end, prev_start, prev_end)
+type PeriodDates = (NaiveDate, NaiveDate, NaiveDate, NaiveDate);
+
+fn get_period_dates(period: &str) -> PeriodDates {
+    let today = timezone::today();
+
+    if let Some(weeks) = full_weeks(period) {
+        // Complete weeks only, ending before the current one
+        let end = timezone::week_start(today) - Duration::days(1);
+        let start = end - Duration::weeks(weeks) + Duration::days(1);
+        let prev_end = start - Duration::days(1);
+        let prev_start = start - Duration::weeks(weeks);
+        return (start, end, prev_start, prev_end);
+    }
 
     match period {
-        "this_week" => {
-            let week_start =
-                today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        "this_week" | "iso_week" => {
+            // ISO weeks always start on Monday
+            let week_start = if period == "iso_week" {
+                today - Duration::days(today.weekday().num_days_from_monday() as i64)
+            } else {
+                timezone::week_start(today)
+            };
             let prev_week_start = week_start - Duration::days(7);
             let prev_week_end = week_start - Duration::days(1);
             (week_start, today, prev_week_start, prev_week_end)
@@ -145,6 +380,33 @@ fn get_period_dates(period: &str) -> (NaiveDate, NaiveDate, NaiveDate, NaiveDate
                 NaiveDate::from_ymd_opt(prev_month_end.year(), prev_month_end.month(), 1).unwrap();
             (month_start, today, prev_month_start, prev_month_end)
         }
+        "this_quarter" => {
+            let quarter_month = (today.month0() / 3) * 3 + 1;
+            let quarter_start = NaiveDate::from_ymd_opt(today.year(), quarter_month, 1).unwrap();
+            let prev_quarter_end = quarter_start - Duration::days(1);
+            let prev_quarter_start = prev_quarter_end
+                .checked_sub_months(Months::new(2))
+                .and_then(|d| d.with_day(1))
+                .unwrap();
+            (quarter_start, today, prev_quarter_start, prev_quarter_end)
+        }
+        "this_year" => {
+            let year_start = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap();
+            let prev_year_end = year_start - Duration::days(1);
+            let prev_year_start = NaiveDate::from_ymd_opt(prev_year_end.year(), 1, 1).unwrap();
+            (year_start, today, prev_year_start, prev_year_end)
+        }
+        "last_30_days" => {
+            let start = today - Duration::days(29);
+            let prev_end = start - Duration::days(1);
+            let prev_start = prev_end - Duration::days(29);
+            (start, today, prev_start, prev_end)
+        }
+        "all_time" => {
+            // Compared with the all-time totals as of 30 days ago
+            let start = claude_code_release();
+            (start, today, start, today - Duration::days(30))
+        }
         _ => {
             // last_7_days
             let start = today - Duration::days(6);
@@ -155,23 +417,48 @@ fn get_period_dates(period: &str) -> (NaiveDate, NaiveDate, NaiveDate, NaiveDate
     }
 }
 
+/// Dates of a named period, or for "custom" the days from `custom_start` up to
+/// `custom_end` (unix seconds, end exclusive) compared with as many days before
+fn resolve_period_dates(
+    period: &str,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<PeriodDates, AppError> {
+    if period != "custom" {
+        return Ok(get_period_dates(period));
+    }
+    let (start, end) = custom_bounds(custom_start, custom_end)?;
+    if end <= start {
+        return Err(AppError::InvalidInput(
+            "Custom end time must be after the start time".to_string(),
+        ));
+    }
+    let (start, end) = (timezone::date_of(start), timezone::date_of(end - 1));
+    let days = (end - start).num_days() + 1;
+    let prev_end = start - Duration::days(1);
+    let prev_start = prev_end - Duration::days(days - 1);
+    Ok((start, end, prev_start, prev_end))
+}
+
 fn sum_activity_in_range(
     activities: &[DailyActivity],
     start: NaiveDate,
     end: NaiveDate,
-) -> (u32, u32) {
+) -> (u32, u32, u32) {
     let mut messages = 0u32;
     let mut sessions = 0u32;
+    let mut tool_calls = 0u32;
 
     for activity in activities {
         if let Ok(date) = NaiveDate::parse_from_str(&activity.date, "%Y-%m-%d") {
             if date >= start && date <= end {
                 messages += activity.message_count;
                 sessions += activity.session_count;
+                tool_calls += activity.tool_call_count;
             }
         }
     }
-    (messages, sessions)
+    (messages, sessions, tool_calls)
 }
 
 fn sum_tokens_in_range(
@@ -192,55 +479,120 @@ fn sum_tokens_in_range(
     total
 }
 
-fn calculate_cost(tokens: u64, pricing_provider: &str) -> f64 {
-    // Simplified cost calculation for period comparisons (using average of input/output rates)
-    // This is used for period-over-period comparisons where we don't have detailed token type breakdown
-    let rate_per_million = match pricing_provider {
-        "google-vertex" => 16.5, // 10% premium
-        _ => 15.0,               // anthropic, aws-bedrock
+/// Cost of `tokens` of a model with no modelUsage entry to derive a token type
+/// breakdown from: the model's rates applied to the mix across all models, or
+/// the average of its input and output rates when there's no mix at all
+fn calculate_cost(model: &str, tokens: u64, mix: &ModelUsage, pricing: &PricingProfile) -> f64 {
+    let mix_tokens = model_usage_tokens(mix);
+    let rate = if mix_tokens > 0 {
+        model_usage_cost(model, mix, pricing) / mix_tokens as f64
+    } else {
+        let rates = pricing.rates_for(model);
+        (rates.input + rates.output) / 2.0 / 1_000_000.0
     };
-    (tokens as f64 / 1_000_000.0) * rate_per_million
-}
-
-fn calculate_detailed_cost(model_usage: &HashMap<String, ModelUsage>, pricing_provider: &str) -> f64 {
-    let mut total_cost = 0.0;
-    let model_lower = |m: &str| m.to_lowercase();
-
-    for (model, usage) in model_usage {
-        let model_lc = model_lower(model);
-        // Determine pricing based on model (per million tokens)
-        // Rates: (input, output, cache_read, cache_creation)
-        // Pricing: cache_read = 10% of input, cache_create = 125% of input
-        let (input_rate, output_rate, cache_read_rate, cache_create_rate) = if model_lc.contains("opus") {
-            // Claude Opus 4/4.5: $5/$25 per MTok
-            match pricing_provider {
-                "google-vertex" => (5.5, 27.5, 0.55, 6.875), // 10% premium
-                "aws-bedrock" => (5.0, 25.0, 0.50, 6.25),
-                _ => (5.0, 25.0, 0.50, 6.25), // anthropic
-            }
-        } else if model_lc.contains("haiku") {
-            // Claude Haiku 3.5: $1/$5 per MTok
-            match pricing_provider {
-                "google-vertex" => (1.1, 5.5, 0.11, 1.375), // 10% premium
-                "aws-bedrock" => (1.0, 5.0, 0.10, 1.25),
-                _ => (1.0, 5.0, 0.10, 1.25), // anthropic
-            }
-        } else {
-            // Default to Sonnet 4 pricing (also covers Sonnet 3.5): $3/$15 per MTok
-            match pricing_provider {
-                "google-vertex" => (3.3, 16.5, 0.33, 4.125), // 10% premium
-                "aws-bedrock" => (3.0, 15.0, 0.30, 3.75),
-                _ => (3.0, 15.0, 0.30, 3.75), // anthropic
-            }
+    tokens as f64 * rate
+}
+
+fn model_usage_cost(model: &str, usage: &ModelUsage, pricing: &PricingProfile) -> f64 {
+    let rates = pricing.rates_for(model);
+
+    (usage.input_tokens as f64 / 1_000_000.0) * rates.input
+        + (usage.output_tokens as f64 / 1_000_000.0) * rates.output
+        + (usage.cache_read_input_tokens as f64 / 1_000_000.0) * rates.cache_read
+        + (usage.cache_creation_input_tokens as f64 / 1_000_000.0) * rates.cache_creation
+}
+
+pub(crate) fn model_usage_tokens(usage: &ModelUsage) -> u64 {
+    usage.input_tokens
+        + usage.output_tokens
+        + usage.cache_read_input_tokens
+        + usage.cache_creation_input_tokens
+}
+
+fn calculate_detailed_cost(
+    model_usage: &HashMap<String, ModelUsage>,
+    pricing: &PricingProfile,
+) -> f64 {
+    model_usage
+        .iter()
+        .map(|(model, usage)| model_usage_cost(model, usage, pricing))
+        .sum()
+}
+
+/// Estimate cost for a date range from daily per-model token totals.
+/// Daily tokens carry no type breakdown, so each model's all-time input/output/cache
+/// mix from modelUsage is applied proportionally (a blended per-token rate).
+/// Models missing from modelUsage use their own rates with the overall mix.
+fn sum_cost_in_range(
+    daily_tokens: &Option<Vec<DailyModelTokens>>,
+    model_usage: &HashMap<String, ModelUsage>,
+    start: NaiveDate,
+    end: NaiveDate,
+    pricing: &PricingProfile,
+) -> f64 {
+    let Some(days) = daily_tokens else { return 0.0 };
+
+    let rates = blended_rates(model_usage, pricing);
+    let mut total = 0.0;
+    for day in days {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
         };
+        if date < start || date > end {
+            continue;
+        }
+        for (model, &tokens) in &day.tokens_by_model {
+            total += blended_cost(model, tokens, &rates, pricing);
+        }
+    }
+    total
+}
+
+/// Per-token cost of each model from its all-time token type mix, and the mix
+/// across all models for models without a breakdown of their own
+pub(crate) struct BlendedRates<'a> {
+    by_model: HashMap<&'a str, f64>,
+    overall_mix: ModelUsage,
+}
 
-        total_cost += (usage.input_tokens as f64 / 1_000_000.0) * input_rate;
-        total_cost += (usage.output_tokens as f64 / 1_000_000.0) * output_rate;
-        total_cost += (usage.cache_read_input_tokens as f64 / 1_000_000.0) * cache_read_rate;
-        total_cost += (usage.cache_creation_input_tokens as f64 / 1_000_000.0) * cache_create_rate;
+pub(crate) fn blended_rates<'a>(
+    model_usage: &'a HashMap<String, ModelUsage>,
+    pricing: &PricingProfile,
+) -> BlendedRates<'a> {
+    let by_model = model_usage
+        .iter()
+        .filter(|(_, usage)| model_usage_tokens(usage) > 0)
+        .map(|(model, usage)| {
+            let rate = model_usage_cost(model, usage, pricing) / model_usage_tokens(usage) as f64;
+            (model.as_str(), rate)
+        })
+        .collect();
+    let overall_mix = model_usage
+        .values()
+        .fold(ModelUsage::default(), |mix, usage| ModelUsage {
+            input_tokens: mix.input_tokens + usage.input_tokens,
+            output_tokens: mix.output_tokens + usage.output_tokens,
+            cache_read_input_tokens: mix.cache_read_input_tokens + usage.cache_read_input_tokens,
+            cache_creation_input_tokens: mix.cache_creation_input_tokens
+                + usage.cache_creation_input_tokens,
+        });
+    BlendedRates {
+        by_model,
+        overall_mix,
     }
+}
 
-    total_cost
+/// Cost of `tokens` of `model` at its blended rate, else its rates at the overall mix
+pub(crate) fn blended_cost(
+    model: &str,
+    tokens: u64,
+    rates: &BlendedRates,
+    pricing: &PricingProfile,
+) -> f64 {
+    match rates.by_model.get(model) {
+        Some(rate) => tokens as f64 * rate,
+        None => calculate_cost(model, tokens, &rates.overall_mix, pricing),
+    }
 }
 
 fn find_peak_hour(hour_counts: &Option<HashMap<String, u32>>) -> Option<u32> {
@@ -294,29 +646,69 @@ fn get_sessions_per_day_points(
         .collect()
 }
 
-pub fn compute_insights(period: &str, pricing_provider: &str) -> Result<InsightsData, String> {
-    let cache = load_stats_cache()?;
-    let (curr_start, curr_end, prev_start, prev_end) = get_period_dates(period);
+fn get_tool_calls_per_day_points(
+    activities: &[DailyActivity],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<DailyActivityPoint> {
+    activities
+        .iter()
+        .filter_map(|a| {
+            let date = NaiveDate::parse_from_str(&a.date, "%Y-%m-%d").ok()?;
+            if date >= start && date <= end {
+                Some(DailyActivityPoint {
+                    date: a.date.clone(),
+                    value: a.tool_call_count as f64,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn compute_insights(
+    cache: StatsCache,
+    period: &str,
+    dates: PeriodDates,
+    pricing: &PricingProfile,
+) -> Result<InsightsData, AppError> {
+    let (curr_start, curr_end, prev_start, prev_end) = dates;
 
     // Calculate comparisons
-    let (curr_msgs, curr_sess) = sum_activity_in_range(&cache.daily_activity, curr_start, curr_end);
-    let (prev_msgs, prev_sess) = sum_activity_in_range(&cache.daily_activity, prev_start, prev_end);
+    let (curr_msgs, curr_sess, curr_tools) =
+        sum_activity_in_range(&cache.daily_activity, curr_start, curr_end);
+    let (prev_msgs, prev_sess, prev_tools) =
+        sum_activity_in_range(&cache.daily_activity, prev_start, prev_end);
 
     let curr_tokens = sum_tokens_in_range(&cache.daily_model_tokens, curr_start, curr_end);
     let prev_tokens = sum_tokens_in_range(&cache.daily_model_tokens, prev_start, prev_end);
 
-    let curr_cost = calculate_cost(curr_tokens, pricing_provider);
-    let prev_cost = calculate_cost(prev_tokens, pricing_provider);
+    let curr_cost = sum_cost_in_range(
+        &cache.daily_model_tokens,
+        &cache.model_usage,
+        curr_start,
+        curr_end,
+        pricing,
+    );
+    let prev_cost = sum_cost_in_range(
+        &cache.daily_model_tokens,
+        &cache.model_usage,
+        prev_start,
+        prev_end,
+        pricing,
+    );
 
     let comparison = PeriodComparison {
         messages: MetricComparison::new(curr_msgs as f64, prev_msgs as f64),
         sessions: MetricComparison::new(curr_sess as f64, prev_sess as f64),
+        tool_calls: MetricComparison::new(curr_tools as f64, prev_tools as f64),
         tokens: MetricComparison::new(curr_tokens as f64, prev_tokens as f64),
         estimated_cost: MetricComparison::new(curr_cost, prev_cost),
     };
 
     // Calculate streak
-    let today = Local::now().date_naive();
+    let today = timezone::today();
     let yesterday = today - Duration::days(1);
     let mut sorted_dates: Vec<NaiveDate> = cache
         .daily_activity
@@ -350,22 +742,252 @@ pub fn compute_insights(period: &str, pricing_provider: &str) -> Result<Insights
 
     let daily_activity = get_daily_activity_points(&cache.daily_activity, curr_start, curr_end);
     let sessions_per_day = get_sessions_per_day_points(&cache.daily_activity, curr_start, curr_end);
+    let tool_calls_per_day =
+        get_tool_calls_per_day_points(&cache.daily_activity, curr_start, curr_end);
 
     Ok(InsightsData {
         period: period.to_string(),
+        start_date: curr_start.format("%Y-%m-%d").to_string(),
+        end_date: curr_end.format("%Y-%m-%d").to_string(),
         comparison,
         daily_activity,
         sessions_per_day,
+        tool_calls_per_day,
         peak_activity,
+        unreadable_sections: cache.unreadable_sections,
+        source: MetricsSource::Local,
     })
 }
 
+/// Per-day totals from Prometheus, keyed by local date
+#[derive(Debug, Default)]
+struct PrometheusDaily {
+    sessions: BTreeMap<NaiveDate, f64>,
+    tokens: BTreeMap<NaiveDate, f64>,
+    cost: BTreeMap<NaiveDate, f64>,
+}
+
+impl PrometheusDaily {
+    fn sum(days: &BTreeMap<NaiveDate, f64>, start: NaiveDate, end: NaiveDate) -> f64 {
+        days.range(start..=end).map(|(_, v)| v).sum()
+    }
+}
+
+/// Daily increases of the session, token and cost counters between `start` and
+/// `end`, one point per day evaluated at the following midnight
+async fn fetch_prometheus_daily(
+    app: &AppHandle,
+    prometheus_url: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<PrometheusDaily, AppError> {
+    let tuning = load_query_tuning(app);
+    let client =
+        PrometheusClient::new(prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let first = timezone::start_of_day(start + Duration::days(1));
+    let last = timezone::start_of_day(end + Duration::days(1));
+
+    let daily = |metric: &'static str| {
+        let query = format!(
+            "sum(increase({}[{}]))",
+            metric,
+            tuning.rate_window(metric, "1d")
+        );
+        let client = &client;
+        async move {
+            let results = client.query_range(&query, first, last, "1d").await?;
+            let mut days = BTreeMap::new();
+            for (timestamp, value) in results
+                .first()
+                .and_then(|r| r.values.clone())
+                .unwrap_or_default()
+            {
+                let date = timezone::date_of(timestamp as i64 - 1);
+                days.insert(date, value.parse::<f64>().unwrap_or(0.0));
+            }
+            Ok::<_, AppError>(days)
+        }
+    };
+    let (sessions, tokens, cost) = tokio::join!(
+        daily("claude_code_session_count_total"),
+        daily("claude_code_token_usage_tokens_total"),
+        daily("claude_code_cost_usage_USD_total"),
+    );
+    Ok(PrometheusDaily {
+        sessions: sessions?,
+        tokens: tokens?,
+        cost: cost?,
+    })
+}
+
+/// Replace the locally estimated sessions, tokens and cost with Prometheus totals
+fn apply_prometheus_daily(
+    insights: &mut InsightsData,
+    dates: PeriodDates,
+    daily: &PrometheusDaily,
+) {
+    let (curr_start, curr_end, prev_start, prev_end) = dates;
+    let compare = |days: &BTreeMap<NaiveDate, f64>| {
+        MetricComparison::new(
+            PrometheusDaily::sum(days, curr_start, curr_end),
+            PrometheusDaily::sum(days, prev_start, prev_end),
+        )
+    };
+
+    insights.comparison.sessions = compare(&daily.sessions);
+    insights.comparison.tokens = compare(&daily.tokens);
+    insights.comparison.estimated_cost = compare(&daily.cost);
+    insights.sessions_per_day = daily
+        .sessions
+        .range(curr_start..=curr_end)
+        .map(|(date, &value)| DailyActivityPoint {
+            date: date.format("%Y-%m-%d").to_string(),
+            value,
+        })
+        .collect();
+    insights.source = MetricsSource::Prometheus;
+}
+
+/// `source` is "local" (stats cache only, the default) or "prometheus", which
+/// takes sessions, tokens and cost from Prometheus and falls back to the stats
+/// cache alone when Prometheus can't be reached. A "custom" period covers
+/// `custom_start` to `custom_end` like the dashboard's custom range.
 #[tauri::command]
 pub async fn get_insights_data(
+    app: AppHandle,
     period: String,
     pricing_provider: String,
-) -> Result<InsightsData, String> {
-    compute_insights(&period, &pricing_provider)
+    source: Option<String>,
+    prometheus_url: Option<String>,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<InsightsData, AppError> {
+    let pricing = resolve_profile(&app, &pricing_provider);
+    let dates = resolve_period_dates(&period, custom_start, custom_end)?;
+    if source.as_deref() != Some("prometheus") {
+        return compute_insights(load_merged_stats_cache(&app)?, &period, dates, &pricing);
+    }
+
+    let url = prometheus_url.unwrap_or_else(|| frontend_prometheus_url(&app));
+    let (_, curr_end, prev_start, _) = dates;
+    let daily = fetch_prometheus_daily(&app, &url, prev_start, curr_end).await;
+    let cache = match (load_merged_stats_cache(&app), &daily) {
+        (Ok(cache), _) => cache,
+        // Prometheus alone is enough when the stats cache hasn't been written yet
+        (Err(AppError::FileNotFound(_)), Ok(_)) => StatsCache::default(),
+        (Err(e), _) => return Err(e),
+    };
+
+    let mut insights = compute_insights(cache, &period, dates, &pricing)?;
+    match daily {
+        Ok(daily) => apply_prometheus_daily(&mut insights, dates, &daily),
+        Err(e) => eprintln!("Insights: Prometheus unavailable, using stats cache: {}", e),
+    }
+    Ok(insights)
+}
+
+fn period_label(insights: &InsightsData) -> String {
+    if let Some(weeks) = full_weeks(&insights.period) {
+        return format!("Last {} Full Weeks", weeks);
+    }
+    match insights.period.as_str() {
+        "this_week" => "This Week".to_string(),
+        "iso_week" => format!("ISO Week {}", timezone::today().iso_week().week()),
+        "this_month" => "This Month".to_string(),
+        "this_quarter" => {
+            let today = timezone::today();
+            format!("Q{} {}", today.month0() / 3 + 1, today.year())
+        }
+        "this_year" => timezone::today().year().to_string(),
+        "last_30_days" => "Last 30 Days".to_string(),
+        "all_time" => "All Time".to_string(),
+        "custom" => format!("{} to {}", insights.start_date, insights.end_date),
+        _ => "Last 7 Days".to_string(),
+    }
+}
+
+fn format_change(comparison: &MetricComparison) -> String {
+    match comparison.percent_change {
+        Some(change) if change >= 0.0 => format!("+{:.0}%", change),
+        Some(change) => format!("{:.0}%", change),
+        None => "—".to_string(),
+    }
+}
+
+/// Render insights as a short markdown summary for standup notes
+fn insights_to_markdown(insights: &InsightsData) -> String {
+    let mut md = format!("## Claude Code — {}\n\n", period_label(insights));
+
+    md.push_str("| Metric | Current | Previous | Change |\n");
+    md.push_str("|---|---:|---:|---:|\n");
+    let c = &insights.comparison;
+    let rows = [
+        ("Messages", &c.messages, false),
+        ("Sessions", &c.sessions, false),
+        ("Tool calls", &c.tool_calls, false),
+        ("Tokens", &c.tokens, false),
+        ("Est. cost", &c.estimated_cost, true),
+    ];
+    for (name, comparison, is_cost) in rows {
+        let (current, previous) = if is_cost {
+            (
+                format!("${:.2}", comparison.current),
+                format!("${:.2}", comparison.previous),
+            )
+        } else {
+            (
+                format!("{:.0}", comparison.current),
+                format!("{:.0}", comparison.previous),
+            )
+        };
+        md.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            name,
+            current,
+            previous,
+            format_change(comparison)
+        ));
+    }
+
+    let peak = &insights.peak_activity;
+    md.push('\n');
+    if let Some(hour) = peak.most_active_hour {
+        md.push_str(&format!("- **Most active hour:** {:02}:00\n", hour));
+    }
+    if let Some(busiest) = insights.daily_activity.iter().max_by(|a, b| {
+        a.value
+            .partial_cmp(&b.value)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }) {
+        md.push_str(&format!(
+            "- **Busiest day:** {} ({:.0} messages)\n",
+            busiest.date, busiest.value
+        ));
+    }
+    if let Some(minutes) = peak.longest_session_minutes {
+        md.push_str(&format!("- **Longest session:** {} min\n", minutes));
+    }
+    md.push_str(&format!(
+        "- **Current streak:** {} day{}\n",
+        peak.current_streak,
+        if peak.current_streak == 1 { "" } else { "s" }
+    ));
+
+    md
+}
+
+#[tauri::command]
+pub async fn export_insights_markdown(
+    app: AppHandle,
+    period: String,
+    pricing_provider: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<String, AppError> {
+    let pricing = resolve_profile(&app, &pricing_provider);
+    let dates = resolve_period_dates(&period, custom_start, custom_end)?;
+    let insights = compute_insights(load_merged_stats_cache(&app)?, &period, dates, &pricing)?;
+    Ok(insights_to_markdown(&insights))
 }
 
 /// Response type for local stats cache view
@@ -373,6 +995,10 @@ pub async fn get_insights_data(
 #[serde(rename_all = "camelCase")]
 pub struct LocalStatsCacheData {
     pub total_tokens: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
     pub total_sessions: u32,
     pub total_messages: u32,
     pub active_days: u32,
@@ -383,6 +1009,7 @@ pub struct LocalStatsCacheData {
     pub daily_activity: Vec<DailyActivityPoint>,
     pub tokens_by_model: Vec<ModelTokens>,
     pub activity_by_hour: Vec<HourActivity>,
+    pub unreadable_sections: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -400,12 +1027,27 @@ pub struct HourActivity {
 }
 
 #[tauri::command]
-pub async fn get_local_stats_cache(pricing_provider: String) -> Result<LocalStatsCacheData, String> {
-    let cache = load_stats_cache()?;
+pub async fn get_local_stats_cache(
+    app: AppHandle,
+    pricing_provider: String,
+) -> Result<LocalStatsCacheData, AppError> {
+    let cache = load_merged_stats_cache(&app)?;
 
     // Calculate totals from modelUsage (more complete than dailyModelTokens)
-    let total_tokens: u64 = cache.model_usage.values()
-        .map(|u| u.input_tokens + u.output_tokens + u.cache_read_input_tokens + u.cache_creation_input_tokens)
+    let total_tokens: u64 = cache.model_usage.values().map(model_usage_tokens).sum();
+
+    // Token type totals across all models (same breakdown as the Prometheus dashboard)
+    let input_tokens: u64 = cache.model_usage.values().map(|u| u.input_tokens).sum();
+    let output_tokens: u64 = cache.model_usage.values().map(|u| u.output_tokens).sum();
+    let cache_read_tokens: u64 = cache
+        .model_usage
+        .values()
+        .map(|u| u.cache_read_input_tokens)
+        .sum();
+    let cache_creation_tokens: u64 = cache
+        .model_usage
+        .values()
+        .map(|u| u.cache_creation_input_tokens)
         .sum();
 
     let total_messages: u32 = cache.daily_activity.iter().map(|d| d.message_count).sum();
@@ -419,7 +1061,8 @@ pub async fn get_local_stats_cache(pricing_provider: String) -> Result<LocalStat
     };
 
     // Use detailed cost calculation based on model and token type
-    let estimated_cost = calculate_detailed_cost(&cache.model_usage, &pricing_provider);
+    let pricing = resolve_profile(&app, &pricing_provider);
+    let estimated_cost = calculate_detailed_cost(&cache.model_usage, &pricing);
     let peak_hour = find_peak_hour(&cache.hour_counts);
 
     // Get all daily activity
@@ -454,6 +1097,10 @@ pub async fn get_local_stats_cache(pricing_provider: String) -> Result<LocalStat
 
     Ok(LocalStatsCacheData {
         total_tokens,
+        input_tokens,
+        output_tokens,
+        cache_read_tokens,
+        cache_creation_tokens,
         total_sessions,
         total_messages,
         active_days,
@@ -464,5 +1111,206 @@ pub async fn get_local_stats_cache(pricing_provider: String) -> Result<LocalStat
         daily_activity,
         tokens_by_model,
         activity_by_hour,
+        unreadable_sections: cache.unreadable_sections,
+    })
+}
+
+/// One calendar day of the activity heatmap
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapDay {
+    pub date: String,
+    /// 0 = Monday ... 6 = Sunday
+    pub weekday: u32,
+    pub messages: u32,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// 0 for no activity, else 1-4 by quartile among the year's active days
+    pub level: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityHeatmap {
+    pub year: i32,
+    /// "messages", "tokens" or "cost"; the value levels are based on
+    pub metric: String,
+    /// Every day of the year in order, including empty ones
+    pub days: Vec<HeatmapDay>,
+    pub active_days: u32,
+    pub total_messages: u32,
+    pub total_tokens: u64,
+    pub total_cost_usd: f64,
+}
+
+/// GitHub-style intensity: 0 for zero, else 1-4 by which quartile of the
+/// non-zero values it falls in
+fn intensity_levels(values: &[f64]) -> Vec<u8> {
+    let mut active: Vec<f64> = values.iter().copied().filter(|&v| v > 0.0).collect();
+    active.sort_by(|a, b| a.total_cmp(b));
+    let quartile = |p: f64| {
+        active
+            .get(((active.len().saturating_sub(1)) as f64 * p).round() as usize)
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let thresholds = [quartile(0.25), quartile(0.5), quartile(0.75)];
+
+    values
+        .iter()
+        .map(|&v| {
+            if v <= 0.0 {
+                0
+            } else {
+                1 + thresholds.iter().filter(|&&t| v > t).count() as u8
+            }
+        })
+        .collect()
+}
+
+/// Daily messages, tokens and cost for every day of `year` from the stats
+/// cache, with intensity levels for a contributions-style heatmap
+#[tauri::command]
+pub async fn get_activity_heatmap(
+    app: AppHandle,
+    year: i32,
+    metric: Option<String>,
+) -> Result<ActivityHeatmap, AppError> {
+    let metric = metric.unwrap_or_else(|| "messages".to_string());
+    if !matches!(metric.as_str(), "messages" | "tokens" | "cost") {
+        return Err(AppError::InvalidInput(format!(
+            "Unknown heatmap metric: {}",
+            metric
+        )));
+    }
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Err(AppError::InvalidInput(format!("Invalid year: {}", year)));
+    };
+
+    let cache = load_merged_stats_cache(&app)?;
+    let pricing = resolve_profile(&app, &frontend_pricing_provider(&app));
+    let rates = blended_rates(&cache.model_usage, &pricing);
+
+    let mut messages: HashMap<&str, u32> = HashMap::new();
+    for day in &cache.daily_activity {
+        *messages.entry(day.date.as_str()).or_default() += day.message_count;
+    }
+    let mut usage: HashMap<&str, (u64, f64)> = HashMap::new();
+    for day in cache.daily_model_tokens.iter().flatten() {
+        let entry = usage.entry(day.date.as_str()).or_default();
+        for (model, &tokens) in &day.tokens_by_model {
+            entry.0 += tokens;
+            entry.1 += blended_cost(model, tokens, &rates, &pricing);
+        }
+    }
+
+    let mut days: Vec<HeatmapDay> = first
+        .iter_days()
+        .take_while(|date| *date <= last)
+        .map(|date| {
+            let key = date.format("%Y-%m-%d").to_string();
+            let (tokens, cost_usd) = usage.get(key.as_str()).copied().unwrap_or_default();
+            HeatmapDay {
+                messages: messages.get(key.as_str()).copied().unwrap_or_default(),
+                date: key,
+                weekday: date.weekday().num_days_from_monday(),
+                tokens,
+                cost_usd,
+                level: 0,
+            }
+        })
+        .collect();
+
+    let values: Vec<f64> = days
+        .iter()
+        .map(|d| match metric.as_str() {
+            "tokens" => d.tokens as f64,
+            "cost" => d.cost_usd,
+            _ => d.messages as f64,
+        })
+        .collect();
+    for (day, level) in days.iter_mut().zip(intensity_levels(&values)) {
+        day.level = level;
+    }
+
+    Ok(ActivityHeatmap {
+        year,
+        metric,
+        active_days: days.iter().filter(|d| d.level > 0).count() as u32,
+        total_messages: days.iter().map(|d| d.messages).sum(),
+        total_tokens: days.iter().map(|d| d.tokens).sum(),
+        total_cost_usd: days.iter().map(|d| d.cost_usd).sum(),
+        days,
+    })
+}
+
+/// Messages by weekday and hour of day for a punchcard chart
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPunchcard {
+    /// 7 rows (Monday first) of 24 hourly values
+    pub cells: Vec<Vec<f64>>,
+    pub max: f64,
+    pub peak_weekday: Option<u32>,
+    pub peak_hour: Option<u32>,
+}
+
+/// Estimated messages per weekday and hour. The stats cache only records hourly
+/// counts across all days, so each weekday's messages are spread over the hours
+/// in that overall proportion.
+#[tauri::command]
+pub async fn get_activity_punchcard(app: AppHandle) -> Result<ActivityPunchcard, AppError> {
+    let cache = load_merged_stats_cache(&app)?;
+
+    let mut by_hour = [0.0; 24];
+    for (hour, &count) in cache.hour_counts.iter().flatten() {
+        if let Some(slot) = hour.parse::<usize>().ok().and_then(|h| by_hour.get_mut(h)) {
+            *slot += count as f64;
+        }
+    }
+    let hour_total: f64 = by_hour.iter().sum();
+
+    let mut by_weekday = [0.0; 7];
+    for day in &cache.daily_activity {
+        if let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") {
+            by_weekday[date.weekday().num_days_from_monday() as usize] += day.message_count as f64;
+        }
+    }
+
+    let cells: Vec<Vec<f64>> = by_weekday
+        .iter()
+        .map(|&messages| {
+            by_hour
+                .iter()
+                .map(|&count| {
+                    if hour_total > 0.0 {
+                        messages * count / hour_total
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let peak = cells
+        .iter()
+        .enumerate()
+        .flat_map(|(weekday, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(hour, &value)| (weekday as u32, hour as u32, value))
+        })
+        .filter(|&(_, _, value)| value > 0.0)
+        .max_by(|a, b| a.2.total_cmp(&b.2));
+
+    Ok(ActivityPunchcard {
+        max: peak.map(|(_, _, value)| value).unwrap_or(0.0),
+        peak_weekday: peak.map(|(weekday, _, _)| weekday),
+        peak_hour: peak.map(|(_, hour, _)| hour),
+        cells,
     })
 }
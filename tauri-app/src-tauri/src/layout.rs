@@ -0,0 +1,85 @@
+// tauri-app/src-tauri/src/layout.rs
+
+use crate::panels::load_custom_panels;
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+const DASHBOARD_LAYOUT_KEY: &str = "dashboardLayout";
+
+/// A single slot on the dashboard; list position determines ordering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutItem {
+    pub panel_id: String,
+    pub visible: bool,
+    /// True for user-defined panels (see panels.rs), false for built-in cards
+    pub custom: bool,
+    pub column_span: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardLayout {
+    pub items: Vec<LayoutItem>,
+}
+
+impl Default for DashboardLayout {
+    fn default() -> Self {
+        let builtin = |panel_id: &str, column_span: u8| LayoutItem {
+            panel_id: panel_id.to_string(),
+            visible: true,
+            custom: false,
+            column_span,
+        };
+        Self {
+            items: vec![
+                builtin("hero", 2),
+                builtin("kpis", 2),
+                builtin("cost-over-time", 2),
+                builtin("model-breakdown", 1),
+                builtin("token-types", 1),
+            ],
+        }
+    }
+}
+
+fn validate_layout(app: &AppHandle, layout: &DashboardLayout) -> Result<(), String> {
+    let custom_panels = load_custom_panels(app);
+    for item in &layout.items {
+        if item.column_span == 0 || item.column_span > 2 {
+            return Err(format!(
+                "Invalid column span {} for panel {}",
+                item.column_span, item.panel_id
+            ));
+        }
+        if item.custom && !custom_panels.iter().any(|p| p.id == item.panel_id) {
+            return Err(format!("Custom panel not found: {}", item.panel_id));
+        }
+    }
+    Ok(())
+}
+
+fn store_layout(app: &AppHandle, layout: &DashboardLayout) -> Result<(), String> {
+    save_setting(app, DASHBOARD_LAYOUT_KEY, layout)?;
+    app.emit("layout-changed", layout)
+        .map_err(|e| format!("Failed to emit layout change: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_dashboard_layout(app: AppHandle) -> Result<DashboardLayout, String> {
+    Ok(load_setting(&app, DASHBOARD_LAYOUT_KEY))
+}
+
+#[tauri::command]
+pub async fn set_dashboard_layout(app: AppHandle, layout: DashboardLayout) -> Result<(), String> {
+    validate_layout(&app, &layout)?;
+    store_layout(&app, &layout)
+}
+
+#[tauri::command]
+pub async fn reset_dashboard_layout(app: AppHandle) -> Result<DashboardLayout, String> {
+    let layout = DashboardLayout::default();
+    store_layout(&app, &layout)?;
+    Ok(layout)
+}
@@ -1,10 +1,60 @@
+mod active_sessions;
+mod alertmanager;
+mod alerts;
+mod api_metrics;
+mod auto_refresh;
+mod blocks;
+mod budgets;
+mod claude_storage;
 mod commands;
+mod compaction;
+mod comparison;
+mod data_sources;
+mod data_watcher;
+mod endpoints;
+mod error;
+mod events;
+mod filters;
+mod forecast;
+mod history_index;
+mod hooks;
 mod insights;
+mod layout;
+mod local_api;
+mod local_data;
 mod metrics;
+mod notifications;
+mod offline;
+mod panels;
+mod permissions;
+mod plan_limits;
+mod plugins;
+mod pricing;
+mod processes;
+mod project_actions;
 mod prometheus;
+mod prometheus_connection;
 mod prometheus_health;
+mod query_tuning;
+mod reconciliation;
+mod remote_pricing;
+mod runaway;
+mod session_events;
+mod session_export;
 mod sessions;
+mod settings;
+mod stats_builder;
+mod team;
+#[cfg(test)]
+mod test_support;
+mod timezone;
+mod transcript_archive;
+mod transcript_search;
+mod transcripts;
 mod tray;
+mod uptime;
+mod verification;
+mod webhooks;
 
 use tauri::{
     menu::{Menu, MenuItem},
@@ -20,9 +70,12 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .manage(TrayState::new())
+        .manage(commands::DashboardRequests::new())
         .setup(|app| {
             let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
             let show = MenuItem::with_id(app, "show", "Open Dashboard", true, None::<&str>)?;
@@ -67,16 +120,179 @@ pub fn run() {
                 *guard = Some(tray);
             }
 
+            // Where stats, prices, archives and indexes are written
+            local_data::load_app_data_dir(app.handle());
+
+            // TLS and other connection options used by every Prometheus client
+            prometheus_connection::load_prometheus_connection(app.handle());
+
+            // Timezone used to bucket usage into days
+            timezone::load_timezone(app.handle());
+
+            // Fetched model prices, refreshed on schedule when enabled
+            remote_pricing::load_remote_pricing(app.handle());
+            remote_pricing::start_pricing_refresh(app.handle().clone());
+
+            // Enforce the retention window on startup
+            let retention = local_data::load_retention_settings(app.handle());
+            tauri::async_runtime::spawn_blocking(move || local_data::apply_retention(&retention));
+
+            // Record CPU/memory of running Claude processes in the background
+            processes::start_sampler();
+
+            // Periodically compare local data with Prometheus and flag drift
+            reconciliation::start_reconciliation(app.handle().clone());
+
+            // Publish session lifecycle, cost and runaway events (webhooks, frontend)
+            session_events::start_session_events(app.handle().clone());
+            runaway::start_runaway_watcher(app.handle().clone());
+            events::start_daily_rollover(app.handle().clone());
+
+            // Tell views to reload when Claude Code writes new history or transcripts
+            data_watcher::start_data_watcher(app.handle().clone());
+
+            // Keep the plugin list current as plugins are added, edited or removed
+            plugins::start_plugin_watcher();
+
+            // Track spend against the daily/weekly/monthly budgets
+            budgets::start_budget_watcher(app.handle().clone());
+
+            // Push dashboard, session and health updates if auto refresh was left on
+            auto_refresh::resume_auto_refresh(app.handle().clone());
+
+            // Serve the /ws event stream if the local API is enabled
+            local_api::start_local_api(app.handle().clone());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            active_sessions::get_active_sessions,
+            alertmanager::get_active_alerts,
+            alertmanager::get_alertmanager_settings,
+            alertmanager::set_alertmanager_settings,
+            alerts::get_alert_history,
+            alerts::get_quiet_hours,
+            alerts::set_quiet_hours,
+            alerts::test_webhook,
+            api_metrics::get_api_error_metrics,
+            api_metrics::get_api_latency_metrics,
+            blocks::get_billing_blocks,
+            budgets::get_budget_settings,
+            budgets::set_budget_settings,
+            budgets::get_budget_status,
+            budgets::get_budget_projection,
+            auto_refresh::get_auto_refresh_settings,
+            auto_refresh::start_auto_refresh,
+            auto_refresh::stop_auto_refresh,
+            claude_storage::get_claude_storage_usage,
+            claude_storage::trim_claude_transcripts,
             commands::get_dashboard_metrics,
+            commands::get_host_breakdown,
+            commands::get_terminal_breakdown,
+            commands::get_version_breakdown,
             commands::test_connection,
             commands::discover_metrics,
+            commands::discover_metric_metadata,
             commands::get_prometheus_health,
+            commands::get_scrape_targets,
+            commands::get_prometheus_rules,
+            commands::get_label_values,
+            prometheus_connection::get_prometheus_connection,
+            prometheus_connection::set_prometheus_connection,
+            compaction::get_session_compactions,
+            compaction::get_compaction_summary,
+            comparison::get_dashboard_comparison,
+            comparison::get_project_comparison,
+            data_sources::get_data_sources,
+            data_sources::save_data_source,
+            data_sources::delete_data_source,
+            history_index::get_history_daily_summaries,
+            history_index::get_history_diagnostics,
+            history_index::repair_history_file,
+            forecast::get_cost_forecast,
+            forecast::get_project_forecasts,
+            hooks::get_command_hooks,
+            hooks::save_command_hook,
+            hooks::delete_command_hook,
+            hooks::test_command_hook,
             insights::get_insights_data,
+            insights::export_insights_markdown,
             insights::get_local_stats_cache,
+            insights::get_activity_heatmap,
+            insights::get_activity_punchcard,
+            layout::get_dashboard_layout,
+            layout::set_dashboard_layout,
+            layout::reset_dashboard_layout,
+            local_api::get_local_api_settings,
+            local_api::set_local_api_settings,
+            local_api::regenerate_local_api_token,
+            local_api::get_local_api_status,
+            local_data::get_retention_settings,
+            local_data::set_retention_settings,
+            local_data::get_local_data_usage,
+            local_data::purge_local_data,
+            panels::get_custom_panels,
+            panels::save_custom_panel,
+            panels::delete_custom_panel,
+            panels::run_panel,
+            notifications::get_notification_settings,
+            notifications::set_notification_settings,
+            permissions::get_permission_analytics,
+            permissions::get_edit_decision_metrics,
+            plan_limits::get_plan_limits,
+            plan_limits::set_plan_limits,
+            plan_limits::get_weekly_limit_status,
+            plugins::list_plugins,
+            plugins::reload_plugin_list,
+            plugins::describe_plugin,
+            plugins::fetch_plugin_metrics,
+            pricing::get_pricing_profiles,
+            pricing::save_pricing_profile,
+            pricing::delete_pricing_profile,
             sessions::get_sessions_data,
+            sessions::get_session_detail,
+            transcript_archive::archive_transcripts,
+            transcript_archive::restore_transcript,
+            transcript_search::search_transcripts,
+            transcripts::get_transcript_session_usage,
+            transcripts::get_transcript_usage,
+            uptime::get_uptime_timeline,
+            verification::verify_usage_totals,
+            webhooks::get_webhooks,
+            webhooks::save_webhook,
+            webhooks::delete_webhook,
+            project_actions::get_editor_settings,
+            project_actions::set_editor_settings,
+            project_actions::open_project_folder,
+            project_actions::open_project_in_editor,
+            project_actions::get_terminal_settings,
+            project_actions::set_terminal_settings,
+            project_actions::resume_session,
+            processes::get_claude_processes,
+            processes::get_process_resource_history,
+            query_tuning::get_query_tuning,
+            query_tuning::set_query_tuning,
+            reconciliation::get_reconciliation_settings,
+            reconciliation::set_reconciliation_settings,
+            reconciliation::get_reconciliation_report,
+            reconciliation::run_reconciliation,
+            remote_pricing::get_remote_pricing_status,
+            remote_pricing::set_remote_pricing_settings,
+            remote_pricing::refresh_pricing,
+            runaway::get_runaway_settings,
+            runaway::set_runaway_settings,
+            runaway::get_runaway_warnings,
+            session_export::copy_sessions_table,
+            session_events::get_session_event_settings,
+            session_events::set_session_event_settings,
+            settings::export_backend_settings,
+            settings::import_backend_settings,
+            stats_builder::rebuild_stats,
+            team::get_team_metrics,
+            timezone::get_timezone,
+            timezone::set_timezone,
+            timezone::get_week_start,
+            timezone::set_week_start,
             tray::update_tray_stats,
         ])
         .run(tauri::generate_context!())
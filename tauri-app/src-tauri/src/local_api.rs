@@ -0,0 +1,299 @@
+// tauri-app/src-tauri/src/local_api.rs
+//
+// Optional HTTP server on 127.0.0.1 for external consumers. Every event that
+// goes through events::publish is streamed as JSON over the /ws endpoint:
+//   { "event": "session-started", "timestamp": "...", "data": { ... } }
+// Clients authenticate with `?token=<token>` or `Authorization: Bearer <token>`.
+
+use crate::settings::{load_setting, save_setting};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::{broadcast, watch};
+
+const LOCAL_API_KEY: &str = "localApi";
+const EVENT_BUFFER: usize = 256;
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Generated on first use; empty means not generated yet
+    pub token: String,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9464,
+            token: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiStatus {
+    pub running: bool,
+    pub address: Option<String>,
+    pub error: Option<String>,
+}
+
+struct RunningServer {
+    address: String,
+    events: broadcast::Sender<String>,
+    shutdown: watch::Sender<bool>,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    token: String,
+    events: broadcast::Sender<String>,
+    shutdown: watch::Receiver<bool>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Deserialize)]
+struct WsQuery {
+    token: Option<String>,
+}
+
+fn generate_token() -> String {
+    rand::random::<[u8; 24]>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Compare without short-circuiting so response timing doesn't leak the token
+fn token_matches(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn load_settings(app: &AppHandle) -> Result<LocalApiSettings, String> {
+    let mut settings: LocalApiSettings = load_setting(app, LOCAL_API_KEY);
+    if settings.token.is_empty() {
+        settings.token = generate_token();
+        save_setting(app, LOCAL_API_KEY, &settings)?;
+    }
+    Ok(settings)
+}
+
+/// Forward a published event to connected WebSocket clients (no-op when the server is off)
+pub fn broadcast(event: &str, payload: &Value) {
+    let Ok(guard) = SERVER.lock() else {
+        return;
+    };
+    if let Some(server) = guard.as_ref() {
+        let message = json!({
+            "event": event,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "data": payload,
+        });
+        // Err only means nobody is connected
+        let _ = server.events.send(message.to_string());
+    }
+}
+
+async fn health() -> impl IntoResponse {
+    axum::Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    Query(query): Query<WsQuery>,
+    State(state): State<ServerState>,
+) -> Response {
+    let bearer = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let provided = query.token.as_deref().or(bearer).unwrap_or_default();
+    if !token_matches(&state.token, provided) {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    }
+
+    let events = state.events.subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, events, state.shutdown))
+}
+
+async fn stream_events(
+    mut socket: WebSocket,
+    mut events: broadcast::Receiver<String>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            // Server stopped or restarted
+            _ = shutdown.changed() => break,
+            event = events.recv() => match event {
+                Ok(text) => {
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow client misses events rather than stalling everyone else
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+/// Signal the server to shut down and wait until it has released its port
+async fn stop_server() {
+    let Some(mut server) = SERVER.lock().ok().and_then(|mut guard| guard.take()) else {
+        return;
+    };
+
+    // Stops accepting connections and closes every open socket
+    let _ = server.shutdown.send(true);
+    if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut server.handle)
+        .await
+        .is_err()
+    {
+        eprintln!("Local API server did not shut down in time, aborting");
+        server.handle.abort();
+        let _ = server.handle.await;
+    }
+}
+
+async fn start_server(settings: &LocalApiSettings) -> Result<String, String> {
+    stop_server().await;
+
+    let address = format!("127.0.0.1:{}", settings.port);
+    let listener = tokio::net::TcpListener::bind(&address)
+        .await
+        .map_err(|e| format!("Failed to bind {}: {}", address, e))?;
+
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let (shutdown, mut shutdown_signal) = watch::channel(false);
+    let state = ServerState {
+        token: settings.token.clone(),
+        events: events.clone(),
+        shutdown: shutdown_signal.clone(),
+    };
+    let router = Router::new()
+        .route("/health", get(health))
+        .route("/ws", get(ws_handler))
+        .with_state(state);
+
+    let handle = tauri::async_runtime::spawn(async move {
+        let serve = axum::serve(listener, router).with_graceful_shutdown(async move {
+            let _ = shutdown_signal.changed().await;
+        });
+        if let Err(e) = serve.await {
+            eprintln!("Local API server stopped: {}", e);
+        }
+    });
+
+    let mut guard = SERVER.lock().map_err(|e| e.to_string())?;
+    *guard = Some(RunningServer {
+        address: address.clone(),
+        events,
+        shutdown,
+        handle,
+    });
+    Ok(address)
+}
+
+/// Start, restart or stop the server to match the saved settings
+async fn apply_settings(app: &AppHandle) -> Result<(), String> {
+    let settings = load_settings(app)?;
+    let result = if settings.enabled {
+        start_server(&settings).await.map(|_| ())
+    } else {
+        stop_server().await;
+        Ok(())
+    };
+
+    if let Ok(mut last_error) = LAST_ERROR.lock() {
+        *last_error = result.as_ref().err().cloned();
+    }
+    result
+}
+
+/// Start the server at launch if it's enabled
+pub fn start_local_api(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = apply_settings(&app).await {
+            eprintln!("{}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_local_api_settings(app: AppHandle) -> Result<LocalApiSettings, String> {
+    load_settings(&app)
+}
+
+#[tauri::command]
+pub async fn set_local_api_settings(
+    app: AppHandle,
+    settings: LocalApiSettings,
+) -> Result<LocalApiStatus, String> {
+    if settings.port < 1024 {
+        return Err("Port must be 1024 or higher".to_string());
+    }
+    // The token is managed by the backend; keep the current one
+    let current = load_settings(&app)?;
+    let settings = LocalApiSettings {
+        token: current.token,
+        ..settings
+    };
+    save_setting(&app, LOCAL_API_KEY, &settings)?;
+    let _ = apply_settings(&app).await;
+    get_local_api_status().await
+}
+
+/// Replace the token, disconnecting every client authenticated with the old one
+#[tauri::command]
+pub async fn regenerate_local_api_token(app: AppHandle) -> Result<LocalApiSettings, String> {
+    let settings = LocalApiSettings {
+        token: generate_token(),
+        ..load_settings(&app)?
+    };
+    save_setting(&app, LOCAL_API_KEY, &settings)?;
+    apply_settings(&app).await?;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn get_local_api_status() -> Result<LocalApiStatus, String> {
+    let address = SERVER
+        .lock()
+        .map_err(|e| e.to_string())?
+        .as_ref()
+        .map(|s| s.address.clone());
+    let error = LAST_ERROR.lock().map_err(|e| e.to_string())?.clone();
+    Ok(LocalApiStatus {
+        running: address.is_some(),
+        address,
+        error,
+    })
+}
@@ -0,0 +1,255 @@
+// tauri-app/src-tauri/src/local_data.rs
+
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+const RETENTION_KEY: &str = "retention";
+
+/// Categories of data the app writes to its own data directory, each in the
+/// subdirectory named by `as_str`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataCategory {
+    /// Stats cache rebuilt from transcripts (stats_builder)
+    Stats,
+    /// Downloaded model prices (remote_pricing)
+    Pricing,
+    /// Compressed transcripts moved out of ~/.claude (transcript_archive)
+    Archive,
+    /// Transcript search index (transcript_search)
+    Index,
+}
+
+impl DataCategory {
+    pub const ALL: [DataCategory; 4] = [
+        DataCategory::Stats,
+        DataCategory::Pricing,
+        DataCategory::Archive,
+        DataCategory::Index,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataCategory::Stats => "stats",
+            DataCategory::Pricing => "pricing",
+            DataCategory::Archive => "archive",
+            DataCategory::Index => "index",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == name)
+    }
+}
+
+/// How many days of each category to keep (None = keep forever). The stats
+/// cache, prices and index are rebuilt on demand and rewritten in place, so
+/// they are only removed by an explicit purge.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionSettings {
+    pub archive_days: Option<u32>,
+}
+
+impl RetentionSettings {
+    fn days_for(&self, category: DataCategory) -> Option<u32> {
+        match category {
+            DataCategory::Archive => self.archive_days,
+            DataCategory::Stats | DataCategory::Pricing | DataCategory::Index => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryUsage {
+    pub category: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: u32,
+    pub oldest_modified: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeResult {
+    pub files_removed: u32,
+    pub bytes_freed: u64,
+}
+
+/// App data directory as resolved by Tauri, set once at startup
+static APP_DATA_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Resolve the app-owned data directory for modules that run without an AppHandle
+pub fn load_app_data_dir(app: &AppHandle) {
+    match app.path().app_data_dir() {
+        Ok(dir) => {
+            if let Ok(mut guard) = APP_DATA_DIR.lock() {
+                *guard = Some(dir);
+            }
+        }
+        Err(e) => eprintln!("Failed to resolve the app data directory: {}", e),
+    }
+}
+
+/// Root of the app-owned data directory
+pub fn app_data_dir() -> Option<PathBuf> {
+    APP_DATA_DIR.lock().ok().and_then(|guard| guard.clone())
+}
+
+pub fn category_dir(category: DataCategory) -> Option<PathBuf> {
+    app_data_dir().map(|d| d.join(category.as_str()))
+}
+
+/// Recursively collect (path, size, modified) for every file under a directory
+pub(crate) fn collect_files(dir: &Path, files: &mut Vec<(PathBuf, u64, SystemTime)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_files(&path, files);
+        } else {
+            let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+            files.push((path, metadata.len(), modified));
+        }
+    }
+}
+
+/// Remove empty subdirectories left behind after a purge (the root is kept)
+pub(crate) fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            let _ = fs::remove_dir(&path); // Fails (harmlessly) if not empty
+        }
+    }
+}
+
+fn category_usage(category: DataCategory) -> Option<CategoryUsage> {
+    let dir = category_dir(category)?;
+    let mut files = Vec::new();
+    collect_files(&dir, &mut files);
+
+    let oldest_modified = files
+        .iter()
+        .filter_map(|(_, _, modified)| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .min();
+
+    Some(CategoryUsage {
+        category: category.as_str().to_string(),
+        path: dir.to_string_lossy().to_string(),
+        total_bytes: files.iter().map(|(_, size, _)| size).sum(),
+        file_count: files.len() as u32,
+        oldest_modified,
+    })
+}
+
+/// Delete files in a category older than `older_than_days` (None = everything)
+fn purge_category(category: DataCategory, older_than_days: Option<u32>) -> PurgeResult {
+    let mut result = PurgeResult::default();
+    let Some(dir) = category_dir(category) else {
+        return result;
+    };
+
+    let cutoff = older_than_days
+        .map(|days| SystemTime::now() - Duration::from_secs(days as u64 * 24 * 3600));
+
+    let mut files = Vec::new();
+    collect_files(&dir, &mut files);
+
+    for (path, size, modified) in files {
+        if cutoff.is_some_and(|cutoff| modified >= cutoff) {
+            continue;
+        }
+        if fs::remove_file(&path).is_ok() {
+            result.files_removed += 1;
+            result.bytes_freed += size;
+        }
+    }
+
+    remove_empty_dirs(&dir);
+    result
+}
+
+/// Apply the configured retention window to every category
+pub fn apply_retention(settings: &RetentionSettings) -> PurgeResult {
+    let mut total = PurgeResult::default();
+    for category in DataCategory::ALL {
+        if let Some(days) = settings.days_for(category) {
+            let result = purge_category(category, Some(days));
+            total.files_removed += result.files_removed;
+            total.bytes_freed += result.bytes_freed;
+        }
+    }
+    total
+}
+
+pub fn load_retention_settings(app: &AppHandle) -> RetentionSettings {
+    load_setting(app, RETENTION_KEY)
+}
+
+#[tauri::command]
+pub async fn get_retention_settings(app: AppHandle) -> Result<RetentionSettings, String> {
+    Ok(load_retention_settings(&app))
+}
+
+#[tauri::command]
+pub async fn set_retention_settings(
+    app: AppHandle,
+    settings: RetentionSettings,
+) -> Result<PurgeResult, String> {
+    save_setting(&app, RETENTION_KEY, &settings)?;
+    // Apply immediately so shortening the window frees space right away
+    tauri::async_runtime::spawn_blocking(move || apply_retention(&settings))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_local_data_usage() -> Result<Vec<CategoryUsage>, String> {
+    Ok(DataCategory::ALL
+        .into_iter()
+        .filter_map(category_usage)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn purge_local_data(
+    category: Option<String>,
+    older_than_days: Option<u32>,
+) -> Result<PurgeResult, String> {
+    let categories: Vec<DataCategory> = match category.as_deref() {
+        Some(name) => {
+            vec![DataCategory::parse(name).ok_or(format!("Unknown data category: {}", name))?]
+        }
+        None => DataCategory::ALL.to_vec(),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut total = PurgeResult::default();
+        for category in categories {
+            let result = purge_category(category, older_than_days);
+            total.files_removed += result.files_removed;
+            total.bytes_freed += result.bytes_freed;
+        }
+        total
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
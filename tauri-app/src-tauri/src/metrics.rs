@@ -1,4 +1,6 @@
+use crate::pricing::ModelRates;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,12 +14,61 @@ pub struct DashboardMetrics {
     pub commit_count: u32,
     pub pull_request_count: u32,
     pub tokens_by_model: Vec<ModelTokens>,
+    pub cost_by_model: Vec<ModelCost>,
+    pub usage_by_terminal: Vec<UsageBreakdown>,
     pub tokens_over_time: Vec<TimeSeriesPoint>,
+    pub cost_over_time: Vec<TimeSeriesPoint>,
+    pub tokens_by_type_over_time: TokenTypeSeries,
+    pub lines_added_over_time: Vec<TimeSeriesPoint>,
+    pub lines_removed_over_time: Vec<TimeSeriesPoint>,
+    pub sessions_over_time: Vec<TimeSeriesPoint>,
+    pub tokens_by_version_over_time: Vec<LabeledSeries>,
     // Token type breakdown
     pub input_tokens: u64,
     pub output_tokens: u64,
     pub cache_read_tokens: u64,
     pub cache_creation_tokens: u64,
+    /// Estimated USD per token type, priced per model
+    #[serde(default)]
+    pub cost_by_token_type: TokenTypeCost,
+    /// Output tokens per second of active time
+    #[serde(default)]
+    pub output_tokens_per_second: f64,
+    /// Output tokens per active second per bucket, one series per model
+    #[serde(default)]
+    pub output_tokens_per_second_by_model: Vec<LabeledSeries>,
+    /// Spend rate over the last 15 minutes of the range
+    #[serde(default)]
+    pub burn_rate_usd_per_hour: f64,
+    /// USD per hour per bucket
+    #[serde(default)]
+    pub burn_rate_over_time: Vec<TimeSeriesPoint>,
+    /// Connection result for each Prometheus endpoint that was queried
+    #[serde(default)]
+    pub endpoints: Vec<EndpointStatus>,
+    /// Request generation from `get_dashboard_metrics`; 0 for internal callers
+    #[serde(default)]
+    pub generation: u64,
+    #[serde(default)]
+    pub source: MetricsSource,
+}
+
+/// Where dashboard metrics came from. `Local` means Prometheus was unreachable and
+/// the values were estimated from the stats cache and history.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsSource {
+    #[default]
+    Prometheus,
+    Local,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointStatus {
+    pub url: String,
+    pub connected: bool,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,8 +77,355 @@ pub struct ModelTokens {
     pub tokens: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCost {
+    pub model: String,
+    pub cost_usd: f64,
+    /// Share of the total cost across models, 0-100
+    #[serde(default)]
+    pub percentage: f64,
+}
+
+/// Sort by cost, most expensive first, and fill in each model's share of the total
+pub fn rank_model_costs(models: &mut [ModelCost]) {
+    let total: f64 = models.iter().map(|m| m.cost_usd).sum();
+    for model in models.iter_mut() {
+        model.percentage = if total > 0.0 {
+            model.cost_usd / total * 100.0
+        } else {
+            0.0
+        };
+    }
+    models.sort_by(|a, b| {
+        b.cost_usd
+            .partial_cmp(&a.cost_usd)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Token, cost and session totals grouped by a single Prometheus label
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageBreakdown {
+    pub label: String,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    #[serde(default)]
+    pub sessions: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeSeriesPoint {
     pub timestamp: i64,
     pub value: f64,
+    /// The bucket ends too recently for all of its data to have been scraped
+    #[serde(default)]
+    pub partial: bool,
+}
+
+/// A time series for one value of a grouping label (e.g. one app version)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabeledSeries {
+    pub label: String,
+    pub points: Vec<TimeSeriesPoint>,
+}
+
+/// Value of the `type` label on the token usage metric
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenType {
+    Input,
+    Output,
+    CacheRead,
+    CacheCreation,
+}
+
+impl TokenType {
+    /// Accepts both the snake_case and camelCase spellings exporters have used
+    pub fn parse(label: &str) -> Option<Self> {
+        match label {
+            "input" => Some(Self::Input),
+            "output" => Some(Self::Output),
+            "cache_read" | "cacheRead" => Some(Self::CacheRead),
+            "cache_creation" | "cacheCreation" => Some(Self::CacheCreation),
+            _ => None,
+        }
+    }
+}
+
+/// Estimated cost of each token type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTypeCost {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_creation: f64,
+}
+
+impl TokenTypeCost {
+    /// Add `tokens` of one type at a model's rates
+    pub fn add(&mut self, token_type: TokenType, tokens: f64, rates: &ModelRates) {
+        let millions = tokens / 1_000_000.0;
+        match token_type {
+            TokenType::Input => self.input += millions * rates.input,
+            TokenType::Output => self.output += millions * rates.output,
+            TokenType::CacheRead => self.cache_read += millions * rates.cache_read,
+            TokenType::CacheCreation => self.cache_creation += millions * rates.cache_creation,
+        }
+    }
+
+    pub fn merge(&mut self, other: &TokenTypeCost) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_creation += other.cache_creation;
+    }
+}
+
+/// Per-type token rate series, stacked by the frontend to show composition
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTypeSeries {
+    pub input: Vec<TimeSeriesPoint>,
+    pub output: Vec<TimeSeriesPoint>,
+    pub cache_read: Vec<TimeSeriesPoint>,
+    pub cache_creation: Vec<TimeSeriesPoint>,
+}
+
+/// Usage for one Claude Code version and the hosts that ran it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionUsage {
+    pub version: String,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    pub sessions: u64,
+    pub hosts: Vec<String>,
+}
+
+/// A host whose newest version in the range is behind the newest seen anywhere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutdatedHost {
+    pub host: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionBreakdown {
+    /// Newest version first
+    pub versions: Vec<VersionUsage>,
+    pub latest_version: Option<String>,
+    pub outdated_hosts: Vec<OutdatedHost>,
+}
+
+/// Order version strings such as "1.0.9" and "1.0.10" numerically, part by part
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parts = |v: &str| -> Vec<u64> {
+        v.split(['.', '-'])
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    };
+    parts(a).cmp(&parts(b))
+}
+
+/// Downsample to at most `threshold` points with largest-triangle-three-buckets,
+/// which keeps the peaks and dips a plain stride would drop
+pub fn lttb<T: Clone>(points: &[T], threshold: usize, xy: impl Fn(&T) -> (f64, f64)) -> Vec<T> {
+    lttb_indices(points, threshold, xy)
+        .into_iter()
+        .map(|index| points[index].clone())
+        .collect()
+}
+
+/// Indices of the points `lttb` keeps, in order
+pub fn lttb_indices<T>(
+    points: &[T],
+    threshold: usize,
+    xy: impl Fn(&T) -> (f64, f64),
+) -> Vec<usize> {
+    if threshold < 3 || points.len() <= threshold {
+        return (0..points.len()).collect();
+    }
+
+    // First and last points are always kept; the rest is split into equal buckets
+    let bucket_size = (points.len() - 2) as f64 / (threshold - 2) as f64;
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(0);
+    let mut previous = 0;
+
+    for bucket in 0..threshold - 2 {
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = ((bucket + 1) as f64 * bucket_size) as usize + 1;
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(points.len());
+
+        // The next bucket's average is the triangle's third vertex
+        let next = &points[end..next_end];
+        let (sum_x, sum_y) = next
+            .iter()
+            .map(&xy)
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (avg_x, avg_y) = (sum_x / next.len() as f64, sum_y / next.len() as f64);
+
+        let (ax, ay) = xy(&points[previous]);
+        let mut best = start;
+        let mut best_area = -1.0;
+        for (index, point) in points.iter().enumerate().take(end).skip(start) {
+            let (x, y) = xy(point);
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > best_area {
+                best_area = area;
+                best = index;
+            }
+        }
+        sampled.push(best);
+        previous = best;
+    }
+
+    sampled.push(points.len() - 1);
+    sampled
+}
+
+pub fn downsample(points: &mut Vec<TimeSeriesPoint>, max_points: usize) {
+    *points = lttb(points, max_points, |p| (p.timestamp as f64, p.value));
+}
+
+/// Downsample series drawn on one time axis (stacked or side by side) so they
+/// all keep the same timestamps, picked by LTTB on their sum
+pub fn downsample_aligned(series: &mut [&mut Vec<TimeSeriesPoint>], max_points: usize) {
+    let mut totals: BTreeMap<i64, f64> = BTreeMap::new();
+    for points in series.iter() {
+        for point in points.iter() {
+            *totals.entry(point.timestamp).or_default() += point.value;
+        }
+    }
+    if totals.len() <= max_points {
+        return;
+    }
+
+    let totals: Vec<(i64, f64)> = totals.into_iter().collect();
+    let kept: HashSet<i64> = lttb_indices(&totals, max_points, |(t, v)| (*t as f64, *v))
+        .into_iter()
+        .map(|index| totals[index].0)
+        .collect();
+    for points in series.iter_mut() {
+        points.retain(|p| kept.contains(&p.timestamp));
+    }
+}
+
+/// Output tokens per active second, 0 without active time
+pub fn output_throughput(output_tokens: u64, active_time_seconds: f64) -> f64 {
+    if active_time_seconds > 0.0 {
+        output_tokens as f64 / active_time_seconds
+    } else {
+        0.0
+    }
+}
+
+impl DashboardMetrics {
+    /// Cap every time series at `max_points` to keep the IPC payload small
+    pub fn downsample(&mut self, max_points: usize) {
+        let series = [
+            &mut self.tokens_over_time,
+            &mut self.cost_over_time,
+            &mut self.sessions_over_time,
+            &mut self.burn_rate_over_time,
+        ];
+        for points in series {
+            downsample(points, max_points);
+        }
+
+        // Series charted together keep matching timestamps so they still stack
+        let types = &mut self.tokens_by_type_over_time;
+        downsample_aligned(
+            &mut [
+                &mut types.input,
+                &mut types.output,
+                &mut types.cache_read,
+                &mut types.cache_creation,
+            ],
+            max_points,
+        );
+        downsample_aligned(
+            &mut [
+                &mut self.lines_added_over_time,
+                &mut self.lines_removed_over_time,
+            ],
+            max_points,
+        );
+        for group in [
+            &mut self.tokens_by_version_over_time,
+            &mut self.output_tokens_per_second_by_model,
+        ] {
+            let mut points: Vec<&mut Vec<TimeSeriesPoint>> = group
+                .iter_mut()
+                .map(|labeled| &mut labeled.points)
+                .collect();
+            downsample_aligned(&mut points, max_points);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(timestamp: i64, value: f64) -> TimeSeriesPoint {
+        TimeSeriesPoint {
+            timestamp,
+            value,
+            partial: false,
+        }
+    }
+
+    fn timestamps(points: &[TimeSeriesPoint]) -> Vec<i64> {
+        points.iter().map(|p| p.timestamp).collect()
+    }
+
+    #[test]
+    fn lttb_keeps_endpoints_and_spikes() {
+        let mut values = vec![1.0; 100];
+        values[37] = 50.0;
+        let points: Vec<TimeSeriesPoint> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| point(i as i64, *v))
+            .collect();
+
+        let sampled = lttb(&points, 10, |p| (p.timestamp as f64, p.value));
+
+        assert_eq!(sampled.len(), 10);
+        assert_eq!(sampled.first().unwrap().timestamp, 0);
+        assert_eq!(sampled.last().unwrap().timestamp, 99);
+        assert!(sampled.iter().any(|p| p.value == 50.0));
+        assert!(timestamps(&sampled).windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn lttb_leaves_short_series_alone() {
+        let points: Vec<TimeSeriesPoint> = (0..5).map(|i| point(i, i as f64)).collect();
+        assert_eq!(
+            lttb_indices(&points, 10, |p| (p.timestamp as f64, p.value)),
+            [0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            lttb_indices(&points, 2, |p| (p.timestamp as f64, p.value)).len(),
+            5
+        );
+    }
+
+    #[test]
+    fn aligned_series_keep_the_same_timestamps() {
+        let mut input: Vec<TimeSeriesPoint> = (0..200).map(|i| point(i, (i % 7) as f64)).collect();
+        let mut output: Vec<TimeSeriesPoint> =
+            (0..200).map(|i| point(i, (i % 13) as f64 * 3.0)).collect();
+
+        downsample_aligned(&mut [&mut input, &mut output], 20);
+
+        assert_eq!(input.len(), 20);
+        assert_eq!(timestamps(&input), timestamps(&output));
+    }
 }
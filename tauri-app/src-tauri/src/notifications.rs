@@ -0,0 +1,74 @@
+// tauri-app/src-tauri/src/notifications.rs
+
+use crate::alerts::alert_text;
+use crate::error::AppError;
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+const NOTIFICATIONS_KEY: &str = "notifications";
+
+/// Which published events raise an OS notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    pub budget_exceeded: bool,
+    pub prometheus_down: bool,
+    pub prometheus_up: bool,
+    pub session_cost_threshold: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            budget_exceeded: true,
+            prometheus_down: true,
+            prometheus_up: true,
+            session_cost_threshold: true,
+        }
+    }
+}
+
+impl NotificationSettings {
+    fn allows(&self, event: &str) -> bool {
+        self.enabled
+            && match event {
+                "budget-exceeded" => self.budget_exceeded,
+                "prometheus-down" => self.prometheus_down,
+                "prometheus-up" => self.prometheus_up,
+                "session-cost-threshold" => self.session_cost_threshold,
+                _ => false,
+            }
+    }
+}
+
+/// Show an OS notification for `event` if its type is enabled
+pub fn dispatch(app: &AppHandle, event: &str, payload: &Value) {
+    let settings: NotificationSettings = load_setting(app, NOTIFICATIONS_KEY);
+    if !settings.allows(event) {
+        return;
+    }
+    let Some((title, body)) = alert_text(event, payload) else {
+        return;
+    };
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show notification for {}: {}", event, e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_notification_settings(app: AppHandle) -> Result<NotificationSettings, AppError> {
+    Ok(load_setting(&app, NOTIFICATIONS_KEY))
+}
+
+#[tauri::command]
+pub async fn set_notification_settings(
+    app: AppHandle,
+    settings: NotificationSettings,
+) -> Result<(), AppError> {
+    Ok(save_setting(&app, NOTIFICATIONS_KEY, &settings)?)
+}
@@ -0,0 +1,223 @@
+// tauri-app/src-tauri/src/offline.rs
+
+use crate::commands::resolve_time_range;
+use crate::data_sources::enabled_sources;
+use crate::error::AppError;
+use crate::history_index::with_history_index;
+use crate::insights::{
+    blended_cost, blended_rates, load_merged_stats_cache, model_usage_tokens, StatsCache,
+};
+use crate::metrics::{
+    rank_model_costs, DashboardMetrics, MetricsSource, ModelCost, ModelTokens, TimeSeriesPoint,
+    TokenType, TokenTypeCost, TokenTypeSeries,
+};
+use crate::pricing::{resolve_profile, PricingProfile};
+use crate::settings::frontend_pricing_provider;
+use crate::timezone;
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
+
+fn point(date: NaiveDate, value: f64) -> TimeSeriesPoint {
+    TimeSeriesPoint {
+        timestamp: timezone::start_of_day(date),
+        value,
+        partial: false,
+    }
+}
+
+/// Per-day token and cost totals by model from the stats cache
+struct DailyUsage {
+    tokens_by_model: HashMap<String, u64>,
+    cost_by_model: HashMap<String, f64>,
+    tokens_by_day: BTreeMap<NaiveDate, u64>,
+    cost_by_day: BTreeMap<NaiveDate, f64>,
+}
+
+fn daily_usage(
+    cache: &StatsCache,
+    pricing: &PricingProfile,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> DailyUsage {
+    let rates = blended_rates(&cache.model_usage, pricing);
+    let mut usage = DailyUsage {
+        tokens_by_model: HashMap::new(),
+        cost_by_model: HashMap::new(),
+        tokens_by_day: BTreeMap::new(),
+        cost_by_day: BTreeMap::new(),
+    };
+
+    for day in cache.daily_model_tokens.iter().flatten() {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        if date < start || date > end {
+            continue;
+        }
+        for (model, &tokens) in &day.tokens_by_model {
+            let cost = blended_cost(model, tokens, &rates, pricing);
+            *usage.tokens_by_model.entry(model.clone()).or_default() += tokens;
+            *usage.cost_by_model.entry(model.clone()).or_default() += cost;
+            *usage.tokens_by_day.entry(date).or_default() += tokens;
+            *usage.cost_by_day.entry(date).or_default() += cost;
+        }
+    }
+    usage
+}
+
+/// Sessions first seen on each local day between `start_time` and `end_time`,
+/// across the history files of every enabled data source
+fn sessions_by_day(app: &AppHandle, start_time: i64, end_time: i64) -> BTreeMap<NaiveDate, u32> {
+    let range = start_time * 1000..=end_time * 1000;
+    let mut first_seen: HashMap<String, i64> = HashMap::new();
+
+    for source in enabled_sources(app) {
+        let _ = with_history_index(&source.history_path(), |index| {
+            for record in index
+                .records
+                .iter()
+                .filter(|r| range.contains(&r.timestamp))
+            {
+                let session_id = &index.sessions[record.session as usize].session_id;
+                first_seen
+                    .entry(session_id.clone())
+                    .and_modify(|t| *t = (*t).min(record.timestamp))
+                    .or_insert(record.timestamp);
+            }
+        });
+    }
+
+    let mut by_day = BTreeMap::new();
+    for timestamp in first_seen.into_values() {
+        *by_day
+            .entry(timezone::date_of(timestamp / 1000))
+            .or_default() += 1;
+    }
+    by_day
+}
+
+fn estimate_local_metrics(
+    app: &AppHandle,
+    start_time: i64,
+    end_time: i64,
+) -> Result<DashboardMetrics, AppError> {
+    let cache = load_merged_stats_cache(app)?;
+    let pricing = resolve_profile(app, &frontend_pricing_provider(app));
+    let usage = daily_usage(
+        &cache,
+        &pricing,
+        timezone::date_of(start_time),
+        timezone::date_of(end_time),
+    );
+    let sessions = sessions_by_day(app, start_time, end_time);
+
+    // Daily tokens carry no type breakdown, so split each model's total by its
+    // all-time mix from modelUsage (the same assumption the cost estimate makes)
+    let (mut input, mut output, mut cache_read, mut cache_creation) = (0, 0, 0, 0);
+    let mut cost_by_token_type = TokenTypeCost::default();
+    for (model, &tokens) in &usage.tokens_by_model {
+        let Some(model_usage) = cache.model_usage.get(model) else {
+            continue;
+        };
+        let total = model_usage_tokens(model_usage);
+        if total == 0 {
+            continue;
+        }
+        let share = |n: u64| tokens as f64 * n as f64 / total as f64;
+        input += share(model_usage.input_tokens).round() as u64;
+        output += share(model_usage.output_tokens).round() as u64;
+        cache_read += share(model_usage.cache_read_input_tokens).round() as u64;
+        cache_creation += share(model_usage.cache_creation_input_tokens).round() as u64;
+
+        let rates = pricing.rates_for(model);
+        for (token_type, n) in [
+            (TokenType::Input, model_usage.input_tokens),
+            (TokenType::Output, model_usage.output_tokens),
+            (TokenType::CacheRead, model_usage.cache_read_input_tokens),
+            (
+                TokenType::CacheCreation,
+                model_usage.cache_creation_input_tokens,
+            ),
+        ] {
+            cost_by_token_type.add(token_type, share(n), &rates);
+        }
+    }
+
+    let mut tokens_by_model: Vec<ModelTokens> = usage
+        .tokens_by_model
+        .into_iter()
+        .map(|(model, tokens)| ModelTokens { model, tokens })
+        .collect();
+    tokens_by_model.sort_by_key(|m| std::cmp::Reverse(m.tokens));
+    let mut cost_by_model: Vec<ModelCost> = usage
+        .cost_by_model
+        .into_iter()
+        .map(|(model, cost_usd)| ModelCost {
+            model,
+            cost_usd,
+            percentage: 0.0,
+        })
+        .collect();
+    rank_model_costs(&mut cost_by_model);
+
+    Ok(DashboardMetrics {
+        total_tokens: usage.tokens_by_day.values().sum(),
+        total_cost_usd: usage.cost_by_day.values().sum(),
+        active_time_seconds: 0.0,
+        session_count: sessions.values().sum(),
+        lines_added: 0,
+        lines_removed: 0,
+        commit_count: 0,
+        pull_request_count: 0,
+        tokens_by_model,
+        cost_by_model,
+        usage_by_terminal: Vec::new(),
+        tokens_over_time: usage
+            .tokens_by_day
+            .into_iter()
+            .map(|(date, tokens)| point(date, tokens as f64))
+            .collect(),
+        cost_over_time: usage
+            .cost_by_day
+            .into_iter()
+            .map(|(date, cost)| point(date, cost))
+            .collect(),
+        tokens_by_type_over_time: TokenTypeSeries::default(),
+        lines_added_over_time: Vec::new(),
+        lines_removed_over_time: Vec::new(),
+        sessions_over_time: sessions
+            .into_iter()
+            .map(|(date, count)| point(date, count as f64))
+            .collect(),
+        tokens_by_version_over_time: Vec::new(),
+        input_tokens: input,
+        output_tokens: output,
+        cache_read_tokens: cache_read,
+        cache_creation_tokens: cache_creation,
+        cost_by_token_type,
+        output_tokens_per_second: 0.0,
+        output_tokens_per_second_by_model: Vec::new(),
+        burn_rate_usd_per_hour: 0.0,
+        burn_rate_over_time: Vec::new(),
+        endpoints: Vec::new(),
+        generation: 0,
+        source: MetricsSource::Local,
+    })
+}
+
+/// Dashboard metrics estimated from `~/.claude/stats-cache.json` and
+/// `history.jsonl`, for when Prometheus can't be reached. Tokens and cost are
+/// only recorded per day, so every day the range touches counts in full; active
+/// time, lines and commits aren't recorded locally and stay at zero.
+pub async fn local_dashboard_metrics(
+    app: AppHandle,
+    time_range: &str,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<DashboardMetrics, AppError> {
+    let (start_time, end_time, _) = resolve_time_range(time_range, custom_start, custom_end)?;
+    tauri::async_runtime::spawn_blocking(move || estimate_local_metrics(&app, start_time, end_time))
+        .await
+        .map_err(|e| AppError::Other(e.to_string()))?
+}
@@ -0,0 +1,283 @@
+// tauri-app/src-tauri/src/panels.rs
+
+use crate::commands::{range_anchor, resolve_time_range, step_and_rate_window, to_time_series};
+use crate::metrics::LabeledSeries;
+use crate::prometheus::{PrometheusClient, QueryResult};
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+const CUSTOM_PANELS_KEY: &str = "customPanels";
+const MAX_QUERY_LENGTH: usize = 4096;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelVisualization {
+    Stat,
+    Line,
+    Bar,
+    Table,
+}
+
+/// How a panel's query is evaluated against the selected time range
+/// - instant: single evaluation at the range end; `$range` is replaced with the range duration
+/// - range: query_range over the window; `$rate_window` is replaced with the step-matched window
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelRangeMode {
+    Instant,
+    Range,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomPanel {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub query: String,
+    pub visualization: PanelVisualization,
+    pub range_mode: PanelRangeMode,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanelValue {
+    pub labels: HashMap<String, String>,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanelResult {
+    pub panel_id: String,
+    pub visualization: PanelVisualization,
+    pub values: Vec<PanelValue>,
+    pub series: Vec<LabeledSeries>,
+}
+
+/// Basic sanity checks before handing a user query to Prometheus
+fn validate_panel(panel: &CustomPanel) -> Result<(), String> {
+    if panel.name.trim().is_empty() {
+        return Err("Panel name is required".to_string());
+    }
+    let query = panel.query.trim();
+    if query.is_empty() {
+        return Err("Panel query is required".to_string());
+    }
+    if query.len() > MAX_QUERY_LENGTH {
+        return Err(format!(
+            "Panel query exceeds {} characters",
+            MAX_QUERY_LENGTH
+        ));
+    }
+
+    // Check brackets are balanced outside of string literals
+    let mut stack = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for c in query.chars() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' | '[' | '{' => stack.push(c),
+            ')' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if stack.pop() != Some(expected) {
+                    return Err(format!("Unbalanced '{}' in panel query", c));
+                }
+            }
+            _ => {}
+        }
+    }
+    if quote.is_some() {
+        return Err("Unterminated string in panel query".to_string());
+    }
+    if let Some(c) = stack.pop() {
+        return Err(format!("Unclosed '{}' in panel query", c));
+    }
+    Ok(())
+}
+
+/// Render a metric's labels as `{k="v", ...}` for use as a series name
+fn format_labels(metric: &HashMap<String, String>) -> String {
+    let mut labels: Vec<_> = metric.iter().filter(|(k, _)| *k != "__name__").collect();
+    if labels.is_empty() {
+        return "value".to_string();
+    }
+    labels.sort();
+    let inner: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, v))
+        .collect();
+    format!("{{{}}}", inner.join(", "))
+}
+
+fn to_panel_value(result: &QueryResult) -> Option<PanelValue> {
+    let value = result.value.as_ref()?.1.parse::<f64>().ok()?;
+    Some(PanelValue {
+        labels: result.metric.clone(),
+        value,
+    })
+}
+
+pub fn load_custom_panels(app: &AppHandle) -> Vec<CustomPanel> {
+    load_setting(app, CUSTOM_PANELS_KEY)
+}
+
+#[tauri::command]
+pub async fn get_custom_panels(app: AppHandle) -> Result<Vec<CustomPanel>, String> {
+    Ok(load_custom_panels(&app))
+}
+
+/// Create or update a panel (matched by id); returns the stored panel
+#[tauri::command]
+pub async fn save_custom_panel(app: AppHandle, panel: CustomPanel) -> Result<CustomPanel, String> {
+    validate_panel(&panel)?;
+
+    let mut panel = panel;
+    if panel.id.is_empty() {
+        panel.id = format!("panel-{}", chrono::Utc::now().timestamp_millis());
+    }
+
+    let mut panels = load_custom_panels(&app);
+    match panels.iter_mut().find(|p| p.id == panel.id) {
+        Some(existing) => *existing = panel.clone(),
+        None => panels.push(panel.clone()),
+    }
+    save_setting(&app, CUSTOM_PANELS_KEY, &panels)?;
+    Ok(panel)
+}
+
+#[tauri::command]
+pub async fn delete_custom_panel(app: AppHandle, panel_id: String) -> Result<(), String> {
+    let mut panels = load_custom_panels(&app);
+    panels.retain(|p| p.id != panel_id);
+    save_setting(&app, CUSTOM_PANELS_KEY, &panels)
+}
+
+#[tauri::command]
+pub async fn run_panel(
+    app: AppHandle,
+    panel_id: String,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<PanelResult, String> {
+    let panel = load_custom_panels(&app)
+        .into_iter()
+        .find(|p| p.id == panel_id)
+        .ok_or(format!("Panel not found: {}", panel_id))?;
+    validate_panel(&panel)?;
+
+    let client = PrometheusClient::new(&prometheus_url).map_err(|e| e.to_string())?;
+    let (start_time, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let (step, rate_window) = step_and_rate_window(&time_range, start_time, end_time);
+
+    let query = panel
+        .query
+        .replace("$rate_window", rate_window)
+        .replace("$range", &range);
+
+    let mut result = PanelResult {
+        panel_id: panel.id,
+        visualization: panel.visualization,
+        values: Vec::new(),
+        series: Vec::new(),
+    };
+
+    match panel.range_mode {
+        PanelRangeMode::Instant => {
+            // Panel queries are free-form, so pin the evaluation time rather than
+            // rewriting their selectors with `@`
+            result.values = client
+                .query_at(&query, range_anchor(&time_range, end_time))
+                .await
+                .map_err(|e| e.to_string())?
+                .iter()
+                .filter_map(to_panel_value)
+                .collect();
+        }
+        PanelRangeMode::Range => {
+            result.series = client
+                .query_range(&query, start_time, end_time, step)
+                .await
+                .map_err(|e| e.to_string())?
+                .iter()
+                .map(|r| LabeledSeries {
+                    label: format_labels(&r.metric),
+                    points: to_time_series(r),
+                })
+                .collect();
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panel(query: &str) -> CustomPanel {
+        CustomPanel {
+            id: String::new(),
+            name: "Panel".to_string(),
+            query: query.to_string(),
+            visualization: PanelVisualization::Stat,
+            range_mode: PanelRangeMode::Instant,
+        }
+    }
+
+    #[test]
+    fn balanced_queries_pass_including_brackets_inside_strings() {
+        assert!(validate_panel(&panel("sum(rate(metric[5m]))")).is_ok());
+        assert!(
+            validate_panel(&panel(r#"sum by (model) (metric{job="a)", path=~"x\"]"})"#)).is_ok()
+        );
+    }
+
+    #[test]
+    fn unbalanced_queries_report_the_offending_bracket() {
+        assert_eq!(
+            validate_panel(&panel("sum(rate(metric[5m])")),
+            Err("Unclosed '(' in panel query".to_string())
+        );
+        assert_eq!(
+            validate_panel(&panel("sum(metric[5m)]")),
+            Err("Unbalanced ')' in panel query".to_string())
+        );
+        assert_eq!(
+            validate_panel(&panel("metric}")),
+            Err("Unbalanced '}' in panel query".to_string())
+        );
+        assert_eq!(
+            validate_panel(&panel(r#"metric{job="a}"#)),
+            Err("Unterminated string in panel query".to_string())
+        );
+    }
+
+    #[test]
+    fn name_and_query_are_required() {
+        let mut unnamed = panel("up");
+        unnamed.name = " ".to_string();
+        assert!(validate_panel(&unnamed).is_err());
+        assert!(validate_panel(&panel("  ")).is_err());
+        assert!(validate_panel(&panel(&"a".repeat(MAX_QUERY_LENGTH + 1))).is_err());
+    }
+}
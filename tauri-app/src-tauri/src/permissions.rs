@@ -0,0 +1,407 @@
+// tauri-app/src-tauri/src/permissions.rs
+
+use crate::claude_storage::project_names;
+use crate::commands::{range_anchor, resolve_time_range, step_and_rate_window, to_time_series};
+use crate::error::AppError;
+use crate::metrics::TimeSeriesPoint;
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::{load_query_tuning, QueryTuning};
+use crate::timezone;
+use crate::transcript_archive::{all_transcripts, TranscriptFile};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufRead;
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::AppHandle;
+
+const DECISION_METRIC: &str = "claude_code_code_edit_tool_decision_total";
+
+/// Tool results Claude Code writes when a tool call is denied at the prompt
+const REJECTION_MARKERS: &[&str] = &[
+    "The user doesn't want to proceed with this tool use",
+    "The user doesn't want to take this action right now",
+    "[Request interrupted by user for tool use]",
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ToolResultContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    id: Option<String>,
+    name: Option<String>,
+    tool_use_id: Option<String>,
+    text: Option<String>,
+    content: Option<ToolResultContent>,
+    #[serde(default)]
+    is_error: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    /// A plain string for typed prompts, blocks for everything else
+    content: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptLine {
+    timestamp: Option<DateTime<Utc>>,
+    message: Option<Message>,
+}
+
+/// Tool calls and how many of them were denied, for one tool, project or day
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionStats {
+    /// Tool name, project name or local date depending on the grouping
+    pub key: String,
+    pub tool_uses: u64,
+    pub rejected: u64,
+    /// Share of tool calls that went ahead, 0-100
+    pub acceptance_rate: f64,
+}
+
+impl PermissionStats {
+    fn record(&mut self, rejected: bool) {
+        self.tool_uses += 1;
+        if rejected {
+            self.rejected += 1;
+        }
+        self.acceptance_rate =
+            (self.tool_uses - self.rejected) as f64 / self.tool_uses as f64 * 100.0;
+    }
+}
+
+/// Accept/reject decisions for an edit tool or language, from the decision metric
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditDecisionStats {
+    /// Tool name or language depending on the grouping
+    pub key: String,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub acceptance_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionAnalytics {
+    pub start_time: i64,
+    pub end_time: i64,
+    /// From transcripts; most rejected first
+    pub by_tool: Vec<PermissionStats>,
+    pub by_project: Vec<PermissionStats>,
+    /// One entry per local day with tool activity
+    pub over_time: Vec<PermissionStats>,
+    /// Empty when no Prometheus URL is given or the query fails
+    pub edit_decisions: Vec<EditDecisionStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditDecisionMetrics {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub acceptance_rate: f64,
+    /// Most rejected first
+    pub by_tool: Vec<EditDecisionStats>,
+    pub by_language: Vec<EditDecisionStats>,
+    /// Decisions per bucket
+    pub accepted_over_time: Vec<TimeSeriesPoint>,
+    pub rejected_over_time: Vec<TimeSeriesPoint>,
+}
+
+fn result_text(content: &Option<ToolResultContent>) -> String {
+    match content {
+        Some(ToolResultContent::Text(text)) => text.clone(),
+        Some(ToolResultContent::Blocks(blocks)) => blocks
+            .iter()
+            .filter_map(|b| b.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => String::new(),
+    }
+}
+
+fn is_rejection(block: &ContentBlock) -> bool {
+    let text = result_text(&block.content);
+    block.is_error && REJECTION_MARKERS.iter().any(|m| text.contains(m))
+}
+
+/// (timestamp, tool, rejected) for every tool call in the transcript between
+/// `start` and `end`
+fn scan_transcript(transcript: &TranscriptFile, start: i64, end: i64) -> Vec<(i64, String, bool)> {
+    let Ok(reader) = transcript.open() else {
+        return Vec::new();
+    };
+    // tool_use id -> (timestamp, tool name), resolved when its result arrives
+    let mut pending: HashMap<String, (i64, String)> = HashMap::new();
+    let mut calls = Vec::new();
+
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<TranscriptLine>(&line) else {
+            continue;
+        };
+        let Some(timestamp) = entry.timestamp.map(|t| t.timestamp()) else {
+            continue;
+        };
+        let Some(Ok(blocks)) = entry
+            .message
+            .and_then(|m| m.content)
+            .map(serde_json::from_value::<Vec<ContentBlock>>)
+        else {
+            continue;
+        };
+
+        for block in blocks {
+            match block.kind.as_deref() {
+                Some("tool_use") => {
+                    if let (Some(id), Some(name)) = (block.id.clone(), block.name.clone()) {
+                        pending.insert(id, (timestamp, name));
+                    }
+                }
+                Some("tool_result") => {
+                    let Some((called_at, tool)) =
+                        block.tool_use_id.as_ref().and_then(|id| pending.remove(id))
+                    else {
+                        continue;
+                    };
+                    if (start..=end).contains(&called_at) {
+                        calls.push((called_at, tool, is_rejection(&block)));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    calls
+}
+
+fn sorted(stats: HashMap<String, PermissionStats>) -> Vec<PermissionStats> {
+    let mut stats: Vec<PermissionStats> = stats.into_values().collect();
+    stats.sort_by(|a, b| {
+        b.rejected
+            .cmp(&a.rejected)
+            .then(b.tool_uses.cmp(&a.tool_uses))
+    });
+    stats
+}
+
+fn transcript_analytics(
+    start: i64,
+    end: i64,
+) -> (
+    Vec<PermissionStats>,
+    Vec<PermissionStats>,
+    Vec<PermissionStats>,
+) {
+    let cutoff = UNIX_EPOCH + Duration::from_secs(start.max(0) as u64);
+    let names = project_names();
+    let mut by_tool: HashMap<String, PermissionStats> = HashMap::new();
+    let mut by_project: HashMap<String, PermissionStats> = HashMap::new();
+    let mut by_day: BTreeMap<String, PermissionStats> = BTreeMap::new();
+
+    for transcript in all_transcripts() {
+        let recent = std::fs::metadata(&transcript.path)
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified >= cutoff);
+        if !recent {
+            continue;
+        }
+        let project = names
+            .get(&transcript.project_folder)
+            .and_then(|path| std::path::Path::new(path).file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| transcript.project_folder.clone());
+
+        for (timestamp, tool, rejected) in scan_transcript(&transcript, start, end) {
+            let day = timezone::date_of(timestamp).format("%Y-%m-%d").to_string();
+            for (stats, key) in [(&mut by_tool, tool), (&mut by_project, project.clone())] {
+                stats
+                    .entry(key.clone())
+                    .or_insert_with(|| PermissionStats {
+                        key,
+                        ..Default::default()
+                    })
+                    .record(rejected);
+            }
+            by_day
+                .entry(day.clone())
+                .or_insert_with(|| PermissionStats {
+                    key: day,
+                    ..Default::default()
+                })
+                .record(rejected);
+        }
+    }
+
+    (
+        sorted(by_tool),
+        sorted(by_project),
+        by_day.into_values().collect(),
+    )
+}
+
+/// Edit decisions over the range grouped by `label` ("tool" or "language")
+async fn edit_decisions(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    label: &str,
+    range: &str,
+    anchor: Option<i64>,
+) -> Result<Vec<EditDecisionStats>, AppError> {
+    let query = format!(
+        "sum by ({}, decision) (increase({}{}))",
+        label,
+        DECISION_METRIC,
+        tuning.selector(DECISION_METRIC, range, anchor)
+    );
+    let results = client.query(&query).await?;
+
+    let mut by_key: HashMap<String, EditDecisionStats> = HashMap::new();
+    for result in results {
+        let key = result
+            .metric
+            .get(label)
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_string());
+        let count = result
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse::<f64>().ok())
+            .unwrap_or(0.0)
+            .round() as u64;
+        let stats = by_key
+            .entry(key.clone())
+            .or_insert_with(|| EditDecisionStats {
+                key,
+                ..Default::default()
+            });
+        match result.metric.get("decision").map(String::as_str) {
+            Some("accept") => stats.accepted += count,
+            Some("reject") => stats.rejected += count,
+            _ => {}
+        }
+    }
+
+    let mut decisions: Vec<EditDecisionStats> = by_key
+        .into_values()
+        .filter(|s| s.accepted + s.rejected > 0)
+        .map(|mut s| {
+            s.acceptance_rate = s.accepted as f64 / (s.accepted + s.rejected) as f64 * 100.0;
+            s
+        })
+        .collect();
+    decisions.sort_by_key(|d| std::cmp::Reverse(d.rejected));
+    Ok(decisions)
+}
+
+/// Tool call acceptance by tool, project and day from transcripts, plus edit
+/// tool accept/reject counts from Prometheus when a URL is given
+#[tauri::command]
+pub async fn get_permission_analytics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: Option<String>,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<PermissionAnalytics, String> {
+    let (start_time, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+
+    let (by_tool, by_project, over_time) =
+        tauri::async_runtime::spawn_blocking(move || transcript_analytics(start_time, end_time))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let edit_decisions = match prometheus_url {
+        Some(url) => {
+            let tuning = load_query_tuning(&app);
+            let client = PrometheusClient::new(&url)
+                .map_err(|e| e.to_string())?
+                .with_lookback_delta(tuning.lookback_delta.clone());
+            let anchor = range_anchor(&time_range, end_time);
+            edit_decisions(&client, &tuning, "tool", &range, anchor)
+                .await
+                .unwrap_or_default()
+        }
+        None => Vec::new(),
+    };
+
+    Ok(PermissionAnalytics {
+        start_time,
+        end_time,
+        by_tool,
+        by_project,
+        over_time,
+        edit_decisions,
+    })
+}
+
+/// Accepted vs rejected edits from the decision metric, by tool and language,
+/// with the trend over the range
+#[tauri::command]
+pub async fn get_edit_decision_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+) -> Result<EditDecisionMetrics, AppError> {
+    let (start_time, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let anchor = range_anchor(&time_range, end_time);
+    let (step, rate_window) = step_and_rate_window(&time_range, start_time, end_time);
+    let tuning = load_query_tuning(&app);
+    let client =
+        PrometheusClient::new(&prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+
+    let trend_query = format!(
+        "sum by (decision) (increase({}[{}]))",
+        DECISION_METRIC,
+        tuning.rate_window(DECISION_METRIC, rate_window)
+    );
+    let (by_tool, by_language, trend) = tokio::join!(
+        edit_decisions(&client, &tuning, "tool", &range, anchor),
+        edit_decisions(&client, &tuning, "language", &range, anchor),
+        client.query_range(&trend_query, start_time, end_time, step),
+    );
+    let (by_tool, by_language) = (by_tool?, by_language?);
+
+    let mut accepted_over_time = Vec::new();
+    let mut rejected_over_time = Vec::new();
+    for result in trend? {
+        match result.metric.get("decision").map(String::as_str) {
+            Some("accept") => accepted_over_time = to_time_series(&result),
+            Some("reject") => rejected_over_time = to_time_series(&result),
+            _ => {}
+        }
+    }
+
+    let accepted: u64 = by_tool.iter().map(|s| s.accepted).sum();
+    let rejected: u64 = by_tool.iter().map(|s| s.rejected).sum();
+    let acceptance_rate = if accepted + rejected > 0 {
+        accepted as f64 / (accepted + rejected) as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(EditDecisionMetrics {
+        start_time,
+        end_time,
+        accepted,
+        rejected,
+        acceptance_rate,
+        by_tool,
+        by_language,
+        accepted_over_time,
+        rejected_over_time,
+    })
+}
@@ -0,0 +1,251 @@
+// tauri-app/src-tauri/src/plan_limits.rs
+
+use crate::blocks::recent_usage;
+use crate::error::AppError;
+use crate::settings::{load_setting, save_setting};
+use crate::timezone;
+use crate::verification::UsageRecord;
+use chrono::{Duration, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const PLAN_LIMITS_KEY: &str = "planLimits";
+/// Burn rate is measured over this trailing window
+const BURN_WINDOW_SECONDS: i64 = 24 * 3600;
+const DEFAULT_HISTORY_WEEKS: u32 = 8;
+
+/// Estimated weekly allowance of a Pro/Max plan. Anthropic doesn't publish exact
+/// figures, so these are set by the user from experience.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlanLimits {
+    pub weekly_tokens: Option<u64>,
+    pub weekly_cost_usd: Option<f64>,
+    /// Day and hour (in the configured timezone) the weekly window resets,
+    /// 0 = Monday ... 6 = Sunday
+    pub reset_day: u8,
+    pub reset_hour: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekUsage {
+    pub week_start: i64,
+    pub week_end: i64,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// Share of the allowance used, by whichever limit is closer; None without limits
+    pub percent_consumed: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyLimitStatus {
+    pub limits: PlanLimits,
+    pub current: WeekUsage,
+    /// Over the last 24 hours
+    pub tokens_per_hour: f64,
+    pub cost_per_hour: f64,
+    /// When the allowance runs out at the current burn rate; None if it lasts
+    /// until the reset (or no limit is set)
+    pub projected_exhaustion: Option<i64>,
+    /// Earlier weeks, newest first
+    pub history: Vec<WeekUsage>,
+}
+
+impl PlanLimits {
+    fn reset_time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.reset_hour.min(23), 0, 0).unwrap_or_default()
+    }
+
+    /// Date of the reset that began the weekly window containing `now`
+    fn window_date(&self, now: i64) -> NaiveDate {
+        let reset_day = Weekday::try_from(self.reset_day.min(6)).unwrap_or(Weekday::Mon);
+        let date = timezone::week_start_on(timezone::date_of(now), reset_day);
+        // On the reset day itself, before the reset hour, the window began a week ago
+        if timezone::timestamp_of(date.and_time(self.reset_time())) > now {
+            date - Duration::weeks(1)
+        } else {
+            date
+        }
+    }
+
+    /// Start and end of the window `weeks_back` weeks before the one beginning
+    /// on `date`. Windows follow the calendar, so one spanning a DST change is
+    /// an hour shorter or longer.
+    fn window(&self, date: NaiveDate, weeks_back: i64) -> (i64, i64) {
+        let start = date - Duration::weeks(weeks_back);
+        let end = start + Duration::weeks(1);
+        (
+            timezone::timestamp_of(start.and_time(self.reset_time())),
+            timezone::timestamp_of(end.and_time(self.reset_time())),
+        )
+    }
+
+    fn percent_consumed(&self, tokens: u64, cost_usd: f64) -> Option<f64> {
+        let by_tokens = self
+            .weekly_tokens
+            .filter(|&limit| limit > 0)
+            .map(|limit| tokens as f64 / limit as f64 * 100.0);
+        let by_cost = self
+            .weekly_cost_usd
+            .filter(|&limit| limit > 0.0)
+            .map(|limit| cost_usd / limit * 100.0);
+        match (by_tokens, by_cost) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn usage_between(&self, records: &[UsageRecord], start: i64, end: i64) -> WeekUsage {
+        let in_range = records.iter().filter(|r| {
+            let t = r.timestamp.timestamp();
+            t >= start && t < end
+        });
+        let (tokens, cost_usd) = in_range.fold((0, 0.0), |(tokens, cost), r| {
+            (tokens + r.tokens, cost + r.cost_usd)
+        });
+        WeekUsage {
+            week_start: start,
+            week_end: end,
+            tokens,
+            cost_usd,
+            percent_consumed: self.percent_consumed(tokens, cost_usd),
+        }
+    }
+
+    /// Seconds until the first limit runs out at the given hourly rates
+    fn seconds_to_exhaustion(
+        &self,
+        current: &WeekUsage,
+        tokens_per_hour: f64,
+        cost_per_hour: f64,
+    ) -> Option<i64> {
+        let by_tokens = self.weekly_tokens.and_then(|limit| {
+            let remaining = limit.saturating_sub(current.tokens) as f64;
+            (tokens_per_hour > 0.0).then(|| remaining / tokens_per_hour * 3600.0)
+        });
+        let by_cost = self.weekly_cost_usd.and_then(|limit| {
+            let remaining = (limit - current.cost_usd).max(0.0);
+            (cost_per_hour > 0.0).then(|| remaining / cost_per_hour * 3600.0)
+        });
+        let seconds = match (by_tokens, by_cost) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => a.or(b)?,
+        };
+        Some(seconds.round() as i64)
+    }
+}
+
+fn weekly_limit_status(app: &AppHandle, weeks: u32) -> WeeklyLimitStatus {
+    let limits: PlanLimits = load_setting(app, PLAN_LIMITS_KEY);
+    let now = chrono::Utc::now().timestamp();
+    let window_date = limits.window_date(now);
+    let (week_start, week_end) = limits.window(window_date, 0);
+    let days = ((now - week_start) / 86400) as u32 + weeks * 7 + 1;
+    let records = recent_usage(app, days, now);
+
+    let current = limits.usage_between(&records, week_start, week_end);
+    let history = (1..=weeks as i64)
+        .map(|n| {
+            let (start, end) = limits.window(window_date, n);
+            limits.usage_between(&records, start, end)
+        })
+        .collect();
+
+    let recent = limits.usage_between(&records, now - BURN_WINDOW_SECONDS, now + 60);
+    let hours = BURN_WINDOW_SECONDS as f64 / 3600.0;
+    let tokens_per_hour = recent.tokens as f64 / hours;
+    let cost_per_hour = recent.cost_usd / hours;
+    let projected_exhaustion = limits
+        .seconds_to_exhaustion(&current, tokens_per_hour, cost_per_hour)
+        .map(|seconds| now + seconds)
+        .filter(|&at| at < current.week_end);
+
+    WeeklyLimitStatus {
+        limits,
+        current,
+        tokens_per_hour,
+        cost_per_hour,
+        projected_exhaustion,
+        history,
+    }
+}
+
+#[tauri::command]
+pub async fn get_plan_limits(app: AppHandle) -> Result<PlanLimits, AppError> {
+    Ok(load_setting(&app, PLAN_LIMITS_KEY))
+}
+
+#[tauri::command]
+pub async fn set_plan_limits(app: AppHandle, limits: PlanLimits) -> Result<(), AppError> {
+    if limits.weekly_tokens == Some(0) || limits.weekly_cost_usd.is_some_and(|c| c <= 0.0) {
+        return Err(AppError::InvalidInput(
+            "Weekly limits must be greater than zero".to_string(),
+        ));
+    }
+    if limits.reset_day > 6 || limits.reset_hour > 23 {
+        return Err(AppError::InvalidInput(
+            "Reset day must be 0-6 and reset hour 0-23".to_string(),
+        ));
+    }
+    Ok(save_setting(&app, PLAN_LIMITS_KEY, &limits)?)
+}
+
+/// Usage in the current weekly window against the configured plan allowance,
+/// when it will run out at the last day's burn rate, and past weeks
+#[tauri::command]
+pub async fn get_weekly_limit_status(
+    app: AppHandle,
+    weeks: Option<u32>,
+) -> Result<WeeklyLimitStatus, AppError> {
+    let weeks = weeks.unwrap_or(DEFAULT_HISTORY_WEEKS);
+    tauri::async_runtime::spawn_blocking(move || weekly_limit_status(&app, weeks))
+        .await
+        .map_err(|e| AppError::Other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall_clock(day: u32, hour: u32) -> i64 {
+        // 2026-06-03 is a Wednesday
+        let date = NaiveDate::from_ymd_opt(2026, 6, day).unwrap();
+        timezone::timestamp_of(date.and_hms_opt(hour, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn window_starts_at_the_latest_reset() {
+        let limits = PlanLimits {
+            reset_day: 2,
+            reset_hour: 9,
+            ..Default::default()
+        };
+        let reset = NaiveDate::from_ymd_opt(2026, 6, 3).unwrap();
+
+        assert_eq!(limits.window_date(wall_clock(3, 9)), reset);
+        assert_eq!(limits.window_date(wall_clock(5, 12)), reset);
+        assert_eq!(limits.window_date(wall_clock(10, 8)), reset);
+        assert_eq!(
+            limits.window_date(wall_clock(3, 8)),
+            reset - Duration::weeks(1)
+        );
+    }
+
+    #[test]
+    fn window_spans_one_calendar_week() {
+        let limits = PlanLimits {
+            reset_day: 2,
+            reset_hour: 9,
+            ..Default::default()
+        };
+        let reset = NaiveDate::from_ymd_opt(2026, 6, 3).unwrap();
+
+        assert_eq!(
+            limits.window(reset, 0),
+            (wall_clock(3, 9), wall_clock(10, 9))
+        );
+        assert_eq!(limits.window(reset, 1).1, wall_clock(3, 9));
+    }
+}
@@ -0,0 +1,331 @@
+// tauri-app/src-tauri/src/plugins.rs
+//
+// Plugins are executables discovered under <app data>/plugins/<id>/plugin.json.
+// The app talks to them over JSON-RPC 2.0: one request is written to the
+// plugin's stdin as a single line and one response line is read from stdout.
+// A fresh process is started per call, so plugins can be written as simple
+// scripts without their own event loop.
+//
+// Methods:
+//   describe                        -> { name, version, capabilities }
+//   fetch_metrics { start, end }    -> { series: [{ name, labels, points: [{timestamp, value}] }] }   (data-source)
+//   export_event { event, data }    -> null                                                           (exporter)
+
+use crate::local_data::app_data_dir;
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+const MANIFEST_FILE: &str = "plugin.json";
+const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+
+/// Changes arriving within this window (e.g. copying a plugin in) cause one reload
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PluginCapability {
+    DataSource,
+    Exporter,
+}
+
+/// Contents of plugin.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    /// Executable, relative to the plugin directory or on PATH
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub capabilities: Vec<PluginCapability>,
+    /// Events forwarded to exporters (empty = all)
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    /// Directory name under the plugins folder
+    pub id: String,
+    pub path: String,
+    pub manifest: Option<PluginManifest>,
+    /// Why the plugin couldn't be loaded, if it couldn't
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPoint {
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSeries {
+    pub name: String,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    pub points: Vec<PluginPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FetchMetricsResult {
+    series: Vec<PluginSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+pub fn plugins_dir() -> Option<PathBuf> {
+    app_data_dir().map(|d| d.join("plugins"))
+}
+
+fn load_plugin(dir: PathBuf) -> PluginInfo {
+    let id = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|_| format!("Missing {}", MANIFEST_FILE))
+        .and_then(|contents| {
+            serde_json::from_str::<PluginManifest>(&contents)
+                .map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))
+        });
+
+    let (manifest, error) = match manifest {
+        Ok(manifest) => (Some(manifest), None),
+        Err(e) => (None, Some(e)),
+    };
+    PluginInfo {
+        id,
+        path: dir.to_string_lossy().to_string(),
+        manifest,
+        error,
+    }
+}
+
+/// Plugins found by the last scan, so publishing an event doesn't hit the disk
+static PLUGINS: Mutex<Option<Vec<PluginInfo>>> = Mutex::new(None);
+
+/// Every plugin directory, including ones whose manifest failed to load
+fn discover_plugins() -> Vec<PluginInfo> {
+    let Some(dir) = plugins_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<PluginInfo> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .map(load_plugin)
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    plugins
+}
+
+/// Rescan the plugins directory and replace the cached list
+pub fn reload_plugins() -> Vec<PluginInfo> {
+    let plugins = discover_plugins();
+    if let Ok(mut guard) = PLUGINS.lock() {
+        *guard = Some(plugins.clone());
+    }
+    plugins
+}
+
+/// Cached plugin list, scanning the directory on first use
+pub fn loaded_plugins() -> Vec<PluginInfo> {
+    let cached = PLUGINS.lock().ok().and_then(|guard| guard.clone());
+    cached.unwrap_or_else(reload_plugins)
+}
+
+/// Reload the plugin list whenever something under the plugins directory changes
+pub fn start_plugin_watcher() {
+    let Some(dir) = plugins_dir() else {
+        return;
+    };
+    std::thread::spawn(move || {
+        // Created up front so plugins added later are picked up without a restart
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            eprintln!("Failed to create {}: {}", dir.display(), e);
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Failed to start the plugin watcher: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&dir, RecursiveMode::Recursive) {
+            eprintln!("Failed to watch {}: {}", dir.display(), e);
+            return;
+        }
+
+        while rx.recv().is_ok() {
+            while rx.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+            reload_plugins();
+        }
+    });
+}
+
+fn find_plugin(plugin_id: &str) -> Result<(PathBuf, PluginManifest), String> {
+    let plugin = loaded_plugins()
+        .into_iter()
+        .find(|p| p.id == plugin_id)
+        .ok_or(format!("Plugin not found: {}", plugin_id))?;
+    let manifest = plugin
+        .manifest
+        .ok_or_else(|| plugin.error.unwrap_or_default())?;
+    Ok((PathBuf::from(plugin.path), manifest))
+}
+
+/// Send one JSON-RPC request to a fresh plugin process and read its response
+async fn call(
+    dir: &Path,
+    manifest: &PluginManifest,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    let local_command = dir.join(&manifest.command);
+    let program = if local_command.is_file() {
+        local_command.to_string_lossy().to_string()
+    } else {
+        manifest.command.clone()
+    };
+
+    let mut child = Command::new(&program)
+        .args(&manifest.args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start plugin {}: {}", manifest.name, e))?;
+
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let mut stdin = child.stdin.take().ok_or("Plugin stdin unavailable")?;
+    let mut line = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    stdin
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    drop(stdin);
+
+    let stdout = child.stdout.take().ok_or("Plugin stdout unavailable")?;
+    let timeout = Duration::from_secs(manifest.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS));
+    let mut response_line = String::new();
+    tokio::time::timeout(
+        timeout,
+        BufReader::new(stdout).read_line(&mut response_line),
+    )
+    .await
+    .map_err(|_| format!("Plugin {} timed out", manifest.name))?
+    .map_err(|e| e.to_string())?;
+
+    let response: RpcResponse = serde_json::from_str(&response_line)
+        .map_err(|e| format!("Invalid response from plugin {}: {}", manifest.name, e))?;
+    if let Some(error) = response.error {
+        return Err(format!(
+            "Plugin {} error {}: {}",
+            manifest.name, error.code, error.message
+        ));
+    }
+    Ok(response.result.unwrap_or(Value::Null))
+}
+
+/// Forward an app event to every exporter plugin subscribed to it, in the background
+pub fn dispatch(event: &str, payload: &Value) {
+    let exporters: Vec<(PathBuf, PluginManifest)> = loaded_plugins()
+        .into_iter()
+        .filter_map(|p| Some((PathBuf::from(p.path), p.manifest?)))
+        .filter(|(_, m)| m.capabilities.contains(&PluginCapability::Exporter))
+        .filter(|(_, m)| m.events.is_empty() || m.events.iter().any(|e| e == event))
+        .collect();
+    if exporters.is_empty() {
+        return;
+    }
+
+    let params = json!({ "event": event, "data": payload });
+    tauri::async_runtime::spawn(async move {
+        for (dir, manifest) in exporters {
+            if let Err(e) = call(&dir, &manifest, "export_event", params.clone()).await {
+                eprintln!("{}", e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn list_plugins() -> Result<Vec<PluginInfo>, String> {
+    Ok(loaded_plugins())
+}
+
+/// Rescan the plugins directory, e.g. after editing a plugin.json by hand
+#[tauri::command]
+pub async fn reload_plugin_list() -> Result<Vec<PluginInfo>, String> {
+    Ok(reload_plugins())
+}
+
+/// Ask a plugin to describe itself (useful to check it runs)
+#[tauri::command]
+pub async fn describe_plugin(plugin_id: String) -> Result<Value, String> {
+    let (dir, manifest) = find_plugin(&plugin_id)?;
+    call(&dir, &manifest, "describe", json!({})).await
+}
+
+#[tauri::command]
+pub async fn fetch_plugin_metrics(
+    plugin_id: String,
+    start: i64,
+    end: i64,
+) -> Result<Vec<PluginSeries>, String> {
+    let (dir, manifest) = find_plugin(&plugin_id)?;
+    if !manifest
+        .capabilities
+        .contains(&PluginCapability::DataSource)
+    {
+        return Err(format!("Plugin {} is not a data source", manifest.name));
+    }
+
+    let result = call(
+        &dir,
+        &manifest,
+        "fetch_metrics",
+        json!({ "start": start, "end": end }),
+    )
+    .await?;
+    let result: FetchMetricsResult = serde_json::from_value(result)
+        .map_err(|e| format!("Invalid metrics from plugin {}: {}", manifest.name, e))?;
+    Ok(result.series)
+}
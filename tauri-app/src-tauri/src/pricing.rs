@@ -0,0 +1,226 @@
+// tauri-app/src-tauri/src/pricing.rs
+
+use crate::remote_pricing::remote_rates;
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const PRICING_PROFILES_KEY: &str = "pricingProfiles";
+const DEFAULT_PROFILE_ID: &str = "anthropic";
+
+/// USD per million tokens for each token type
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRates {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_creation: f64,
+}
+
+impl ModelRates {
+    /// Cache read = 10% of input, cache creation = 125% of input
+    const fn from_input_output(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_read: input * 0.1,
+            cache_creation: input * 1.25,
+        }
+    }
+
+    fn scaled(self, factor: f64) -> Self {
+        Self {
+            input: self.input * factor,
+            output: self.output * factor,
+            cache_read: self.cache_read * factor,
+            cache_creation: self.cache_creation * factor,
+        }
+    }
+}
+
+/// Rates for models whose id contains `pattern` (case-insensitive)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRatePattern {
+    pub pattern: String,
+    pub rates: ModelRates,
+}
+
+/// List prices of model versions priced differently from their family default,
+/// most specific pattern first
+fn default_model_rates() -> Vec<ModelRatePattern> {
+    let pattern = |pattern: &str, input: f64, output: f64| ModelRatePattern {
+        pattern: pattern.to_string(),
+        rates: ModelRates::from_input_output(input, output),
+    };
+    vec![
+        pattern("opus-4-5", 5.0, 25.0),
+        pattern("opus-4-1", 15.0, 75.0),
+        // claude-opus-4-20250514
+        pattern("opus-4-2025", 15.0, 75.0),
+        pattern("3-opus", 15.0, 75.0),
+        pattern("3-5-haiku", 0.8, 4.0),
+        pattern("3-haiku", 0.25, 1.25),
+    ]
+}
+
+/// List prices per model family before provider adjustments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseRates {
+    pub opus: ModelRates,
+    pub sonnet: ModelRates,
+    pub haiku: ModelRates,
+}
+
+impl Default for BaseRates {
+    fn default() -> Self {
+        Self {
+            // Claude Opus 4.5: $5/$25 per MTok
+            opus: ModelRates::from_input_output(5.0, 25.0),
+            // Sonnet 3.7/4/4.5: $3/$15 per MTok
+            sonnet: ModelRates::from_input_output(3.0, 15.0),
+            // Claude Haiku 4.5: $1/$5 per MTok
+            haiku: ModelRates::from_input_output(1.0, 5.0),
+        }
+    }
+}
+
+/// A pricing provider (Anthropic API, Bedrock, Vertex, or user-defined)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub base_rates: BaseRates,
+    /// Checked before the family rates; the first matching pattern wins
+    #[serde(default = "default_model_rates")]
+    pub model_rates: Vec<ModelRatePattern>,
+    /// Regional pricing multiplier (1.0 = list price)
+    #[serde(default = "default_multiplier")]
+    pub regional_multiplier: f64,
+    /// Provider premium on top of list price, in percent
+    #[serde(default)]
+    pub premium_percent: f64,
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+fn default_multiplier() -> f64 {
+    1.0
+}
+
+impl PricingProfile {
+    fn factor(&self) -> f64 {
+        self.regional_multiplier * (1.0 + self.premium_percent / 100.0)
+    }
+
+    /// Effective rates for a model name. Built-in profiles use fetched list prices
+    /// when remote pricing is on; otherwise rates are matched by pattern, then family.
+    pub fn rates_for(&self, model: &str) -> ModelRates {
+        let model_lc = model.to_lowercase();
+        let matched = self
+            .model_rates
+            .iter()
+            .find(|p| model_lc.contains(&p.pattern.to_lowercase()));
+        let remote = self.builtin.then(|| remote_rates(model)).flatten();
+        let base = if let Some(remote) = remote {
+            remote
+        } else if let Some(matched) = matched {
+            matched.rates
+        } else if model_lc.contains("opus") {
+            self.base_rates.opus
+        } else if model_lc.contains("haiku") {
+            self.base_rates.haiku
+        } else {
+            // Default to Sonnet pricing
+            self.base_rates.sonnet
+        };
+        base.scaled(self.factor())
+    }
+}
+
+fn builtin_profiles() -> Vec<PricingProfile> {
+    let builtin = |id: &str, name: &str, premium_percent: f64| PricingProfile {
+        id: id.to_string(),
+        name: name.to_string(),
+        base_rates: BaseRates::default(),
+        model_rates: default_model_rates(),
+        regional_multiplier: 1.0,
+        premium_percent,
+        builtin: true,
+    };
+    vec![
+        builtin("anthropic", "Anthropic API", 0.0),
+        builtin("aws-bedrock", "AWS Bedrock", 0.0),
+        builtin("google-vertex", "Google Vertex AI", 10.0),
+    ]
+}
+
+fn load_custom_profiles(app: &AppHandle) -> Vec<PricingProfile> {
+    load_setting(app, PRICING_PROFILES_KEY)
+}
+
+/// Built-in profiles, with user-defined profiles overriding by id
+pub fn load_profiles(app: &AppHandle) -> Vec<PricingProfile> {
+    let mut profiles = builtin_profiles();
+    for custom in load_custom_profiles(app) {
+        match profiles.iter_mut().find(|p| p.id == custom.id) {
+            Some(existing) => *existing = custom,
+            None => profiles.push(custom),
+        }
+    }
+    profiles
+}
+
+/// Look up a profile by id, falling back to Anthropic list pricing
+pub fn resolve_profile(app: &AppHandle, id: &str) -> PricingProfile {
+    let mut profiles = load_profiles(app);
+    let index = profiles
+        .iter()
+        .position(|p| p.id == id)
+        .or_else(|| profiles.iter().position(|p| p.id == DEFAULT_PROFILE_ID))
+        .unwrap_or(0);
+    profiles.swap_remove(index)
+}
+
+#[tauri::command]
+pub async fn get_pricing_profiles(app: AppHandle) -> Result<Vec<PricingProfile>, String> {
+    Ok(load_profiles(&app))
+}
+
+#[tauri::command]
+pub async fn save_pricing_profile(app: AppHandle, profile: PricingProfile) -> Result<(), String> {
+    if profile.id.trim().is_empty() || profile.name.trim().is_empty() {
+        return Err("Pricing profile id and name are required".to_string());
+    }
+    if profile.regional_multiplier <= 0.0 || profile.premium_percent < -100.0 {
+        return Err("Regional multiplier must be positive and premium above -100%".to_string());
+    }
+    if profile
+        .model_rates
+        .iter()
+        .any(|p| p.pattern.trim().is_empty())
+    {
+        return Err("Model rate patterns must not be empty".to_string());
+    }
+
+    let mut profile = profile;
+    profile.builtin = false;
+    let mut custom = load_custom_profiles(&app);
+    match custom.iter_mut().find(|p| p.id == profile.id) {
+        Some(existing) => *existing = profile,
+        None => custom.push(profile),
+    }
+    save_setting(&app, PRICING_PROFILES_KEY, &custom)
+}
+
+/// Remove a user-defined profile (a customized built-in reverts to its defaults)
+#[tauri::command]
+pub async fn delete_pricing_profile(app: AppHandle, profile_id: String) -> Result<(), String> {
+    let mut custom = load_custom_profiles(&app);
+    custom.retain(|p| p.id != profile_id);
+    save_setting(&app, PRICING_PROFILES_KEY, &custom)
+}
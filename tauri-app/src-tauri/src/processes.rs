@@ -0,0 +1,227 @@
+// tauri-app/src-tauri/src/processes.rs
+
+use crate::history_index::{get_history_path, with_history_index};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// How often the background sampler records CPU and memory
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samples kept per process (one hour at the default interval)
+const MAX_SAMPLES: usize = 720;
+
+/// A running Claude Code CLI process
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProcess {
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+    pub working_directory: Option<String>,
+    /// Process start time in milliseconds since the epoch
+    pub started_at: i64,
+    pub uptime_seconds: u64,
+    pub session_id: Option<String>,
+    pub project: Option<String>,
+    /// CPU usage since the previous sample (100 = one full core)
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSample {
+    pub timestamp: i64,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessResourceHistory {
+    pub pid: u32,
+    pub name: String,
+    pub session_id: Option<String>,
+    pub samples: Vec<ResourceSample>,
+    pub peak_memory_bytes: u64,
+}
+
+/// Keeps one `System` alive between scans so CPU usage can be computed as a
+/// delta, along with the recent samples of every Claude process
+struct ProcessSampler {
+    system: System,
+    processes: Vec<ClaudeProcess>,
+    samples: HashMap<u32, VecDeque<ResourceSample>>,
+}
+
+static SAMPLER: Mutex<Option<ProcessSampler>> = Mutex::new(None);
+
+/// Claude Code runs either as a native `claude` binary or as node executing the
+/// @anthropic-ai/claude-code CLI script
+fn is_claude_process(name: &str, cmd: &[OsString]) -> bool {
+    let name = name.to_lowercase();
+    if name == "claude" || name == "claude.exe" {
+        return true;
+    }
+    if !name.starts_with("node") {
+        return false;
+    }
+    cmd.iter().skip(1).take(2).any(|arg| {
+        let arg = arg.to_string_lossy();
+        arg.contains("claude-code")
+            || Path::new(arg.as_ref())
+                .file_name()
+                .is_some_and(|f| f == "claude")
+    })
+}
+
+/// Session id passed explicitly via `--resume <id>` / `-r <id>`
+fn resumed_session_id(cmd: &[OsString]) -> Option<String> {
+    cmd.windows(2)
+        .find(|pair| pair[0] == "--resume" || pair[0] == "-r")
+        .map(|pair| pair[1].to_string_lossy().to_string())
+}
+
+fn refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::nothing()
+        .with_cmd(UpdateKind::Always)
+        .with_cwd(UpdateKind::Always)
+        .with_cpu()
+        .with_memory()
+}
+
+/// Refresh the process list and record a resource sample for each Claude process
+pub fn scan_claude_processes() -> Vec<ClaudeProcess> {
+    let Ok(mut guard) = SAMPLER.lock() else {
+        return Vec::new();
+    };
+    let sampler = guard.get_or_insert_with(|| ProcessSampler {
+        system: System::new(),
+        processes: Vec::new(),
+        samples: HashMap::new(),
+    });
+    sampler
+        .system
+        .refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind());
+
+    let mut processes: Vec<ClaudeProcess> = sampler
+        .system
+        .processes()
+        .values()
+        // Skip threads reported as processes on Linux
+        .filter(|p| p.thread_kind().is_none())
+        .filter(|p| is_claude_process(&p.name().to_string_lossy(), p.cmd()))
+        .map(|p| ClaudeProcess {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string_lossy().to_string(),
+            command: p
+                .cmd()
+                .iter()
+                .map(|a| a.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" "),
+            working_directory: p.cwd().map(|d| d.to_string_lossy().to_string()),
+            started_at: p.start_time() as i64 * 1000,
+            uptime_seconds: p.run_time(),
+            session_id: resumed_session_id(p.cmd()),
+            project: p
+                .cwd()
+                .and_then(|d| d.file_name())
+                .map(|n| n.to_string_lossy().to_string()),
+            cpu_percent: p.cpu_usage(),
+            memory_bytes: p.memory(),
+        })
+        .collect();
+
+    correlate_sessions(&mut processes);
+    processes.sort_by_key(|p| p.started_at);
+
+    let now = chrono::Utc::now().timestamp_millis();
+    sampler
+        .samples
+        .retain(|pid, _| processes.iter().any(|p| p.pid == *pid));
+    for process in &processes {
+        let samples = sampler.samples.entry(process.pid).or_default();
+        samples.push_back(ResourceSample {
+            timestamp: now,
+            cpu_percent: process.cpu_percent,
+            memory_bytes: process.memory_bytes,
+        });
+        if samples.len() > MAX_SAMPLES {
+            samples.pop_front();
+        }
+    }
+    sampler.processes = processes.clone();
+
+    processes
+}
+
+/// Sample Claude processes every `SAMPLE_INTERVAL` for the lifetime of the app
+pub fn start_sampler() {
+    std::thread::spawn(|| loop {
+        scan_claude_processes();
+        std::thread::sleep(SAMPLE_INTERVAL);
+    });
+}
+
+/// Match processes without an explicit session id to the latest session in their
+/// working directory that had activity after the process started
+fn correlate_sessions(processes: &mut [ClaudeProcess]) {
+    let Some(history_path) = get_history_path() else {
+        return;
+    };
+
+    let _ = with_history_index(&history_path, |index| {
+        for process in processes.iter_mut().filter(|p| p.session_id.is_none()) {
+            let Some(cwd) = &process.working_directory else {
+                continue;
+            };
+            process.session_id = index
+                .latest_session_in_project(cwd, process.started_at)
+                .map(|s| s.session_id.clone());
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_claude_processes() -> Result<Vec<ClaudeProcess>, String> {
+    tauri::async_runtime::spawn_blocking(scan_claude_processes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// CPU and memory samples for running Claude processes (or a single `pid`)
+#[tauri::command]
+pub async fn get_process_resource_history(
+    pid: Option<u32>,
+) -> Result<Vec<ProcessResourceHistory>, String> {
+    let guard = SAMPLER.lock().map_err(|e| e.to_string())?;
+    let Some(sampler) = guard.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(sampler
+        .processes
+        .iter()
+        .filter(|p| pid.is_none_or(|pid| p.pid == pid))
+        .map(|p| {
+            let samples: Vec<ResourceSample> = sampler
+                .samples
+                .get(&p.pid)
+                .map(|s| s.iter().cloned().collect())
+                .unwrap_or_default();
+            ProcessResourceHistory {
+                pid: p.pid,
+                name: p.name.clone(),
+                session_id: p.session_id.clone(),
+                peak_memory_bytes: samples.iter().map(|s| s.memory_bytes).max().unwrap_or(0),
+                samples,
+            }
+        })
+        .collect())
+}
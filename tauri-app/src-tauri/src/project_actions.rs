@@ -0,0 +1,258 @@
+// tauri-app/src-tauri/src/project_actions.rs
+
+use crate::data_sources::enabled_sources;
+use crate::history_index::with_history_index;
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_shell::ShellExt;
+
+const EDITOR_KEY: &str = "editor";
+const TERMINAL_KEY: &str = "terminal";
+
+/// Placeholder in `TerminalSettings::args` replaced by the shell command to run
+const COMMAND_PLACEHOLDER: &str = "{command}";
+/// Placeholder in `TerminalSettings::args` replaced by the project directory
+const DIR_PLACEHOLDER: &str = "{dir}";
+
+/// Editor launched by `open_project_in_editor`; the project path is appended to `args`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditorSettings {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            command: "code".to_string(),
+            args: Vec::new(),
+        }
+    }
+}
+
+/// Terminal used by `resume_session`. Each arg may contain `{command}`, which is
+/// replaced with the shell command line to run, and `{dir}`, the project directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSettings {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl Default for TerminalSettings {
+    #[cfg(target_os = "macos")]
+    fn default() -> Self {
+        Self {
+            command: "osascript".to_string(),
+            args: vec![
+                "-e".to_string(),
+                "tell application \"Terminal\" to do script \"{command}\"".to_string(),
+                "-e".to_string(),
+                "tell application \"Terminal\" to activate".to_string(),
+            ],
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn default() -> Self {
+        Self {
+            command: "cmd".to_string(),
+            // `start` takes its first quoted argument as the window title, so an
+            // explicit empty one keeps a quoted directory from being taken for it
+            args: [
+                "/C",
+                "start",
+                "",
+                "/D",
+                DIR_PLACEHOLDER,
+                "cmd",
+                "/K",
+                COMMAND_PLACEHOLDER,
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn default() -> Self {
+        Self {
+            command: "x-terminal-emulator".to_string(),
+            args: ["-e", "sh", "-c", "{command}; exec \"${SHELL:-sh}\""]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+/// Quote a value for a POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Only open directories that exist and appear as a project in the history files,
+/// so the frontend can't be used to launch arbitrary paths
+fn validate_project_path(app: &AppHandle, project_path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(project_path);
+    if !path.is_absolute() {
+        return Err(format!("Project path must be absolute: {}", project_path));
+    }
+    let canonical = path
+        .canonicalize()
+        .map_err(|_| format!("Project directory not found: {}", project_path))?;
+    if !canonical.is_dir() {
+        return Err(format!("Not a directory: {}", project_path));
+    }
+
+    let known = enabled_sources(app).iter().any(|source| {
+        with_history_index(&source.history_path(), |index| {
+            index
+                .sessions
+                .iter()
+                .any(|s| s.project_path == project_path)
+        })
+        .unwrap_or(false)
+    });
+    if !known {
+        return Err(format!("Unknown project: {}", project_path));
+    }
+
+    Ok(canonical)
+}
+
+#[tauri::command]
+pub async fn get_editor_settings(app: AppHandle) -> Result<EditorSettings, String> {
+    Ok(load_setting(&app, EDITOR_KEY))
+}
+
+#[tauri::command]
+pub async fn set_editor_settings(app: AppHandle, settings: EditorSettings) -> Result<(), String> {
+    if settings.command.trim().is_empty() {
+        return Err("Editor command cannot be empty".to_string());
+    }
+    save_setting(&app, EDITOR_KEY, &settings)
+}
+
+#[tauri::command]
+pub async fn open_project_folder(app: AppHandle, project_path: String) -> Result<(), String> {
+    let path = validate_project_path(&app, &project_path)?;
+    app.opener()
+        .open_path(path.to_string_lossy().to_string(), None::<&str>)
+        .map_err(|e| format!("Failed to open folder: {}", e))
+}
+
+#[tauri::command]
+pub async fn open_project_in_editor(app: AppHandle, project_path: String) -> Result<(), String> {
+    let path = validate_project_path(&app, &project_path)?;
+    let editor: EditorSettings = load_setting(&app, EDITOR_KEY);
+
+    app.shell()
+        .command(&editor.command)
+        .args(&editor.args)
+        .arg(&path)
+        .current_dir(&path)
+        .spawn()
+        .map_err(|e| format!("Failed to launch {}: {}", editor.command, e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_terminal_settings(app: AppHandle) -> Result<TerminalSettings, String> {
+    Ok(load_setting(&app, TERMINAL_KEY))
+}
+
+#[tauri::command]
+pub async fn set_terminal_settings(
+    app: AppHandle,
+    settings: TerminalSettings,
+) -> Result<(), String> {
+    if settings.command.trim().is_empty() {
+        return Err("Terminal command cannot be empty".to_string());
+    }
+    if !settings
+        .args
+        .iter()
+        .any(|a| a.contains(COMMAND_PLACEHOLDER))
+    {
+        return Err(format!(
+            "Terminal args must include {}",
+            COMMAND_PLACEHOLDER
+        ));
+    }
+    save_setting(&app, TERMINAL_KEY, &settings)
+}
+
+/// Launch `claude --resume <session_id>` in the configured terminal, from the
+/// session's project directory
+#[tauri::command]
+pub async fn resume_session(app: AppHandle, session_id: String) -> Result<(), String> {
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+
+    let project_path = enabled_sources(&app)
+        .iter()
+        .find_map(|source| {
+            with_history_index(&source.history_path(), |index| {
+                index
+                    .sessions
+                    .iter()
+                    .find(|s| s.session_id == session_id)
+                    .map(|s| s.project_path.clone())
+            })
+            .ok()
+            .flatten()
+        })
+        .ok_or(format!("Session not found: {}", session_id))?;
+    let project_dir = validate_project_path(&app, &project_path)?;
+
+    // cmd.exe can't reliably quote a path inside a /K command line, so on Windows
+    // the directory only reaches the terminal through {dir} and the working directory
+    let command_line = if cfg!(target_os = "windows") {
+        format!("claude --resume {}", session_id)
+    } else {
+        format!(
+            "cd {} && claude --resume {}",
+            shell_quote(&project_dir.to_string_lossy()),
+            session_id
+        )
+    };
+
+    let terminal: TerminalSettings = load_setting(&app, TERMINAL_KEY);
+    // AppleScript string literals need their own escaping
+    let escape = |value: &str| {
+        if terminal.command == "osascript" {
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        } else {
+            value.to_string()
+        }
+    };
+    // The path as recorded in history; the canonical one is \\?\-prefixed on Windows
+    let (command_line, dir) = (escape(&command_line), escape(&project_path));
+    let args: Vec<String> = terminal
+        .args
+        .iter()
+        .map(|arg| {
+            arg.replace(COMMAND_PLACEHOLDER, &command_line)
+                .replace(DIR_PLACEHOLDER, &dir)
+        })
+        .collect();
+
+    app.shell()
+        .command(&terminal.command)
+        .args(&args)
+        .current_dir(&project_dir)
+        .spawn()
+        .map_err(|e| format!("Failed to launch {}: {}", terminal.command, e))?;
+    Ok(())
+}
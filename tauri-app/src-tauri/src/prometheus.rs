@@ -1,7 +1,12 @@
-use reqwest::Client;
-use serde::Deserialize;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, Identity, RequestBuilder};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Proxies commonly reject URLs much longer than this with a 414
+const MAX_GET_URL_LENGTH: usize = 4000;
 
 #[derive(Debug, thiserror::Error)]
 pub enum PrometheusError {
@@ -9,6 +14,198 @@ pub enum PrometheusError {
     Request(#[from] reqwest::Error),
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+    #[error("Invalid TLS configuration: {0}")]
+    Tls(String),
+    #[error("Invalid header: {0}")]
+    Header(String),
+}
+
+/// TLS settings for a self-hosted Prometheus behind an internal CA or mTLS
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TlsOptions {
+    /// PEM root certificate trusted in addition to the system roots
+    pub ca_cert_path: Option<String>,
+    /// PEM client certificate and PKCS#8 key, for mutual TLS
+    pub client_cert_path: Option<String>,
+    pub client_key_path: Option<String>,
+    /// Accept any server certificate. Only for testing.
+    pub insecure_skip_verify: bool,
+}
+
+/// Exponential backoff for timeouts, connection failures and 5xx responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryPolicy {
+    /// Including the first try; 1 disables retries
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Upper bound on one query including every retry and backoff
+    pub max_total_seconds: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 2000,
+            max_total_seconds: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionType {
+    #[default]
+    Prometheus,
+    /// Grafana Cloud's hosted Prometheus (`.../api/prom`) or a Grafana
+    /// datasource proxy URL (`.../api/datasources/proxy/uid/<uid>`)
+    GrafanaCloud,
+}
+
+/// How clients connect to Prometheus, shared by every query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConnectionOptions {
+    pub connection_type: ConnectionType,
+    /// Grafana Cloud instance id. With it the API key is sent as basic auth,
+    /// without it as a bearer token (e.g. a Grafana service account token).
+    pub username: Option<String>,
+    pub api_key: Option<String>,
+    pub tls: TlsOptions,
+    /// Whole-request timeout, per attempt
+    pub timeout_seconds: u64,
+    pub connect_timeout_seconds: u64,
+    pub retry: RetryPolicy,
+    /// Sent as `X-Scope-OrgID` for multi-tenant Mimir, Cortex or Thanos
+    pub tenant_id: Option<String>,
+    /// Sent with every request, e.g. a proxy's auth header
+    pub headers: HashMap<String, String>,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            connection_type: ConnectionType::Prometheus,
+            username: None,
+            api_key: None,
+            tls: TlsOptions::default(),
+            // Long range queries on a busy server can legitimately take a while
+            timeout_seconds: 30,
+            connect_timeout_seconds: 3,
+            retry: RetryPolicy::default(),
+            tenant_id: None,
+            headers: HashMap::new(),
+        }
+    }
+}
+
+impl ConnectionOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.timeout_seconds == 0 || self.connect_timeout_seconds == 0 {
+            return Err("Timeouts must be at least one second".to_string());
+        }
+        if self.retry.max_attempts == 0 {
+            return Err("Max attempts must be at least 1".to_string());
+        }
+        if self.retry.max_total_seconds == 0 {
+            return Err("Total retry time must be at least one second".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Options applied by `PrometheusClient::new`, loaded from settings at startup
+static CONNECTION: Mutex<Option<ConnectionOptions>> = Mutex::new(None);
+
+pub fn set_default_connection(options: ConnectionOptions) {
+    if let Ok(mut guard) = CONNECTION.lock() {
+        *guard = Some(options);
+    }
+}
+
+pub fn default_connection() -> ConnectionOptions {
+    CONNECTION
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+fn read_pem(path: &Option<String>) -> Result<Option<Vec<u8>>, PrometheusError> {
+    match path.as_deref().filter(|p| !p.trim().is_empty()) {
+        Some(path) => std::fs::read(path)
+            .map(Some)
+            .map_err(|e| PrometheusError::Tls(format!("Failed to read {}: {}", path, e))),
+        None => Ok(None),
+    }
+}
+
+fn default_headers(options: &ConnectionOptions) -> Result<HeaderMap, PrometheusError> {
+    let tenant = options
+        .tenant_id
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| ("X-Scope-OrgID", t));
+    let headers = options
+        .headers
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .chain(tenant);
+
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| PrometheusError::Header(name.to_string()))?;
+        let value = HeaderValue::from_str(value.trim())
+            .map_err(|_| PrometheusError::Header(format!("{}: {}", name, value)))?;
+        map.insert(name, value);
+    }
+    Ok(map)
+}
+
+fn build_client(options: &ConnectionOptions) -> Result<Client, PrometheusError> {
+    let tls = &options.tls;
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(options.timeout_seconds))
+        .connect_timeout(Duration::from_secs(options.connect_timeout_seconds))
+        .default_headers(default_headers(options)?)
+        .danger_accept_invalid_certs(tls.insecure_skip_verify);
+
+    if let Some(pem) = read_pem(&tls.ca_cert_path)? {
+        builder = builder.add_root_certificate(Certificate::from_pem(&pem)?);
+    }
+    match (
+        read_pem(&tls.client_cert_path)?,
+        read_pem(&tls.client_key_path)?,
+    ) {
+        (Some(cert), Some(key)) => {
+            builder = builder.identity(Identity::from_pkcs8_pem(&cert, &key)?);
+        }
+        (None, None) => {}
+        _ => {
+            return Err(PrometheusError::Tls(
+                "Client certificate and key must be set together".to_string(),
+            ))
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Grafana Cloud's hosted Prometheus serves the query API under /api/prom
+fn resolve_base_url(base_url: &str, connection_type: ConnectionType) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let is_proxy = base_url.contains("/api/datasources/proxy/");
+    match connection_type {
+        ConnectionType::GrafanaCloud if !is_proxy && !base_url.ends_with("/api/prom") => {
+            format!("{}/api/prom", base_url)
+        }
+        _ => base_url.to_string(),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,41 +229,190 @@ pub struct QueryResult {
     pub values: Option<Vec<(f64, String)>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct MetadataEntry {
+    #[serde(rename = "type")]
+    metric_type: String,
+    #[serde(default)]
+    help: String,
+    #[serde(default)]
+    unit: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataResponse {
+    status: String,
+    #[serde(default)]
+    data: HashMap<String, Vec<MetadataEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricMetadata {
+    pub name: String,
+    /// "counter", "gauge", "histogram", ... or empty when Prometheus has no metadata
+    pub metric_type: String,
+    pub help: String,
+    pub unit: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TargetsData {
+    #[serde(default)]
+    active_targets: Vec<ScrapeTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TargetsResponse {
+    status: String,
+    data: TargetsData,
+}
+
+/// An active scrape target from /api/v1/targets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrapeTarget {
+    pub scrape_pool: String,
+    pub scrape_url: String,
+    /// "up", "down" or "unknown"
+    pub health: String,
+    /// RFC 3339 timestamp of the last scrape
+    pub last_scrape: Option<String>,
+    #[serde(default)]
+    pub last_scrape_duration: f64,
+    #[serde(default)]
+    pub last_error: String,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesData {
+    #[serde(default)]
+    groups: Vec<RuleGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesResponse {
+    status: String,
+    data: RulesData,
+}
+
+/// A recording or alerting rule with its last evaluation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub name: String,
+    pub query: String,
+    /// "recording" or "alerting"
+    #[serde(rename(deserialize = "type"))]
+    pub rule_type: String,
+    /// "ok", "err" or "unknown"
+    pub health: String,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    pub last_evaluation: Option<String>,
+    #[serde(default)]
+    pub evaluation_time: f64,
+    /// Alerting rules only: "inactive", "pending" or "firing"
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleGroup {
+    pub name: String,
+    pub file: String,
+    #[serde(default)]
+    pub interval: f64,
+    pub last_evaluation: Option<String>,
+    #[serde(default)]
+    pub evaluation_time: f64,
+    pub rules: Vec<Rule>,
+}
+
 pub struct PrometheusClient {
     client: Client,
     base_url: String,
+    lookback_delta: Option<String>,
+    options: ConnectionOptions,
 }
 
 impl PrometheusClient {
-    pub fn new(base_url: &str) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(5))
-            .connect_timeout(Duration::from_secs(3))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+    /// Client with the saved connection options. Fails if they don't build
+    /// (e.g. an unreadable certificate or an invalid header).
+    pub fn new(base_url: &str) -> Result<Self, PrometheusError> {
+        Self::with_options(base_url, &default_connection())
+    }
 
-        Self {
-            client,
-            base_url: base_url.trim_end_matches('/').to_string(),
+    /// Client with explicit connection options instead of the saved ones
+    pub fn with_options(
+        base_url: &str,
+        options: &ConnectionOptions,
+    ) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            client: build_client(options)?,
+            base_url: resolve_base_url(base_url, options.connection_type),
+            lookback_delta: None,
+            options: options.clone(),
+        })
+    }
+
+    /// GET request with the configured API key attached
+    fn get(&self, url: &str) -> RequestBuilder {
+        self.authorize(self.client.get(url))
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        let username = self.options.username.as_deref().filter(|u| !u.is_empty());
+        match (username, self.options.api_key.as_deref()) {
+            (_, None | Some("")) => request,
+            (Some(username), Some(key)) => request.basic_auth(username, Some(key)),
+            (None, Some(key)) => request.bearer_auth(key),
         }
     }
 
-    pub async fn query(&self, query: &str) -> Result<Vec<QueryResult>, PrometheusError> {
-        let url = format!("{}/api/v1/query", self.base_url);
-        let response: QueryResponse = self
-            .client
-            .get(&url)
-            .query(&[("query", query)])
-            .send()
-            .await?
-            .json()
-            .await?;
+    /// Override how far back Prometheus looks for the latest sample of a series
+    pub fn with_lookback_delta(mut self, lookback_delta: Option<String>) -> Self {
+        self.lookback_delta = lookback_delta;
+        self
+    }
 
-        if response.status != "success" {
-            return Err(PrometheusError::InvalidResponse(response.status));
+    /// Query API request, POSTed as a form when the GET URL would be too long
+    fn query_request(&self, url: &str, mut params: Vec<(&str, String)>) -> RequestBuilder {
+        if let Some(lookback_delta) = &self.lookback_delta {
+            params.push(("lookback_delta", lookback_delta.clone()));
+        }
+        let get_length = reqwest::Url::parse_with_params(url, &params)
+            .map(|u| u.as_str().len())
+            .unwrap_or(usize::MAX);
+        if get_length > MAX_GET_URL_LENGTH {
+            self.authorize(self.client.post(url)).form(&params)
+        } else {
+            self.get(url).query(&params)
         }
+    }
 
-        Ok(response.data.result)
+    pub async fn query(&self, query: &str) -> Result<Vec<QueryResult>, PrometheusError> {
+        self.query_at(query, None).await
+    }
+
+    /// Instant query evaluated at `time` (unix seconds) instead of now
+    pub async fn query_at(
+        &self,
+        query: &str,
+        time: Option<i64>,
+    ) -> Result<Vec<QueryResult>, PrometheusError> {
+        let url = format!("{}/api/v1/query", self.base_url);
+        let mut params = vec![("query", query.to_string())];
+        if let Some(time) = time {
+            params.push(("time", time.to_string()));
+        }
+        self.send_query(self.query_request(&url, params)).await
     }
 
     pub async fn query_range(
@@ -77,37 +423,70 @@ impl PrometheusClient {
         step: &str,
     ) -> Result<Vec<QueryResult>, PrometheusError> {
         let url = format!("{}/api/v1/query_range", self.base_url);
-        let response: QueryResponse = self
-            .client
-            .get(&url)
-            .query(&[
-                ("query", query),
-                ("start", &start.to_string()),
-                ("end", &end.to_string()),
-                ("step", step),
-            ])
-            .send()
-            .await?
-            .json()
-            .await?;
+        let params = vec![
+            ("query", query.to_string()),
+            ("start", start.to_string()),
+            ("end", end.to_string()),
+            ("step", step.to_string()),
+        ];
+        self.send_query(self.query_request(&url, params)).await
+    }
 
-        if response.status != "success" {
-            return Err(PrometheusError::InvalidResponse(response.status));
-        }
+    /// Send a query, retrying transient failures with exponential backoff
+    async fn send_query(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Vec<QueryResult>, PrometheusError> {
+        let retry = &self.options.retry;
+        let deadline = Instant::now() + Duration::from_secs(retry.max_total_seconds);
+        let timeout = Duration::from_secs(self.options.timeout_seconds);
+        let mut backoff = Duration::from_millis(retry.initial_backoff_ms);
+        let mut attempt = 1;
+
+        loop {
+            let attempt_request = request.try_clone().ok_or_else(|| {
+                PrometheusError::InvalidResponse("Request cannot be retried".to_string())
+            })?;
+            // The last attempt only gets whatever is left of the total budget
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let error = match attempt_request.timeout(timeout.min(remaining)).send().await {
+                Ok(response) if response.status().is_server_error() => {
+                    PrometheusError::InvalidResponse(format!("HTTP {}", response.status()))
+                }
+                Ok(response) => {
+                    let response: QueryResponse = response.json().await?;
+                    if response.status != "success" {
+                        return Err(PrometheusError::InvalidResponse(response.status));
+                    }
+                    return Ok(response.data.result);
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => e.into(),
+                Err(e) => return Err(e.into()),
+            };
 
-        Ok(response.data.result)
+            if attempt >= retry.max_attempts || Instant::now() + backoff >= deadline {
+                return Err(error);
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_millis(retry.max_backoff_ms));
+            attempt += 1;
+        }
     }
 
     pub async fn test_connection(&self) -> Result<bool, PrometheusError> {
+        // Hosted Prometheus doesn't expose /-/healthy, so run a trivial query instead
+        if self.options.connection_type == ConnectionType::GrafanaCloud {
+            return Ok(self.query("vector(1)").await.is_ok());
+        }
         let url = format!("{}/-/healthy", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.get(&url).send().await?;
         Ok(response.status().is_success())
     }
 
     pub async fn discover_metrics(&self) -> Result<Vec<String>, PrometheusError> {
         // Query for all claude_code_ metrics
         let url = format!("{}/api/v1/label/__name__/values", self.base_url);
-        let response: serde_json::Value = self.client.get(&url).send().await?.json().await?;
+        let response: serde_json::Value = self.get(&url).send().await?.json().await?;
 
         if response["status"] != "success" {
             return Err(PrometheusError::InvalidResponse(
@@ -128,4 +507,111 @@ impl PrometheusClient {
 
         Ok(metrics)
     }
+
+    /// Values of `label` on series matching `selector` between `start` and `end`
+    pub async fn label_values(
+        &self,
+        label: &str,
+        selector: &str,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<String>, PrometheusError> {
+        let url = format!("{}/api/v1/label/{}/values", self.base_url, label);
+        let params = [
+            ("match[]", selector.to_string()),
+            ("start", start.to_string()),
+            ("end", end.to_string()),
+        ];
+        let response: serde_json::Value =
+            self.get(&url).query(&params).send().await?.json().await?;
+
+        if response["status"] != "success" {
+            return Err(PrometheusError::InvalidResponse(
+                response["status"].to_string(),
+            ));
+        }
+
+        let mut values: Vec<String> = response["data"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        values.sort();
+        Ok(values)
+    }
+
+    /// Type, help text and unit for each claude_code_ series from /api/v1/metadata
+    pub async fn discover_metric_metadata(&self) -> Result<Vec<MetricMetadata>, PrometheusError> {
+        let url = format!("{}/api/v1/metadata", self.base_url);
+        let (names, response) = tokio::join!(self.discover_metrics(), async {
+            self.get(&url)
+                .send()
+                .await?
+                .json::<MetadataResponse>()
+                .await
+        });
+        let response = response?;
+        if response.status != "success" {
+            return Err(PrometheusError::InvalidResponse(response.status));
+        }
+
+        // Metadata is keyed by metric family, which drops the counter's _total suffix
+        Ok(names?
+            .into_iter()
+            .map(|name| {
+                let entry = response
+                    .data
+                    .get(&name)
+                    .or_else(|| response.data.get(name.strip_suffix("_total")?))
+                    .and_then(|entries| entries.first());
+                MetricMetadata {
+                    metric_type: entry.map(|e| e.metric_type.clone()).unwrap_or_default(),
+                    help: entry.map(|e| e.help.clone()).unwrap_or_default(),
+                    unit: entry.map(|e| e.unit.clone()).unwrap_or_default(),
+                    name,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn scrape_targets(&self) -> Result<Vec<ScrapeTarget>, PrometheusError> {
+        let url = format!("{}/api/v1/targets", self.base_url);
+        let response: TargetsResponse = self
+            .get(&url)
+            .query(&[("state", "active")])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if response.status != "success" {
+            return Err(PrometheusError::InvalidResponse(response.status));
+        }
+
+        Ok(response.data.active_targets)
+    }
+
+    /// Rule groups from /api/v1/rules; `rule_type` is "record" or "alert" to filter
+    pub async fn rules(&self, rule_type: Option<&str>) -> Result<Vec<RuleGroup>, PrometheusError> {
+        let url = format!("{}/api/v1/rules", self.base_url);
+        let type_param: Vec<(&str, &str)> =
+            rule_type.map(|t| vec![("type", t)]).unwrap_or_default();
+        let response: RulesResponse = self
+            .get(&url)
+            .query(&type_param)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if response.status != "success" {
+            return Err(PrometheusError::InvalidResponse(response.status));
+        }
+
+        Ok(response.data.groups)
+    }
 }
@@ -0,0 +1,30 @@
+// tauri-app/src-tauri/src/prometheus_connection.rs
+
+use crate::prometheus::{set_default_connection, ConnectionOptions, PrometheusClient};
+use crate::settings::{load_setting, save_setting};
+use tauri::AppHandle;
+
+const CONNECTION_KEY: &str = "prometheusConnection";
+
+/// Apply the saved connection options to every Prometheus client
+pub fn load_prometheus_connection(app: &AppHandle) {
+    set_default_connection(load_setting(app, CONNECTION_KEY));
+}
+
+#[tauri::command]
+pub async fn get_prometheus_connection(app: AppHandle) -> Result<ConnectionOptions, String> {
+    Ok(load_setting(&app, CONNECTION_KEY))
+}
+
+#[tauri::command]
+pub async fn set_prometheus_connection(
+    app: AppHandle,
+    options: ConnectionOptions,
+) -> Result<(), String> {
+    options.validate()?;
+    // Fail on unreadable certificates now rather than on the next query
+    PrometheusClient::with_options("", &options).map_err(|e| e.to_string())?;
+    save_setting(&app, CONNECTION_KEY, &options)?;
+    set_default_connection(options);
+    Ok(())
+}
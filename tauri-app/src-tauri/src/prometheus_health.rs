@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::AppError;
+use crate::metrics::lttb;
 use crate::prometheus::PrometheusClient;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,11 +99,24 @@ impl Default for PrometheusHealthMetrics {
     }
 }
 
+impl PrometheusHealthMetrics {
+    /// Cap each sparkline at `max_points`
+    pub fn downsample(&mut self, max_points: usize) {
+        for points in [
+            &mut self.storage_over_time,
+            &mut self.memory_over_time,
+            &mut self.samples_rate_over_time,
+        ] {
+            *points = lttb(points, max_points, |p| (p.timestamp, p.value));
+        }
+    }
+}
+
 pub async fn fetch_prometheus_health(
     client: &PrometheusClient,
     start_time: i64,
     end_time: i64,
-) -> Result<PrometheusHealthMetrics, String> {
+) -> Result<PrometheusHealthMetrics, AppError> {
     let mut metrics = PrometheusHealthMetrics::default();
 
     // Check if Prometheus is ready
@@ -0,0 +1,164 @@
+// tauri-app/src-tauri/src/query_tuning.rs
+
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+const QUERY_TUNING_KEY: &str = "queryTuning";
+
+/// Window overrides for one metric (unset fields fall back to the defaults)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricWindow {
+    /// Range selectors are never narrower than this, e.g. "5m" with a 1m scrape interval
+    pub min_window: Option<String>,
+    /// Replaces the step-derived window in chart queries
+    pub rate_window: Option<String>,
+}
+
+/// How range selectors are sized when building PromQL
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTuning {
+    /// Sent to Prometheus as `lookback_delta`; None keeps the server default (5m)
+    pub lookback_delta: Option<String>,
+    pub default: MetricWindow,
+    /// Keyed by metric name, e.g. "claude_code_cost_usage_USD_total"
+    pub metrics: HashMap<String, MetricWindow>,
+}
+
+/// Parse a PromQL duration such as "90s", "1h30m" or "7d" into seconds
+pub fn parse_duration(duration: &str) -> Option<i64> {
+    let mut total = 0;
+    let mut number = String::new();
+    let mut chars = duration.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+        let unit = match c {
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                total += value / 1000;
+                continue;
+            }
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 24 * 3600,
+            'w' => 7 * 24 * 3600,
+            'y' => 365 * 24 * 3600,
+            _ => return None,
+        };
+        total += value * unit;
+    }
+
+    (number.is_empty() && total > 0).then_some(total)
+}
+
+/// Format seconds as a PromQL duration, e.g. 133200 -> "1d13h"
+pub fn format_duration(seconds: i64) -> String {
+    let units = [("d", 24 * 3600), ("h", 3600), ("m", 60), ("s", 1)];
+    let mut remaining = seconds.max(1);
+    let mut formatted = String::new();
+    for (unit, size) in units {
+        if remaining >= size {
+            formatted.push_str(&format!("{}{}", remaining / size, unit));
+            remaining %= size;
+        }
+    }
+    formatted
+}
+
+/// Subquery resolution for a window: one minute, coarsened so long windows
+/// stay under ~10k evaluations
+fn subquery_resolution(window_seconds: i64) -> String {
+    let minutes = (window_seconds / 10_000 / 60 + 1).max(1);
+    format_duration(minutes * 60)
+}
+
+impl QueryTuning {
+    fn min_window(&self, metric: &str) -> Option<&str> {
+        self.metrics
+            .get(metric)
+            .and_then(|m| m.min_window.as_deref())
+            .or(self.default.min_window.as_deref())
+    }
+
+    /// Widen `window` to the metric's minimum if it's narrower
+    fn clamp(&self, metric: &str, window: &str) -> String {
+        let minimum = self
+            .min_window(metric)
+            .and_then(|min| Some((min, parse_duration(min)?)));
+        match (minimum, parse_duration(window)) {
+            (Some((min, min_secs)), Some(secs)) if secs < min_secs => min.to_string(),
+            _ => window.to_string(),
+        }
+    }
+
+    /// Range selector for a total over the whole time range
+    pub fn window(&self, metric: &str, range: &str) -> String {
+        self.clamp(metric, range)
+    }
+
+    /// `[window]` selector for a total. Custom ranges are pinned with `@ end` so they
+    /// cover the requested window rather than the one ending now, and evaluated as a
+    /// subquery so arbitrary durations (e.g. 37h) line up with the range exactly.
+    pub fn selector(&self, metric: &str, range: &str, anchor: Option<i64>) -> String {
+        let window = self.window(metric, range);
+        match anchor {
+            Some(end) => {
+                let resolution = subquery_resolution(parse_duration(&window).unwrap_or(0));
+                format!("[{}:{}] @ {}", window, resolution, end)
+            }
+            None => format!("[{}]", window),
+        }
+    }
+
+    /// Range selector for per-bucket rate()/increase() in chart queries
+    pub fn rate_window(&self, metric: &str, step_window: &str) -> String {
+        let window = self
+            .metrics
+            .get(metric)
+            .and_then(|m| m.rate_window.as_deref())
+            .or(self.default.rate_window.as_deref())
+            .unwrap_or(step_window);
+        self.clamp(metric, window)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let durations = std::iter::once(self.lookback_delta.as_deref())
+            .chain(
+                std::iter::once(&self.default)
+                    .chain(self.metrics.values())
+                    .flat_map(|m| [m.min_window.as_deref(), m.rate_window.as_deref()]),
+            )
+            .flatten();
+        for duration in durations {
+            if parse_duration(duration).is_none() {
+                return Err(format!("Invalid duration: {}", duration));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn load_query_tuning(app: &AppHandle) -> QueryTuning {
+    load_setting(app, QUERY_TUNING_KEY)
+}
+
+#[tauri::command]
+pub async fn get_query_tuning(app: AppHandle) -> Result<QueryTuning, String> {
+    Ok(load_query_tuning(&app))
+}
+
+#[tauri::command]
+pub async fn set_query_tuning(app: AppHandle, tuning: QueryTuning) -> Result<(), String> {
+    tuning.validate()?;
+    save_setting(&app, QUERY_TUNING_KEY, &tuning)
+}
@@ -0,0 +1,306 @@
+// tauri-app/src-tauri/src/reconciliation.rs
+
+use crate::events::publish;
+use crate::history_index::{get_history_path, with_history_index};
+use crate::insights::load_merged_stats_cache;
+use crate::prometheus::PrometheusClient;
+use crate::settings::{frontend_prometheus_url, load_setting, save_setting};
+use crate::timezone;
+use crate::verification::{local_days, prometheus_day_total};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const RECONCILIATION_KEY: &str = "reconciliation";
+
+/// Small absolute differences are noise (a session straddling midnight, a
+/// request still in flight), so they never count as drift
+const MIN_SESSION_DIFFERENCE: u64 = 2;
+const MIN_TOKEN_DIFFERENCE: u64 = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationSettings {
+    pub enabled: bool,
+    pub interval_minutes: u32,
+    pub lookback_days: u32,
+    pub threshold_percent: f64,
+}
+
+impl Default for ReconciliationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 60,
+            lookback_days: 7,
+            threshold_percent: 10.0,
+        }
+    }
+}
+
+/// One day's counts from each source (None = source unavailable)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftDay {
+    pub date: String,
+    pub stats_cache_sessions: Option<u64>,
+    pub history_sessions: Option<u64>,
+    pub prometheus_sessions: Option<u64>,
+    pub stats_cache_tokens: Option<u64>,
+    pub prometheus_tokens: Option<u64>,
+    pub reasons: Vec<String>,
+}
+
+/// Compact status for the warning badge
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftBadge {
+    /// "ok" or "warning"
+    pub level: String,
+    pub flagged_days: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReport {
+    pub generated_at: i64,
+    pub threshold_percent: f64,
+    pub prometheus_available: bool,
+    pub days: Vec<DriftDay>,
+    pub badge: DriftBadge,
+}
+
+static LAST_REPORT: Mutex<Option<ReconciliationReport>> = Mutex::new(None);
+
+fn diverges(a: u64, b: u64, threshold_percent: f64, min_difference: u64) -> bool {
+    let difference = a.abs_diff(b);
+    difference >= min_difference && difference as f64 / a.max(b) as f64 * 100.0 > threshold_percent
+}
+
+/// Compare every pair of available sources for one metric, describing any drift
+fn compare(
+    metric: &str,
+    values: &[(&str, Option<u64>)],
+    threshold_percent: f64,
+    min_difference: u64,
+    reasons: &mut Vec<String>,
+) {
+    let available: Vec<(&str, u64)> = values
+        .iter()
+        .filter_map(|(name, value)| value.map(|v| (*name, v)))
+        .collect();
+
+    for (i, (name_a, a)) in available.iter().enumerate() {
+        for (name_b, b) in &available[i + 1..] {
+            if diverges(*a, *b, threshold_percent, min_difference) {
+                reasons.push(format!("{}: {} {} vs {} {}", metric, name_a, a, name_b, b));
+            }
+        }
+    }
+}
+
+pub async fn reconcile(
+    app: &AppHandle,
+    settings: &ReconciliationSettings,
+    prometheus_url: &str,
+) -> ReconciliationReport {
+    // Today is excluded: the stats cache is only rewritten periodically by Claude Code
+    let today_start = timezone::start_of_day(timezone::today());
+    let start = today_start - Duration::days(settings.lookback_days as i64).num_seconds();
+    let days = local_days(start, today_start);
+
+    // Merged like the other views so extra data sources don't read as drift
+    let stats_cache = load_merged_stats_cache(app).ok();
+    let cache_sessions: HashMap<String, u64> = stats_cache
+        .as_ref()
+        .map(|cache| {
+            cache
+                .daily_activity
+                .iter()
+                .map(|a| (a.date.clone(), a.session_count as u64))
+                .collect()
+        })
+        .unwrap_or_default();
+    let cache_tokens: Option<HashMap<String, u64>> = stats_cache
+        .as_ref()
+        .and_then(|cache| cache.daily_model_tokens.as_ref())
+        .map(|daily| {
+            daily
+                .iter()
+                .map(|d| (d.date.clone(), d.tokens_by_model.values().sum()))
+                .collect()
+        });
+
+    let since = days.first().map(|(date, _, _)| *date);
+    let history_sessions: Option<HashMap<String, u64>> = get_history_path()
+        .and_then(|path| with_history_index(&path, |index| index.day_summaries(since)).ok())
+        .map(|summaries| {
+            summaries
+                .into_iter()
+                .map(|s| (s.date, s.session_count as u64))
+                .collect()
+        });
+
+    // Connection settings that don't build count as Prometheus being unavailable
+    let client = PrometheusClient::new(prometheus_url).ok();
+    let prometheus_available = match &client {
+        Some(client) => client.test_connection().await.unwrap_or(false),
+        None => false,
+    };
+
+    let mut drift_days = Vec::new();
+    for (date, day_start, day_end) in days {
+        let key = date.format("%Y-%m-%d").to_string();
+
+        let (prometheus_sessions, prometheus_tokens) =
+            match client.as_ref().filter(|_| prometheus_available) {
+                Some(client) => {
+                    let sessions = prometheus_day_total(
+                        client,
+                        "claude_code_session_count_total",
+                        day_start,
+                        day_end,
+                    )
+                    .await
+                    .ok();
+                    let tokens = prometheus_day_total(
+                        client,
+                        "claude_code_token_usage_tokens_total",
+                        day_start,
+                        day_end,
+                    )
+                    .await
+                    .ok();
+                    (
+                        sessions.map(|v| v.round() as u64),
+                        tokens.map(|v| v.round() as u64),
+                    )
+                }
+                None => (None, None),
+            };
+
+        let mut day = DriftDay {
+            stats_cache_sessions: stats_cache
+                .as_ref()
+                .map(|_| cache_sessions.get(&key).copied().unwrap_or(0)),
+            history_sessions: history_sessions
+                .as_ref()
+                .map(|h| h.get(&key).copied().unwrap_or(0)),
+            prometheus_sessions,
+            stats_cache_tokens: cache_tokens
+                .as_ref()
+                .map(|t| t.get(&key).copied().unwrap_or(0)),
+            prometheus_tokens,
+            date: key,
+            reasons: Vec::new(),
+        };
+
+        compare(
+            "sessions",
+            &[
+                ("stats cache", day.stats_cache_sessions),
+                ("history", day.history_sessions),
+                ("Prometheus", day.prometheus_sessions),
+            ],
+            settings.threshold_percent,
+            MIN_SESSION_DIFFERENCE,
+            &mut day.reasons,
+        );
+        compare(
+            "tokens",
+            &[
+                ("stats cache", day.stats_cache_tokens),
+                ("Prometheus", day.prometheus_tokens),
+            ],
+            settings.threshold_percent,
+            MIN_TOKEN_DIFFERENCE,
+            &mut day.reasons,
+        );
+        drift_days.push(day);
+    }
+
+    let flagged_days = drift_days.iter().filter(|d| !d.reasons.is_empty()).count() as u32;
+    let badge = if flagged_days == 0 {
+        DriftBadge {
+            level: "ok".to_string(),
+            flagged_days,
+            message: "Local data and Prometheus agree".to_string(),
+        }
+    } else {
+        DriftBadge {
+            level: "warning".to_string(),
+            flagged_days,
+            message: format!(
+                "{} day(s) differ by more than {}% between sources",
+                flagged_days, settings.threshold_percent
+            ),
+        }
+    };
+
+    ReconciliationReport {
+        generated_at: chrono::Utc::now().timestamp_millis(),
+        threshold_percent: settings.threshold_percent,
+        prometheus_available,
+        days: drift_days,
+        badge,
+    }
+}
+
+async fn run_and_publish(app: &AppHandle, prometheus_url: &str) -> ReconciliationReport {
+    let settings: ReconciliationSettings = load_setting(app, RECONCILIATION_KEY);
+    let report = reconcile(app, &settings, prometheus_url).await;
+
+    if let Ok(mut guard) = LAST_REPORT.lock() {
+        *guard = Some(report.clone());
+    }
+    publish(app, "reconciliation-updated", report.badge.clone());
+    report
+}
+
+/// Re-run reconciliation on the configured interval for the lifetime of the app
+pub fn start_reconciliation(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let settings: ReconciliationSettings = load_setting(&app, RECONCILIATION_KEY);
+            if settings.enabled {
+                run_and_publish(&app, &frontend_prometheus_url(&app)).await;
+            }
+            let interval = settings.interval_minutes.max(1) as u64 * 60;
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_reconciliation_settings(app: AppHandle) -> Result<ReconciliationSettings, String> {
+    Ok(load_setting(&app, RECONCILIATION_KEY))
+}
+
+#[tauri::command]
+pub async fn set_reconciliation_settings(
+    app: AppHandle,
+    settings: ReconciliationSettings,
+) -> Result<(), String> {
+    if settings.threshold_percent <= 0.0 {
+        return Err("Threshold must be greater than zero".to_string());
+    }
+    save_setting(&app, RECONCILIATION_KEY, &settings)
+}
+
+/// Latest scheduled report, if one has run yet
+#[tauri::command]
+pub async fn get_reconciliation_report() -> Result<Option<ReconciliationReport>, String> {
+    Ok(LAST_REPORT.lock().map_err(|e| e.to_string())?.clone())
+}
+
+#[tauri::command]
+pub async fn run_reconciliation(
+    app: AppHandle,
+    prometheus_url: Option<String>,
+) -> Result<ReconciliationReport, String> {
+    let prometheus_url = prometheus_url.unwrap_or_else(|| frontend_prometheus_url(&app));
+    Ok(run_and_publish(&app, &prometheus_url).await)
+}
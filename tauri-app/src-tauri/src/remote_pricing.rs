@@ -0,0 +1,255 @@
+// tauri-app/src-tauri/src/remote_pricing.rs
+
+use crate::error::AppError;
+use crate::local_data::{category_dir, DataCategory};
+use crate::pricing::ModelRates;
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const REMOTE_PRICING_KEY: &str = "remotePricing";
+const DEFAULT_SOURCE_URL: &str =
+    "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+/// How often the scheduler checks whether the cached prices are stale
+const CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Pull Claude list prices from a LiteLLM-format price list instead of relying on
+/// the bundled table alone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemotePricingSettings {
+    /// Use the fetched prices for built-in profiles and refresh them on schedule
+    pub enabled: bool,
+    pub url: String,
+    pub refresh_interval_hours: u32,
+}
+
+impl Default for RemotePricingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: DEFAULT_SOURCE_URL.to_string(),
+            refresh_interval_hours: 24,
+        }
+    }
+}
+
+/// Fetched prices, cached on disk so they survive restarts and offline starts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemotePrices {
+    pub fetched_at: i64,
+    pub source_url: String,
+    /// Lowercased model id to USD per million tokens
+    pub models: HashMap<String, ModelRates>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemotePricingStatus {
+    pub settings: RemotePricingSettings,
+    /// When the cached prices were fetched; None if never
+    pub fetched_at: Option<i64>,
+    pub model_count: usize,
+    /// Whether built-in profiles are priced from the fetched list right now
+    pub active: bool,
+    pub last_error: Option<String>,
+}
+
+/// Prices in use; None falls back to the bundled table
+static ACTIVE_PRICES: Mutex<Option<RemotePrices>> = Mutex::new(None);
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+fn cache_path() -> Option<PathBuf> {
+    category_dir(DataCategory::Pricing).map(|d| d.join("remote-prices.json"))
+}
+
+fn read_cache() -> Option<RemotePrices> {
+    let contents = std::fs::read(cache_path()?).ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+fn write_cache(prices: &RemotePrices) -> Result<(), AppError> {
+    let path = cache_path().ok_or("Could not find the app data directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Other(e.to_string()))?;
+    }
+    let contents = serde_json::to_vec(prices).map_err(|e| AppError::Other(e.to_string()))?;
+    std::fs::write(&path, contents).map_err(|e| AppError::Other(e.to_string()))
+}
+
+fn set_active(prices: Option<RemotePrices>) {
+    if let Ok(mut guard) = ACTIVE_PRICES.lock() {
+        *guard = prices;
+    }
+}
+
+fn set_last_error(error: Option<String>) {
+    if let Ok(mut guard) = LAST_ERROR.lock() {
+        *guard = error;
+    }
+}
+
+/// Rates of one LiteLLM entry, converted from per token to per million tokens
+fn parse_entry(entry: &Value) -> Option<ModelRates> {
+    let per_million = |key: &str| entry[key].as_f64().map(|v| v * 1_000_000.0);
+    let input = per_million("input_cost_per_token")?;
+    let output = per_million("output_cost_per_token")?;
+    Some(ModelRates {
+        input,
+        output,
+        cache_read: per_million("cache_read_input_token_cost").unwrap_or(input * 0.1),
+        cache_creation: per_million("cache_creation_input_token_cost").unwrap_or(input * 1.25),
+    })
+}
+
+/// Anthropic API prices of Claude models; Bedrock and Vertex entries are left to
+/// the profiles' premiums
+fn parse_prices(root: &Value) -> HashMap<String, ModelRates> {
+    root.as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(name, entry)| entry["litellm_provider"] == "anthropic" && name.contains("claude"))
+        .filter_map(|(name, entry)| Some((name.to_lowercase(), parse_entry(entry)?)))
+        .collect()
+}
+
+async fn fetch_prices(url: &str) -> Result<RemotePrices, AppError> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| AppError::ConnectionFailed(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(AppError::QueryFailed(format!(
+            "Pricing source returned {}",
+            response.status()
+        )));
+    }
+    let root: Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::ParseError(e.to_string()))?;
+
+    let models = parse_prices(&root);
+    if models.is_empty() {
+        return Err(AppError::NoData(
+            "No Claude prices found at the pricing source".to_string(),
+        ));
+    }
+    Ok(RemotePrices {
+        fetched_at: chrono::Utc::now().timestamp(),
+        source_url: url.to_string(),
+        models,
+    })
+}
+
+/// Fetched list price of a model: its exact id, else the longest id it starts with
+pub fn remote_rates(model: &str) -> Option<ModelRates> {
+    let guard = ACTIVE_PRICES.lock().ok()?;
+    let models = &guard.as_ref()?.models;
+    let model = model.to_lowercase();
+    models.get(&model).copied().or_else(|| {
+        models
+            .iter()
+            .filter(|(id, _)| model.starts_with(id.as_str()))
+            .max_by_key(|(id, _)| id.len())
+            .map(|(_, rates)| *rates)
+    })
+}
+
+/// Use the cached prices if remote pricing is enabled
+pub fn load_remote_pricing(app: &AppHandle) {
+    let settings: RemotePricingSettings = load_setting(app, REMOTE_PRICING_KEY);
+    set_active(read_cache().filter(|_| settings.enabled));
+}
+
+/// Fetch and cache the price list, applying it if enabled. On failure the
+/// previous prices (or the bundled table) stay in use.
+async fn refresh(settings: &RemotePricingSettings) -> Result<(), AppError> {
+    let prices = match fetch_prices(&settings.url).await {
+        Ok(prices) => prices,
+        Err(e) => {
+            set_last_error(Some(e.to_string()));
+            return Err(e);
+        }
+    };
+    set_last_error(None);
+    write_cache(&prices)?;
+    if settings.enabled {
+        set_active(Some(prices));
+    }
+    Ok(())
+}
+
+/// Refresh the prices whenever the cache is older than the configured interval
+pub fn start_pricing_refresh(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let settings: RemotePricingSettings = load_setting(&app, REMOTE_PRICING_KEY);
+            let fetched_at = read_cache().map(|p| p.fetched_at).unwrap_or_default();
+            let interval = settings.refresh_interval_hours.max(1) as i64 * 3600;
+            if settings.enabled && chrono::Utc::now().timestamp() - fetched_at >= interval {
+                if let Err(e) = refresh(&settings).await {
+                    eprintln!("Pricing refresh failed, keeping current prices: {}", e);
+                }
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+fn status(settings: RemotePricingSettings) -> RemotePricingStatus {
+    let cached = read_cache();
+    RemotePricingStatus {
+        fetched_at: cached.as_ref().map(|p| p.fetched_at),
+        model_count: cached.map(|p| p.models.len()).unwrap_or_default(),
+        active: ACTIVE_PRICES.lock().is_ok_and(|guard| guard.is_some()),
+        last_error: LAST_ERROR.lock().ok().and_then(|guard| guard.clone()),
+        settings,
+    }
+}
+
+#[tauri::command]
+pub async fn get_remote_pricing_status(app: AppHandle) -> Result<RemotePricingStatus, AppError> {
+    Ok(status(load_setting(&app, REMOTE_PRICING_KEY)))
+}
+
+#[tauri::command]
+pub async fn set_remote_pricing_settings(
+    app: AppHandle,
+    settings: RemotePricingSettings,
+) -> Result<RemotePricingStatus, AppError> {
+    if !(settings.url.starts_with("http://") || settings.url.starts_with("https://")) {
+        return Err(AppError::InvalidInput(
+            "Pricing source URL must start with http:// or https://".to_string(),
+        ));
+    }
+    if settings.refresh_interval_hours == 0 {
+        return Err(AppError::InvalidInput(
+            "Refresh interval must be at least one hour".to_string(),
+        ));
+    }
+    save_setting(&app, REMOTE_PRICING_KEY, &settings)?;
+    load_remote_pricing(&app);
+    Ok(status(settings))
+}
+
+/// Fetch the price list now. Prices are cached even while remote pricing is
+/// disabled and take effect once it's enabled.
+#[tauri::command]
+pub async fn refresh_pricing(app: AppHandle) -> Result<RemotePricingStatus, AppError> {
+    let settings: RemotePricingSettings = load_setting(&app, REMOTE_PRICING_KEY);
+    refresh(&settings).await?;
+    Ok(status(settings))
+}
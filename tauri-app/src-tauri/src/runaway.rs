@@ -0,0 +1,168 @@
+// tauri-app/src-tauri/src/runaway.rs
+
+use crate::events::publish;
+use crate::processes::scan_claude_processes;
+use crate::session_events::{load_session_event_settings, recent_sessions, session_costs};
+use crate::settings::{frontend_prometheus_url, load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const RUNAWAY_KEY: &str = "runawaySessions";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RunawaySettings {
+    pub enabled: bool,
+    pub poll_interval_seconds: u32,
+    /// Warn once a live session has cost more than this
+    pub max_cost_usd: Option<f64>,
+    /// Warn once a live session's process has been running longer than this
+    pub max_duration_minutes: Option<u32>,
+}
+
+impl Default for RunawaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_seconds: 60,
+            max_cost_usd: Some(25.0),
+            max_duration_minutes: Some(180),
+        }
+    }
+}
+
+/// A live, still-active session past one of the thresholds
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunawayWarning {
+    pub pid: u32,
+    pub session_id: Option<String>,
+    pub project: Option<String>,
+    /// "cost" or "duration"
+    pub reason: String,
+    pub cost_usd: Option<f64>,
+    pub duration_minutes: u64,
+    pub threshold: f64,
+    pub detected_at: i64,
+}
+
+static WARNINGS: Mutex<Vec<RunawayWarning>> = Mutex::new(Vec::new());
+
+/// Check every running Claude process with recent activity against the thresholds
+async fn check(app: &AppHandle, settings: &RunawaySettings) -> Vec<RunawayWarning> {
+    let processes = tauri::async_runtime::spawn_blocking(scan_claude_processes)
+        .await
+        .unwrap_or_default();
+    if processes.is_empty() {
+        return Vec::new();
+    }
+
+    // An open but idle process isn't burning anything
+    let idle_after = load_session_event_settings(app).idle_after_minutes.max(1) as i64;
+    let now = chrono::Utc::now().timestamp_millis();
+    let active: HashSet<String> = recent_sessions(now - idle_after * 60 * 1000)
+        .into_iter()
+        .map(|(session_id, _, _, _)| session_id)
+        .collect();
+
+    let costs: HashMap<String, f64> = match settings.max_cost_usd {
+        Some(_) => session_costs(&frontend_prometheus_url(app)).await,
+        None => HashMap::new(),
+    };
+
+    let mut warnings = Vec::new();
+    for process in processes {
+        let Some(session_id) = process.session_id.clone() else {
+            continue;
+        };
+        if !active.contains(&session_id) {
+            continue;
+        }
+
+        let cost = costs.get(&session_id).copied();
+        let duration_minutes = process.uptime_seconds / 60;
+        let warning = |reason: &str, threshold: f64| RunawayWarning {
+            pid: process.pid,
+            session_id: Some(session_id.clone()),
+            project: process.project.clone(),
+            reason: reason.to_string(),
+            cost_usd: cost,
+            duration_minutes,
+            threshold,
+            detected_at: now,
+        };
+
+        if let (Some(max), Some(cost)) = (settings.max_cost_usd, cost) {
+            if cost >= max {
+                warnings.push(warning("cost", max));
+            }
+        }
+        if let Some(max) = settings.max_duration_minutes {
+            if duration_minutes >= max as u64 {
+                warnings.push(warning("duration", max as f64));
+            }
+        }
+    }
+    warnings
+}
+
+/// Poll live sessions and publish `session-runaway` the first time each session
+/// crosses each threshold
+pub fn start_runaway_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut warned: HashSet<(String, String)> = HashSet::new();
+
+        loop {
+            let settings: RunawaySettings = load_setting(&app, RUNAWAY_KEY);
+            let warnings = if settings.enabled {
+                check(&app, &settings).await
+            } else {
+                Vec::new()
+            };
+
+            for warning in &warnings {
+                let key = (
+                    warning.session_id.clone().unwrap_or_default(),
+                    warning.reason.clone(),
+                );
+                if warned.insert(key) {
+                    publish(&app, "session-runaway", warning.clone());
+                }
+            }
+            // Forget sessions that stopped so a resumed session can warn again
+            warned.retain(|(session_id, reason)| {
+                warnings
+                    .iter()
+                    .any(|w| w.session_id.as_deref() == Some(session_id) && w.reason == *reason)
+            });
+            if let Ok(mut guard) = WARNINGS.lock() {
+                *guard = warnings;
+            }
+
+            let interval = settings.poll_interval_seconds.max(10) as u64;
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_runaway_settings(app: AppHandle) -> Result<RunawaySettings, String> {
+    Ok(load_setting(&app, RUNAWAY_KEY))
+}
+
+#[tauri::command]
+pub async fn set_runaway_settings(app: AppHandle, settings: RunawaySettings) -> Result<(), String> {
+    if settings.max_cost_usd.is_some_and(|c| c <= 0.0) {
+        return Err("Cost threshold must be greater than zero".to_string());
+    }
+    save_setting(&app, RUNAWAY_KEY, &settings)
+}
+
+/// Sessions currently past a threshold, as of the last check
+#[tauri::command]
+pub async fn get_runaway_warnings() -> Result<Vec<RunawayWarning>, String> {
+    Ok(WARNINGS.lock().map_err(|e| e.to_string())?.clone())
+}
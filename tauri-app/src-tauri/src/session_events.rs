@@ -0,0 +1,249 @@
+// tauri-app/src-tauri/src/session_events.rs
+
+use crate::events::publish;
+use crate::history_index::{get_history_path, with_history_index};
+use crate::prometheus::PrometheusClient;
+use crate::settings::{frontend_prometheus_url, load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const SESSION_EVENTS_KEY: &str = "sessionEvents";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionEventSettings {
+    pub poll_interval_seconds: u32,
+    /// A session with no new history entries for this long is considered idle
+    pub idle_after_minutes: u32,
+    /// A session with no new history entries for this long is considered ended
+    pub end_after_minutes: u32,
+    /// Fire `session-cost-threshold` once a session's cost passes this amount
+    pub cost_threshold_usd: Option<f64>,
+}
+
+impl Default for SessionEventSettings {
+    fn default() -> Self {
+        Self {
+            poll_interval_seconds: 15,
+            idle_after_minutes: 5,
+            end_after_minutes: 15,
+            cost_threshold_usd: None,
+        }
+    }
+}
+
+/// Lifecycle of a session, driven by time since its last history entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionState {
+    Active,
+    Idle,
+    Ended,
+}
+
+impl SessionEventSettings {
+    pub fn state_for(&self, last_activity: i64, now: i64) -> SessionState {
+        let inactive_minutes = (now - last_activity) / 60_000;
+        if inactive_minutes >= self.end_after_minutes.max(1) as i64 {
+            SessionState::Ended
+        } else if inactive_minutes >= self.idle_after_minutes.max(1) as i64 {
+            SessionState::Idle
+        } else {
+            SessionState::Active
+        }
+    }
+}
+
+pub fn load_session_event_settings(app: &AppHandle) -> SessionEventSettings {
+    load_setting(app, SESSION_EVENTS_KEY)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEvent {
+    pub session_id: String,
+    pub project: String,
+    pub project_path: String,
+    pub started_at: i64,
+    pub last_activity: i64,
+    pub cost_usd: Option<f64>,
+    pub state: SessionState,
+}
+
+#[derive(Debug, Clone)]
+struct TrackedSession {
+    project_path: String,
+    started_at: i64,
+    last_activity: i64,
+    state: SessionState,
+    cost_usd: Option<f64>,
+    cost_alerted: bool,
+}
+
+impl TrackedSession {
+    fn event(&self, session_id: &str) -> SessionEvent {
+        SessionEvent {
+            session_id: session_id.to_string(),
+            project: std::path::Path::new(&self.project_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| self.project_path.clone()),
+            project_path: self.project_path.clone(),
+            started_at: self.started_at,
+            last_activity: self.last_activity,
+            cost_usd: self.cost_usd,
+            state: self.state,
+        }
+    }
+}
+
+/// Sessions with history activity since `since`, as (session id, project path, first, last)
+pub(crate) fn recent_sessions(since: i64) -> Vec<(String, String, i64, i64)> {
+    let Some(path) = get_history_path() else {
+        return Vec::new();
+    };
+    with_history_index(&path, |index| {
+        let mut sessions: HashMap<u32, (i64, i64)> = HashMap::new();
+        for record in index
+            .records
+            .iter()
+            .rev()
+            .take_while(|r| r.timestamp >= since)
+        {
+            let entry = sessions
+                .entry(record.session)
+                .or_insert((record.timestamp, record.timestamp));
+            entry.0 = entry.0.min(record.timestamp);
+            entry.1 = entry.1.max(record.timestamp);
+        }
+        sessions
+            .into_iter()
+            .map(|(id, (first, last))| {
+                let info = &index.sessions[id as usize];
+                (
+                    info.session_id.clone(),
+                    info.project_path.clone(),
+                    first,
+                    last,
+                )
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+pub(crate) async fn session_costs(prometheus_url: &str) -> HashMap<String, f64> {
+    let Ok(client) = PrometheusClient::new(prometheus_url) else {
+        return HashMap::new();
+    };
+    let query = "sum by (session_id) (increase(claude_code_cost_usage_USD_total[1d]))";
+    client
+        .query(query)
+        .await
+        .map(|results| {
+            results
+                .iter()
+                .filter_map(|r| {
+                    let session_id = r.metric.get("session_id")?.clone();
+                    let cost = r.value.as_ref()?.1.parse::<f64>().ok()?;
+                    Some((session_id, cost))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Poll history.jsonl for session starts/ends (and Prometheus for cost when a
+/// threshold is set), publishing lifecycle events for the app's lifetime
+pub fn start_session_events(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tracked: HashMap<String, TrackedSession> = HashMap::new();
+        let mut first_pass = true;
+
+        loop {
+            let settings = load_session_event_settings(&app);
+            let now = chrono::Utc::now().timestamp_millis();
+            let end_after = settings.end_after_minutes.max(1) as i64 * 60 * 1000;
+
+            for (session_id, project_path, first, last) in recent_sessions(now - end_after) {
+                match tracked.get_mut(&session_id) {
+                    Some(session) => session.last_activity = last,
+                    None => {
+                        let session = TrackedSession {
+                            project_path,
+                            started_at: first,
+                            last_activity: last,
+                            state: settings.state_for(last, now),
+                            cost_usd: None,
+                            cost_alerted: false,
+                        };
+                        // Sessions already running when the app starts aren't "new"
+                        if !first_pass {
+                            publish(&app, "session-started", session.event(&session_id));
+                        }
+                        tracked.insert(session_id, session);
+                    }
+                }
+            }
+            first_pass = false;
+
+            if let Some(threshold) = settings.cost_threshold_usd {
+                let costs = session_costs(&frontend_prometheus_url(&app)).await;
+                for (session_id, session) in tracked.iter_mut() {
+                    session.cost_usd = costs.get(session_id).copied().or(session.cost_usd);
+                    if !session.cost_alerted && session.cost_usd.is_some_and(|c| c >= threshold) {
+                        session.cost_alerted = true;
+                        publish(&app, "session-cost-threshold", session.event(session_id));
+                    }
+                }
+            }
+
+            // active <-> idle -> ended, publishing each transition
+            let mut ended = Vec::new();
+            for (session_id, session) in tracked.iter_mut() {
+                let state = settings.state_for(session.last_activity, now);
+                if state == session.state {
+                    continue;
+                }
+                let previous = session.state;
+                session.state = state;
+                match (previous, state) {
+                    (_, SessionState::Ended) => ended.push(session_id.clone()),
+                    (_, SessionState::Idle) => {
+                        publish(&app, "session-idle", session.event(session_id))
+                    }
+                    (SessionState::Idle, SessionState::Active) => {
+                        publish(&app, "session-resumed", session.event(session_id))
+                    }
+                    _ => {}
+                }
+            }
+            for session_id in ended {
+                if let Some(session) = tracked.remove(&session_id) {
+                    publish(&app, "session-ended", session.event(&session_id));
+                }
+            }
+
+            let interval = settings.poll_interval_seconds.max(5) as u64;
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_session_event_settings(app: AppHandle) -> Result<SessionEventSettings, String> {
+    Ok(load_session_event_settings(&app))
+}
+
+#[tauri::command]
+pub async fn set_session_event_settings(
+    app: AppHandle,
+    settings: SessionEventSettings,
+) -> Result<(), String> {
+    if settings.idle_after_minutes >= settings.end_after_minutes {
+        return Err("Sessions must go idle before they end".to_string());
+    }
+    save_setting(&app, SESSION_EVENTS_KEY, &settings)
+}
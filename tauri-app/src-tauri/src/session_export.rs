@@ -0,0 +1,144 @@
+// tauri-app/src-tauri/src/session_export.rs
+
+use crate::sessions::{ProjectStats, SessionMetrics};
+use chrono::{Local, TimeZone};
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[derive(Debug, Clone, Copy)]
+enum TableFormat {
+    Tsv,
+    Markdown,
+}
+
+impl TableFormat {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format {
+            "tsv" => Ok(TableFormat::Tsv),
+            "markdown" | "md" => Ok(TableFormat::Markdown),
+            _ => Err(format!("Unsupported table format: {}", format)),
+        }
+    }
+}
+
+fn format_duration(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).round() as u64;
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn format_timestamp(millis: i64) -> String {
+    Local
+        .timestamp_millis_opt(millis)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Keep cell text from breaking the row/column structure of the output format
+fn escape_cell(cell: &str, format: TableFormat) -> String {
+    match format {
+        TableFormat::Tsv => cell.replace(['\t', '\n', '\r'], " "),
+        TableFormat::Markdown => cell.replace('|', "\\|").replace(['\n', '\r'], " "),
+    }
+}
+
+fn render_table(headers: &[&str], rows: &[Vec<String>], format: TableFormat) -> String {
+    let render_row = |cells: Vec<String>| -> String {
+        let cells: Vec<String> = cells.iter().map(|c| escape_cell(c, format)).collect();
+        match format {
+            TableFormat::Tsv => cells.join("\t"),
+            TableFormat::Markdown => format!("| {} |", cells.join(" | ")),
+        }
+    };
+
+    let mut lines = vec![render_row(headers.iter().map(|h| h.to_string()).collect())];
+    if let TableFormat::Markdown = format {
+        lines.push(format!("|{}", "---|".repeat(headers.len())));
+    }
+    lines.extend(rows.iter().cloned().map(render_row));
+    lines.join("\n") + "\n"
+}
+
+fn sessions_table(sessions: &[SessionMetrics], format: TableFormat) -> String {
+    let headers = [
+        "Session",
+        "Project",
+        "Origin",
+        "Started",
+        "Last Active",
+        "Messages",
+        "Tokens",
+        "Cost (USD)",
+        "Active Time",
+        "Length",
+    ];
+    let rows: Vec<Vec<String>> = sessions
+        .iter()
+        .map(|s| {
+            vec![
+                s.session_id.clone(),
+                s.project.clone().unwrap_or_default(),
+                s.origin.clone().unwrap_or_default(),
+                format_timestamp(s.started_at),
+                format_timestamp(s.ended_at),
+                s.message_count.to_string(),
+                s.total_tokens.to_string(),
+                format!("{:.2}", s.total_cost_usd),
+                format_duration(s.active_time_seconds),
+                format_duration(s.duration_seconds as f64),
+            ]
+        })
+        .collect();
+    render_table(&headers, &rows, format)
+}
+
+fn projects_table(projects: &[ProjectStats], format: TableFormat) -> String {
+    let headers = ["Project", "Sessions", "Tokens", "Cost (USD)", "Active Time"];
+    let rows: Vec<Vec<String>> = projects
+        .iter()
+        .map(|p| {
+            vec![
+                p.project.clone(),
+                p.session_count.to_string(),
+                p.total_tokens.to_string(),
+                format!("{:.2}", p.total_cost_usd),
+                format_duration(p.active_time_seconds),
+            ]
+        })
+        .collect();
+    render_table(&headers, &rows, format)
+}
+
+/// Format the selected sessions and/or projects as a table and copy it to the
+/// clipboard. Returns the number of rows copied.
+#[tauri::command]
+pub async fn copy_sessions_table(
+    app: AppHandle,
+    format: String,
+    sessions: Option<Vec<SessionMetrics>>,
+    projects: Option<Vec<ProjectStats>>,
+) -> Result<usize, String> {
+    let format = TableFormat::parse(&format)?;
+    let sessions = sessions.unwrap_or_default();
+    let projects = projects.unwrap_or_default();
+    if sessions.is_empty() && projects.is_empty() {
+        return Err("Nothing selected to copy".to_string());
+    }
+
+    let mut tables = Vec::new();
+    if !sessions.is_empty() {
+        tables.push(sessions_table(&sessions, format));
+    }
+    if !projects.is_empty() {
+        tables.push(projects_table(&projects, format));
+    }
+
+    app.clipboard()
+        .write_text(tables.join("\n"))
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    Ok(sessions.len() + projects.len())
+}
@@ -1,28 +1,37 @@
 // tauri-app/src-tauri/src/sessions.rs
 
+use crate::commands::{step_and_rate_window, to_time_series};
+use crate::data_sources::enabled_sources;
+use crate::endpoints::endpoint_urls;
+use crate::error::AppError;
+use crate::filters::escape;
+use crate::history_index::{with_history_index, with_recent_history, HistoryIndex};
+use crate::metrics::{EndpointStatus, TimeSeriesPoint, TokenType};
+use crate::pricing::resolve_profile;
 use crate::prometheus::PrometheusClient;
+use crate::query_tuning::{load_query_tuning, QueryTuning};
+use crate::session_events::{load_session_event_settings, SessionState};
+use crate::settings::frontend_pricing_provider;
+use crate::transcript_archive::find_transcript;
+use crate::transcripts::{session_usage, ModelUsageTotals, SessionUsage, ToolCallCount};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
 
-/// Entry from ~/.claude/history.jsonl
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct HistoryEntry {
-    timestamp: i64,
-    project: String,
-    session_id: String,
-}
-
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionMetrics {
     pub session_id: String,
     pub project: Option<String>,
     pub project_path: Option<String>,
+    /// Latest history entry, in milliseconds (same as `ended_at`)
     pub timestamp: i64,
+    /// First history entry in the loaded range, in milliseconds
+    pub started_at: i64,
+    /// Last history entry, in milliseconds
+    pub ended_at: i64,
+    /// Wall-clock time from the first to the last entry
+    pub duration_seconds: i64,
     pub message_count: u32,
     pub total_cost_usd: f64,
     pub total_tokens: u64,
@@ -32,16 +41,20 @@ pub struct SessionMetrics {
     pub cache_creation_tokens: u64,
     pub active_time_seconds: f64,
     pub tokens_by_model: Vec<ModelTokenCount>,
+    /// Machine the session ran on (Prometheus host label, else the data source name)
+    pub origin: Option<String>,
+    /// Derived from time since the last history entry
+    pub status: SessionState,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelTokenCount {
     pub model: String,
     pub tokens: u64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectStats {
     pub project: String,
@@ -58,10 +71,25 @@ pub struct SessionsData {
     pub sessions: Vec<SessionMetrics>,
     pub projects: Vec<ProjectStats>,
     pub total_count: usize,
+    /// Every origin seen in the range, for the origin filter
+    pub origins: Vec<String>,
+    /// Connection result for each Prometheus endpoint that was queried
+    pub endpoints: Vec<EndpointStatus>,
 }
 
-fn get_history_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("history.jsonl"))
+/// One session's totals plus the series and breakdowns behind its drill-down page
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDetail {
+    pub session: SessionMetrics,
+    /// Per-second rates over the session's lifetime, as on the dashboard charts
+    pub cost_over_time: Vec<TimeSeriesPoint>,
+    pub tokens_over_time: Vec<TimeSeriesPoint>,
+    /// Tool calls by name, from the session's transcript
+    pub tool_usage: Vec<ToolCallCount>,
+    /// Tokens and estimated cost by model, from the session's transcript
+    pub model_mix: Vec<ModelUsageTotals>,
+    pub endpoints: Vec<EndpointStatus>,
 }
 
 /// Extract the last folder name from a path
@@ -88,7 +116,15 @@ fn time_range_to_millis(range: &str) -> i64 {
     hours * 60 * 60 * 1000
 }
 
-fn time_range_to_promql(range: &str) -> &str {
+/// Shortest preset range reaching back `age_millis`
+fn covering_time_range(age_millis: i64) -> &'static str {
+    ["1h", "8h", "24h", "2d", "7d", "30d"]
+        .into_iter()
+        .find(|range| time_range_to_millis(range) >= age_millis)
+        .unwrap_or("90d")
+}
+
+pub(crate) fn time_range_to_promql(range: &str) -> &str {
     match range {
         "1h" => "1h",
         "8h" => "8h",
@@ -101,12 +137,10 @@ fn time_range_to_promql(range: &str) -> &str {
     }
 }
 
-fn load_history_sessions(time_range: &str) -> Result<HashMap<String, SessionMetrics>, String> {
-    let path = get_history_path().ok_or("Could not find home directory")?;
-    let file = File::open(&path)
-        .map_err(|_| "History file not found. Use Claude Code to generate usage data.")?;
-    let reader = BufReader::new(file);
-
+fn load_history_sessions(
+    app: &AppHandle,
+    time_range: &str,
+) -> Result<HashMap<String, SessionMetrics>, AppError> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
@@ -114,37 +148,84 @@ fn load_history_sessions(time_range: &str) -> Result<HashMap<String, SessionMetr
     let cutoff = now - time_range_to_millis(time_range);
 
     let mut sessions_map: HashMap<String, SessionMetrics> = HashMap::new();
+    let mut first_error = None;
+    let mut loaded_any = false;
+
+    for source in enabled_sources(app) {
+        let path = source.history_path();
+
+        // Short ranges only need the end of the file, so avoid building the full index
+        let result = match time_range {
+            "1h" | "8h" | "24h" => with_recent_history(&path, cutoff, |index| {
+                sessions_since(index, cutoff, &source.name)
+            }),
+            _ => with_history_index(&path, |index| sessions_since(index, cutoff, &source.name)),
+        };
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
-        if line.trim().is_empty() {
-            continue;
+        match result {
+            Ok(sessions) => {
+                loaded_any = true;
+                merge_sessions(&mut sessions_map, sessions);
+            }
+            Err(e) if path.exists() => {
+                first_error.get_or_insert(AppError::Other(e));
+            }
+            Err(e) => {
+                first_error.get_or_insert(AppError::FileNotFound(e));
+            }
         }
+    }
 
-        let entry: HistoryEntry = match serde_json::from_str(&line) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+    match first_error {
+        Some(e) if !loaded_any => Err(e),
+        _ => Ok(sessions_map),
+    }
+}
 
-        if entry.timestamp < cutoff {
-            continue;
+/// Merge sessions from another source. The same session id showing up twice means
+/// the file was synced from one machine to another, so keep the more complete copy.
+fn merge_sessions(
+    target: &mut HashMap<String, SessionMetrics>,
+    sessions: HashMap<String, SessionMetrics>,
+) {
+    for (session_id, session) in sessions {
+        match target.get(&session_id) {
+            Some(existing) if existing.message_count >= session.message_count => {}
+            _ => {
+                target.insert(session_id, session);
+            }
         }
+    }
+}
 
-        let project_name = extract_project_name(&entry.project);
+/// Sessions with entries at or after `cutoff`. Message counts and end times
+/// cover only the range, while start times come from each session's first entry.
+fn sessions_since(
+    index: &HistoryIndex,
+    cutoff: i64,
+    origin: &str,
+) -> HashMap<String, SessionMetrics> {
+    let mut sessions_map: HashMap<String, SessionMetrics> = HashMap::new();
+
+    for record in index.records.iter().filter(|r| r.timestamp >= cutoff) {
+        let info = &index.sessions[record.session as usize];
 
         sessions_map
-            .entry(entry.session_id.clone())
+            .entry(info.session_id.clone())
             .and_modify(|s| {
                 s.message_count += 1;
-                if entry.timestamp > s.timestamp {
-                    s.timestamp = entry.timestamp;
-                }
+                s.ended_at = s.ended_at.max(record.timestamp);
+                s.timestamp = s.ended_at;
+                s.duration_seconds = (s.ended_at - s.started_at) / 1000;
             })
             .or_insert(SessionMetrics {
-                session_id: entry.session_id,
-                project: Some(project_name),
-                project_path: Some(entry.project),
-                timestamp: entry.timestamp,
+                session_id: info.session_id.clone(),
+                project: Some(extract_project_name(&info.project_path)),
+                project_path: Some(info.project_path.clone()),
+                timestamp: record.timestamp,
+                started_at: info.first_seen,
+                ended_at: record.timestamp,
+                duration_seconds: (record.timestamp - info.first_seen) / 1000,
                 message_count: 1,
                 total_cost_usd: 0.0,
                 total_tokens: 0,
@@ -154,35 +235,39 @@ fn load_history_sessions(time_range: &str) -> Result<HashMap<String, SessionMetr
                 cache_creation_tokens: 0,
                 active_time_seconds: 0.0,
                 tokens_by_model: vec![],
+                origin: Some(origin.to_string()),
+                status: SessionState::Ended,
             });
     }
 
-    Ok(sessions_map)
+    sessions_map
 }
 
 async fn enrich_with_prometheus(
     sessions_map: &mut HashMap<String, SessionMetrics>,
     prometheus_url: &str,
     time_range: &str,
-) -> Result<(), String> {
-    let client = PrometheusClient::new(prometheus_url);
+    tuning: &QueryTuning,
+) -> Result<(), AppError> {
+    let client =
+        PrometheusClient::new(prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
     let range = time_range_to_promql(time_range);
 
     // Query cost by session
     let cost_query = format!(
         "sum by (session_id) (increase(claude_code_cost_usage_USD_total[{}]))",
-        range
+        tuning.window("claude_code_cost_usage_USD_total", range)
     );
-    if let Ok(cost_results) = client.query(&cost_query).await {
-        for result in &cost_results {
-            if let Some(session_id) = result.metric.get("session_id") {
-                if let Some(session) = sessions_map.get_mut(session_id) {
-                    session.total_cost_usd = result
-                        .value
-                        .as_ref()
-                        .and_then(|(_, v)| v.parse::<f64>().ok())
-                        .unwrap_or(0.0);
-                }
+    // Only the first query's error is reported, as this endpoint's connection status
+    let cost_results = client.query(&cost_query).await?;
+    for result in &cost_results {
+        if let Some(session_id) = result.metric.get("session_id") {
+            if let Some(session) = sessions_map.get_mut(session_id) {
+                session.total_cost_usd = result
+                    .value
+                    .as_ref()
+                    .and_then(|(_, v)| v.parse::<f64>().ok())
+                    .unwrap_or(0.0);
             }
         }
     }
@@ -190,7 +275,7 @@ async fn enrich_with_prometheus(
     // Query total tokens by session
     let tokens_query = format!(
         "sum by (session_id) (increase(claude_code_token_usage_tokens_total[{}]))",
-        range
+        tuning.window("claude_code_token_usage_tokens_total", range)
     );
     if let Ok(tokens_results) = client.query(&tokens_query).await {
         for result in &tokens_results {
@@ -209,7 +294,7 @@ async fn enrich_with_prometheus(
     // Query tokens by type
     let type_query = format!(
         "sum by (session_id, type) (increase(claude_code_token_usage_tokens_total[{}]))",
-        range
+        tuning.window("claude_code_token_usage_tokens_total", range)
     );
     if let Ok(type_results) = client.query(&type_query).await {
         for result in &type_results {
@@ -223,12 +308,12 @@ async fn enrich_with_prometheus(
                         .and_then(|(_, v)| v.parse::<f64>().ok())
                         .unwrap_or(0.0) as u64;
 
-                    match token_type.as_str() {
-                        "input" => session.input_tokens = tokens,
-                        "output" => session.output_tokens = tokens,
-                        "cache_read" => session.cache_read_tokens = tokens,
-                        "cache_creation" => session.cache_creation_tokens = tokens,
-                        _ => {}
+                    match TokenType::parse(token_type) {
+                        Some(TokenType::Input) => session.input_tokens += tokens,
+                        Some(TokenType::Output) => session.output_tokens += tokens,
+                        Some(TokenType::CacheRead) => session.cache_read_tokens += tokens,
+                        Some(TokenType::CacheCreation) => session.cache_creation_tokens += tokens,
+                        None => {}
                     }
                 }
             }
@@ -238,7 +323,7 @@ async fn enrich_with_prometheus(
     // Query active time by session
     let time_query = format!(
         "sum by (session_id) (increase(claude_code_active_time_seconds_total[{}]))",
-        range
+        tuning.window("claude_code_active_time_seconds_total", range)
     );
     if let Ok(time_results) = client.query(&time_query).await {
         for result in &time_results {
@@ -254,9 +339,49 @@ async fn enrich_with_prometheus(
         }
     }
 
+    // Query the originating host by session (needs host.name in OTEL_RESOURCE_ATTRIBUTES)
+    let host_query = format!(
+        "count by (session_id, host_name) (increase(claude_code_token_usage_tokens_total[{}]))",
+        tuning.window("claude_code_token_usage_tokens_total", range)
+    );
+    if let Ok(host_results) = client.query(&host_query).await {
+        for result in &host_results {
+            if let (Some(session_id), Some(host)) = (
+                result.metric.get("session_id"),
+                result.metric.get("host_name"),
+            ) {
+                if let Some(session) = sessions_map.get_mut(session_id) {
+                    session.origin = Some(host.clone());
+                }
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Add one endpoint's Prometheus values onto the sessions loaded from history
+fn add_prometheus_values(
+    target: &mut HashMap<String, SessionMetrics>,
+    enriched: HashMap<String, SessionMetrics>,
+) {
+    for (session_id, values) in enriched {
+        let Some(session) = target.get_mut(&session_id) else {
+            continue;
+        };
+        session.total_cost_usd += values.total_cost_usd;
+        session.total_tokens += values.total_tokens;
+        session.input_tokens += values.input_tokens;
+        session.output_tokens += values.output_tokens;
+        session.cache_read_tokens += values.cache_read_tokens;
+        session.cache_creation_tokens += values.cache_creation_tokens;
+        session.active_time_seconds += values.active_time_seconds;
+        if values.total_tokens > 0 {
+            session.origin = values.origin;
+        }
+    }
+}
+
 fn aggregate_by_project(sessions: &[SessionMetrics]) -> Vec<ProjectStats> {
     let mut project_map: HashMap<String, ProjectStats> = HashMap::new();
 
@@ -288,17 +413,65 @@ fn aggregate_by_project(sessions: &[SessionMetrics]) -> Vec<ProjectStats> {
 
 #[tauri::command]
 pub async fn get_sessions_data(
+    app: AppHandle,
     time_range: String,
     prometheus_url: String,
-) -> Result<SessionsData, String> {
+    origin: Option<String>,
+    additional_urls: Option<Vec<String>>,
+) -> Result<SessionsData, AppError> {
     // Load sessions from history.jsonl
-    let mut sessions_map = load_history_sessions(&time_range)?;
+    let mut sessions_map = load_history_sessions(&app, &time_range)?;
+
+    // Enrich with Prometheus data (cost, tokens, time), each endpoint concurrently
+    let tuning = load_query_tuning(&app);
+    let urls = endpoint_urls(&prometheus_url, additional_urls);
+    let handles: Vec<_> = urls
+        .iter()
+        .map(|url| {
+            let mut enriched = sessions_map.clone();
+            let (url, time_range, tuning) = (url.clone(), time_range.clone(), tuning.clone());
+            tauri::async_runtime::spawn(async move {
+                enrich_with_prometheus(&mut enriched, &url, &time_range, &tuning)
+                    .await
+                    .map(|_| enriched)
+            })
+        })
+        .collect();
+
+    let mut endpoints = Vec::new();
+    for (url, handle) in urls.into_iter().zip(handles) {
+        let result = handle
+            .await
+            .map_err(|e| AppError::Other(e.to_string()))
+            .and_then(|r| r);
+        endpoints.push(EndpointStatus {
+            url,
+            connected: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        if let Ok(enriched) = result {
+            add_prometheus_values(&mut sessions_map, enriched);
+        }
+    }
 
-    // Enrich with Prometheus data (cost, tokens, time)
-    let _ = enrich_with_prometheus(&mut sessions_map, &prometheus_url, &time_range).await;
+    let mut origins: Vec<String> = sessions_map
+        .values()
+        .filter_map(|s| s.origin.clone())
+        .collect();
+    origins.sort();
+    origins.dedup();
+
+    let settings = load_session_event_settings(&app);
+    let now = chrono::Utc::now().timestamp_millis();
+    for session in sessions_map.values_mut() {
+        session.status = settings.state_for(session.timestamp, now);
+    }
 
     // Convert to sorted vec (by cost descending, then by timestamp)
-    let mut sessions: Vec<SessionMetrics> = sessions_map.into_values().collect();
+    let mut sessions: Vec<SessionMetrics> = sessions_map
+        .into_values()
+        .filter(|s| origin.is_none() || s.origin == origin)
+        .collect();
     sessions.sort_by(|a, b| {
         b.total_cost_usd
             .partial_cmp(&a.total_cost_usd)
@@ -315,5 +488,253 @@ pub async fn get_sessions_data(
         sessions,
         projects,
         total_count,
+        origins,
+        endpoints,
     })
 }
+
+/// A session's full history from whichever source has the most of it
+fn find_history_session(app: &AppHandle, session_id: &str) -> Option<SessionMetrics> {
+    enabled_sources(app)
+        .into_iter()
+        .filter_map(|source| {
+            with_history_index(&source.history_path(), |index| {
+                let info = index.sessions.iter().find(|s| s.session_id == session_id)?;
+                sessions_since(index, info.first_seen, &source.name).remove(session_id)
+            })
+            .ok()
+            .flatten()
+        })
+        .max_by_key(|session| session.message_count)
+}
+
+/// Sum another endpoint's series into `target`, matching points by timestamp
+fn add_series(target: &mut Vec<TimeSeriesPoint>, series: Vec<TimeSeriesPoint>) {
+    let mut points: BTreeMap<i64, TimeSeriesPoint> =
+        target.drain(..).map(|p| (p.timestamp, p)).collect();
+    for point in series {
+        points
+            .entry(point.timestamp)
+            .and_modify(|p| {
+                p.value += point.value;
+                p.partial |= point.partial;
+            })
+            .or_insert(point);
+    }
+    *target = points.into_values().collect();
+}
+
+/// Cost and token rates over time, and tokens by model
+type SessionSeries = (
+    Vec<TimeSeriesPoint>,
+    Vec<TimeSeriesPoint>,
+    Vec<ModelTokenCount>,
+);
+
+/// Range queries and model split for one session on one endpoint
+async fn session_series(
+    prometheus_url: &str,
+    session_id: &str,
+    (start, end): (i64, i64),
+    range: &str,
+    tuning: &QueryTuning,
+) -> Result<SessionSeries, AppError> {
+    let client =
+        PrometheusClient::new(prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let matcher = format!("session_id=\"{}\"", escape(session_id));
+    let (step, rate_window) = step_and_rate_window("custom", start, end);
+
+    let cost_query = format!(
+        "sum(rate(claude_code_cost_usage_USD_total{{{}}}[{}]))",
+        matcher,
+        tuning.rate_window("claude_code_cost_usage_USD_total", rate_window)
+    );
+    let cost_over_time = client
+        .query_range(&cost_query, start, end, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
+
+    let tokens_query = format!(
+        "sum(rate(claude_code_token_usage_tokens_total{{{}}}[{}]))",
+        matcher,
+        tuning.rate_window("claude_code_token_usage_tokens_total", rate_window)
+    );
+    let tokens_over_time = client
+        .query_range(&tokens_query, start, end, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
+
+    let model_query = format!(
+        "sum by (model) (increase(claude_code_token_usage_tokens_total{{{}}}[{}]))",
+        matcher,
+        tuning.window("claude_code_token_usage_tokens_total", range)
+    );
+    let tokens_by_model = client
+        .query(&model_query)
+        .await
+        .map(|results| {
+            results
+                .iter()
+                .filter_map(|r| {
+                    let model = r.metric.get("model")?.clone();
+                    let tokens = r.value.as_ref()?.1.parse::<f64>().ok()? as u64;
+                    Some(ModelTokenCount { model, tokens })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok((cost_over_time, tokens_over_time, tokens_by_model))
+}
+
+/// Fill a session's totals from its transcript when Prometheus has none for it
+fn apply_transcript_totals(session: &mut SessionMetrics, usage: &SessionUsage) {
+    session.total_cost_usd = usage.cost_usd;
+    session.total_tokens = usage.total_tokens;
+    session.input_tokens = usage.input_tokens;
+    session.output_tokens = usage.output_tokens;
+    session.cache_read_tokens = usage.cache_read_tokens;
+    session.cache_creation_tokens = usage.cache_creation_tokens;
+    session.tokens_by_model = usage
+        .by_model
+        .iter()
+        .map(|m| ModelTokenCount {
+            model: m.model.clone(),
+            tokens: m.tokens,
+        })
+        .collect();
+}
+
+#[tauri::command]
+pub async fn get_session_detail(
+    app: AppHandle,
+    session_id: String,
+    prometheus_url: String,
+    additional_urls: Option<Vec<String>>,
+    pricing_provider: Option<String>,
+) -> Result<SessionDetail, AppError> {
+    let pricing = resolve_profile(
+        &app,
+        &pricing_provider.unwrap_or_else(|| frontend_pricing_provider(&app)),
+    );
+    let (history, transcript) = {
+        let (app, session_id) = (app.clone(), session_id.clone());
+        tauri::async_runtime::spawn_blocking(move || {
+            let history = find_history_session(&app, &session_id);
+            let transcript =
+                find_transcript(&session_id).and_then(|t| session_usage(&t, &pricing).ok());
+            (history, transcript)
+        })
+        .await
+        .map_err(|e| AppError::Other(e.to_string()))?
+    };
+    let not_found = || AppError::NoData(format!("Session {} not found in history", session_id));
+    let session = history.ok_or_else(not_found)?;
+    let (first_entry, last_entry) = (session.started_at, session.ended_at);
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let started_at = transcript
+        .as_ref()
+        .and_then(|t| t.first_activity)
+        .map_or(first_entry, |t| first_entry.min(t * 1000));
+    let last_activity = transcript
+        .as_ref()
+        .and_then(|t| t.last_activity)
+        .map_or(last_entry, |t| last_entry.max(t * 1000));
+    // Pad either side so the first and last exports fall inside the chart
+    let start = started_at / 1000 - 60;
+    let end = (last_activity / 1000 + 300).min(now / 1000);
+    let range = covering_time_range(now - started_at);
+
+    let tuning = load_query_tuning(&app);
+    let mut cost_over_time = Vec::new();
+    let mut tokens_over_time = Vec::new();
+    let mut tokens_by_model: HashMap<String, u64> = HashMap::new();
+    let mut endpoints = Vec::new();
+    let mut sessions = HashMap::from([(session_id.clone(), session)]);
+    for url in endpoint_urls(&prometheus_url, additional_urls) {
+        let mut enriched = sessions.clone();
+        let result = match enrich_with_prometheus(&mut enriched, &url, range, &tuning).await {
+            Ok(()) => session_series(&url, &session_id, (start, end), range, &tuning)
+                .await
+                .map(|series| (enriched, series)),
+            Err(e) => Err(e),
+        };
+        endpoints.push(EndpointStatus {
+            url,
+            connected: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        if let Ok((enriched, (cost, tokens, models))) = result {
+            add_prometheus_values(&mut sessions, enriched);
+            add_series(&mut cost_over_time, cost);
+            add_series(&mut tokens_over_time, tokens);
+            for model in models {
+                *tokens_by_model.entry(model.model).or_default() += model.tokens;
+            }
+        }
+    }
+    let mut session = sessions.remove(&session_id).ok_or_else(not_found)?;
+    session.tokens_by_model = tokens_by_model
+        .into_iter()
+        .map(|(model, tokens)| ModelTokenCount { model, tokens })
+        .collect();
+    session
+        .tokens_by_model
+        .sort_by_key(|m| std::cmp::Reverse(m.tokens));
+
+    if let Some(usage) = transcript.as_ref().filter(|_| session.total_tokens == 0) {
+        apply_transcript_totals(&mut session, usage);
+    }
+    let settings = load_session_event_settings(&app);
+    session.status = settings.state_for(last_activity, now);
+    session.started_at = started_at;
+    session.ended_at = last_activity;
+    session.duration_seconds = (last_activity - started_at) / 1000;
+
+    let (tool_usage, model_mix) = transcript
+        .map(|t| (t.tool_calls, t.by_model))
+        .unwrap_or_default();
+    Ok(SessionDetail {
+        session,
+        cost_over_time,
+        tokens_over_time,
+        tool_usage,
+        model_mix,
+        endpoints,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{history_entry as entry, TempDir};
+
+    #[test]
+    fn session_started_before_cutoff_keeps_its_start() {
+        let dir = TempDir::new("sessions");
+        let path = dir.path().join("history.jsonl");
+        let history = [
+            entry("a", 1_000),
+            entry("b", 5_000),
+            entry("a", 60_000),
+            entry("a", 90_000),
+        ]
+        .concat();
+        std::fs::write(&path, history).unwrap();
+
+        let sessions =
+            with_history_index(&path, |index| sessions_since(index, 50_000, "local")).unwrap();
+
+        let a = &sessions["a"];
+        assert_eq!(a.started_at, 1_000);
+        assert_eq!(a.ended_at, 90_000);
+        assert_eq!(a.duration_seconds, 89);
+        assert_eq!(a.message_count, 2);
+        assert!(!sessions.contains_key("b"));
+    }
+}
@@ -0,0 +1,81 @@
+// tauri-app/src-tauri/src/settings.rs
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/// Store file for settings owned by the Rust backend. Kept separate from the
+/// frontend's settings.json so neither side overwrites the other on save.
+const BACKEND_STORE_PATH: &str = "backend-settings.json";
+
+/// Store file written by the frontend settings page
+const FRONTEND_STORE_PATH: &str = "settings.json";
+const DEFAULT_PROMETHEUS_URL: &str = "http://localhost:9090";
+
+/// Prometheus URL configured in the frontend settings, for background tasks that
+/// run without a request from the UI
+pub fn frontend_prometheus_url(app: &AppHandle) -> String {
+    app.store(FRONTEND_STORE_PATH)
+        .ok()
+        .and_then(|store| store.get("settings"))
+        .and_then(|settings| settings.get("prometheusUrl")?.as_str().map(String::from))
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROMETHEUS_URL.to_string())
+}
+
+/// Pricing profile chosen in the frontend settings, for costs estimated in the backend
+pub fn frontend_pricing_provider(app: &AppHandle) -> String {
+    app.store(FRONTEND_STORE_PATH)
+        .ok()
+        .and_then(|store| store.get("settings"))
+        .and_then(|settings| settings.get("pricingProvider")?.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// Load a backend setting, falling back to its default when missing or unreadable
+pub fn load_setting<T: DeserializeOwned + Default>(app: &AppHandle, key: &str) -> T {
+    app.store(BACKEND_STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(key))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Persist a backend setting and flush the store to disk
+pub fn save_setting<T: Serialize>(app: &AppHandle, key: &str, value: &T) -> Result<(), String> {
+    let store = app
+        .store(BACKEND_STORE_PATH)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+    let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    store.set(key, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+/// Dump every backend setting (layout, panels, retention, ...) for export
+#[tauri::command]
+pub async fn export_backend_settings(app: AppHandle) -> Result<Value, String> {
+    let store = app
+        .store(BACKEND_STORE_PATH)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+    Ok(Value::Object(store.entries().into_iter().collect()))
+}
+
+/// Restore settings previously produced by `export_backend_settings`
+#[tauri::command]
+pub async fn import_backend_settings(
+    app: AppHandle,
+    settings: Map<String, Value>,
+) -> Result<(), String> {
+    let store = app
+        .store(BACKEND_STORE_PATH)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+    for (key, value) in settings {
+        store.set(key, value);
+    }
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
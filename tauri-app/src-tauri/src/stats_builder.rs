@@ -0,0 +1,302 @@
+// tauri-app/src-tauri/src/stats_builder.rs
+
+use crate::error::AppError;
+use crate::local_data::{category_dir, DataCategory};
+use crate::timezone;
+use crate::transcript_archive::all_transcripts;
+use crate::transcripts::{read_entries, UsageDedupe};
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Claude's own cache is only used while it trails the transcripts by less than this
+const STALE_AFTER_SECONDS: i64 = 24 * 3600;
+
+/// Same layout as Claude's `stats-cache.json`, so `parse_stats_cache` reads both
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuiltStatsCache {
+    version: u32,
+    /// Newest transcript write included, unix seconds
+    built_at: i64,
+    daily_activity: Vec<DayActivity>,
+    daily_model_tokens: Vec<DayModelTokens>,
+    model_usage: BTreeMap<String, ModelTokenTotals>,
+    total_sessions: u32,
+    total_messages: u32,
+    longest_session: Option<LongestSession>,
+    first_session_date: Option<String>,
+    /// Sessions started per hour of the day
+    hour_counts: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DayActivity {
+    date: String,
+    message_count: u32,
+    session_count: u32,
+    tool_call_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DayModelTokens {
+    date: String,
+    tokens_by_model: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelTokenTotals {
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_input_tokens: u64,
+    cache_creation_input_tokens: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LongestSession {
+    session_id: String,
+    /// Milliseconds between the first and last message
+    duration: u64,
+    message_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsBuildReport {
+    pub transcripts_scanned: u32,
+    pub days: usize,
+    pub total_sessions: u32,
+    pub total_messages: u32,
+    pub built_at: i64,
+}
+
+#[derive(Debug, Default)]
+struct DayTotals {
+    messages: u32,
+    tool_calls: u32,
+    sessions: HashSet<String>,
+    tokens_by_model: BTreeMap<String, u64>,
+}
+
+#[derive(Debug)]
+struct SessionSpan {
+    first: DateTime<Utc>,
+    last: DateTime<Utc>,
+    messages: u32,
+}
+
+/// Location of the app-owned cache
+pub fn built_stats_cache_path() -> Option<PathBuf> {
+    category_dir(DataCategory::Stats).map(|d| d.join("stats-cache.json"))
+}
+
+fn modified_secs(path: &Path) -> Option<i64> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+}
+
+fn newest_transcript_write() -> Option<i64> {
+    all_transcripts()
+        .iter()
+        .filter_map(|t| modified_secs(&t.path))
+        .max()
+}
+
+fn tool_calls(content: &Value) -> u32 {
+    content
+        .as_array()
+        .map(|items| items.iter().filter(|i| i["type"] == "tool_use").count() as u32)
+        .unwrap_or_default()
+}
+
+/// Aggregate every live and archived transcript into stats cache sections
+fn build() -> (BuiltStatsCache, u32) {
+    let mut days: BTreeMap<NaiveDate, DayTotals> = BTreeMap::new();
+    let mut model_usage: BTreeMap<String, ModelTokenTotals> = BTreeMap::new();
+    let mut sessions: HashMap<String, SessionSpan> = HashMap::new();
+    let mut seen_messages = UsageDedupe::default();
+    let mut seen_usage = UsageDedupe::default();
+    let mut scanned = 0;
+    let mut built_at = 0;
+
+    for transcript in all_transcripts() {
+        let Ok(reader) = transcript.open() else {
+            continue;
+        };
+        scanned += 1;
+        built_at = built_at.max(modified_secs(&transcript.path).unwrap_or_default());
+
+        for entry in read_entries(reader) {
+            if !matches!(entry.kind.as_deref(), Some("user" | "assistant")) {
+                continue;
+            }
+            let Some(timestamp) = entry.timestamp else {
+                continue;
+            };
+            let Some(message) = &entry.message else {
+                continue;
+            };
+            let session_id = entry
+                .session_id
+                .clone()
+                .unwrap_or_else(|| transcript.session_id.clone());
+            let day = days
+                .entry(timezone::date_of(timestamp.timestamp()))
+                .or_default();
+            day.tool_calls += tool_calls(&message.content);
+
+            if seen_messages.first_sighting(&entry) {
+                day.messages += 1;
+                day.sessions.insert(session_id.clone());
+                let span = sessions.entry(session_id).or_insert(SessionSpan {
+                    first: timestamp,
+                    last: timestamp,
+                    messages: 0,
+                });
+                span.first = span.first.min(timestamp);
+                span.last = span.last.max(timestamp);
+                span.messages += 1;
+            }
+
+            let Some(usage) = message.usage else {
+                continue;
+            };
+            if !seen_usage.first_sighting(&entry) {
+                continue;
+            }
+            let model = message
+                .model
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string());
+            *day.tokens_by_model.entry(model.clone()).or_default() += usage.total();
+            let totals = model_usage.entry(model).or_default();
+            totals.input_tokens += usage.input_tokens;
+            totals.output_tokens += usage.output_tokens;
+            totals.cache_read_input_tokens += usage.cache_read_input_tokens;
+            totals.cache_creation_input_tokens += usage.cache_creation_input_tokens;
+        }
+    }
+
+    let mut hour_counts: BTreeMap<String, u32> = BTreeMap::new();
+    for span in sessions.values() {
+        let hour = timezone::datetime_of(span.first.timestamp()).hour();
+        *hour_counts.entry(hour.to_string()).or_default() += 1;
+    }
+    let longest_session = sessions
+        .iter()
+        .max_by_key(|(_, span)| span.last - span.first)
+        .map(|(id, span)| LongestSession {
+            session_id: id.clone(),
+            duration: (span.last - span.first).num_milliseconds().max(0) as u64,
+            message_count: span.messages,
+        });
+    let first_session_date = sessions
+        .values()
+        .map(|span| span.first)
+        .min()
+        .map(|first| first.to_rfc3339());
+
+    let cache = BuiltStatsCache {
+        version: 1,
+        built_at,
+        total_sessions: sessions.len() as u32,
+        total_messages: days.values().map(|d| d.messages).sum(),
+        daily_activity: days
+            .iter()
+            .map(|(date, day)| DayActivity {
+                date: date.format("%Y-%m-%d").to_string(),
+                message_count: day.messages,
+                session_count: day.sessions.len() as u32,
+                tool_call_count: day.tool_calls,
+            })
+            .collect(),
+        daily_model_tokens: days
+            .into_iter()
+            .filter(|(_, day)| !day.tokens_by_model.is_empty())
+            .map(|(date, day)| DayModelTokens {
+                date: date.format("%Y-%m-%d").to_string(),
+                tokens_by_model: day.tokens_by_model,
+            })
+            .collect(),
+        model_usage,
+        longest_session,
+        first_session_date,
+        hour_counts,
+    };
+    (cache, scanned)
+}
+
+/// Scan the transcripts and rewrite the app-owned stats cache
+pub fn rebuild_stats_cache() -> Result<StatsBuildReport, AppError> {
+    let path = built_stats_cache_path().ok_or("Could not find the app data directory")?;
+    let (cache, transcripts_scanned) = build();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::Other(e.to_string()))?;
+    }
+    let contents = serde_json::to_vec(&cache).map_err(|e| AppError::Other(e.to_string()))?;
+    fs::write(&path, contents).map_err(|e| AppError::Other(e.to_string()))?;
+
+    Ok(StatsBuildReport {
+        transcripts_scanned,
+        days: cache.daily_activity.len(),
+        total_sessions: cache.total_sessions,
+        total_messages: cache.total_messages,
+        built_at: cache.built_at,
+    })
+}
+
+fn built_at(contents: &str) -> Option<i64> {
+    serde_json::from_str::<Value>(contents).ok()?["builtAt"].as_i64()
+}
+
+/// Contents of the local stats cache: Claude's own `claude_cache` unless it is
+/// missing or lags the transcripts by more than a day, in which case the
+/// app-built cache is used, rebuilt first if transcripts changed since
+pub fn local_stats_contents(claude_cache: &Path) -> Option<String> {
+    let claude = fs::read_to_string(claude_cache).ok();
+    let Some(newest) = newest_transcript_write() else {
+        return claude;
+    };
+    if claude.is_some()
+        && modified_secs(claude_cache).is_some_and(|m| newest - m < STALE_AFTER_SECONDS)
+    {
+        return claude;
+    }
+
+    let path = built_stats_cache_path()?;
+    let built = fs::read_to_string(&path).ok();
+    if built
+        .as_deref()
+        .and_then(built_at)
+        .is_some_and(|b| b >= newest)
+    {
+        return built;
+    }
+    match rebuild_stats_cache() {
+        Ok(_) => fs::read_to_string(&path).ok(),
+        Err(e) => {
+            eprintln!("Failed to build stats cache from transcripts: {}", e);
+            built.or(claude)
+        }
+    }
+}
+
+/// Rebuild the app-owned stats cache from transcripts now
+#[tauri::command]
+pub async fn rebuild_stats() -> Result<StatsBuildReport, AppError> {
+    tauri::async_runtime::spawn_blocking(rebuild_stats_cache)
+        .await
+        .map_err(|e| AppError::Other(e.to_string()))?
+}
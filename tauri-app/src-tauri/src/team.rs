@@ -0,0 +1,161 @@
+// tauri-app/src-tauri/src/team.rs
+
+use crate::commands::{
+    fetch_usage_breakdown, range_anchor, resolve_time_range, step_and_rate_window, to_time_series,
+};
+use crate::error::AppError;
+use crate::filters::LabelFilters;
+use crate::metrics::{downsample, TimeSeriesPoint, UsageBreakdown};
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::{load_query_tuning, QueryTuning};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// Metrics for shared Prometheus deployments that collect from several users
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamMetrics {
+    pub active_users_over_time: Vec<TimeSeriesPoint>,
+    pub usage_by_os: Vec<UsageBreakdown>,
+    /// Most expensive first
+    pub users: Vec<UserUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserUsage {
+    /// `user_email`, else `user_account_uuid`, else "unknown"
+    pub user: String,
+    pub cost_usd: f64,
+    pub tokens: u64,
+    pub sessions: u64,
+    pub active_time_seconds: f64,
+}
+
+/// Total of `metric` over the range per user
+async fn sum_by_user(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    metric: &str,
+    range: &str,
+    anchor: Option<i64>,
+) -> Result<HashMap<String, f64>, AppError> {
+    let query = format!(
+        "sum by (user_email, user_account_uuid) (increase({}{}))",
+        metric,
+        tuning.selector(metric, range, anchor)
+    );
+    let mut totals = HashMap::new();
+    for result in client.query(&query).await? {
+        let user = ["user_email", "user_account_uuid"]
+            .iter()
+            .filter_map(|label| result.metric.get(*label))
+            .find(|value| !value.is_empty())
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_string());
+        let value = result
+            .value
+            .as_ref()
+            .and_then(|(_, v)| v.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        *totals.entry(user).or_insert(0.0) += value;
+    }
+    Ok(totals)
+}
+
+/// Stores one metric's per-user total on a `UserUsage`
+type SetField = fn(&mut UserUsage, f64);
+
+async fn fetch_user_usage(
+    client: &PrometheusClient,
+    tuning: &QueryTuning,
+    range: &str,
+    anchor: Option<i64>,
+) -> Result<Vec<UserUsage>, AppError> {
+    let sum = |metric| sum_by_user(client, tuning, metric, range, anchor);
+    let (cost, tokens, sessions, active_time) = tokio::join!(
+        sum("claude_code_cost_usage_USD_total"),
+        sum("claude_code_token_usage_tokens_total"),
+        sum("claude_code_session_count_total"),
+        sum("claude_code_active_time_seconds_total"),
+    );
+
+    let fields: [(_, SetField); 4] = [
+        (cost?, |u, v| u.cost_usd = v),
+        (tokens?, |u, v| u.tokens = v.round() as u64),
+        (sessions?, |u, v| u.sessions = v.round() as u64),
+        (active_time?, |u, v| u.active_time_seconds = v),
+    ];
+    let mut users: HashMap<String, UserUsage> = HashMap::new();
+    for (totals, set) in fields {
+        for (name, value) in totals {
+            let user = users.entry(name.clone()).or_insert_with(|| UserUsage {
+                user: name,
+                ..Default::default()
+            });
+            set(user, value);
+        }
+    }
+
+    let mut users: Vec<UserUsage> = users.into_values().collect();
+    users.sort_by(|a, b| {
+        b.cost_usd
+            .partial_cmp(&a.cost_usd)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(users)
+}
+
+#[tauri::command]
+pub async fn get_team_metrics(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+    max_points: Option<usize>,
+) -> Result<TeamMetrics, AppError> {
+    let tuning = load_query_tuning(&app);
+    let client =
+        PrometheusClient::new(&prometheus_url)?.with_lookback_delta(tuning.lookback_delta.clone());
+    let (start_time, end_time, range) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let (step, rate_window) = step_and_rate_window(&time_range, start_time, end_time);
+
+    // Count users with any token usage within each bucket
+    let active_users_query = format!(
+        "count(sum by (user_email) (increase(claude_code_token_usage_tokens_total[{}])) > 0)",
+        tuning.rate_window("claude_code_token_usage_tokens_total", rate_window)
+    );
+    let mut active_users_over_time: Vec<TimeSeriesPoint> = client
+        .query_range(&active_users_query, start_time, end_time, step)
+        .await?
+        .first()
+        .map(to_time_series)
+        .unwrap_or_default();
+
+    if let Some(max_points) = max_points {
+        downsample(&mut active_users_over_time, max_points);
+    }
+
+    let anchor = range_anchor(&time_range, end_time);
+
+    // Group by the os.type resource attribute (macOS/Linux/Windows rollout)
+    let usage_by_os = fetch_usage_breakdown(
+        &client,
+        &tuning,
+        "os_type",
+        &range,
+        anchor,
+        &LabelFilters::default(),
+    )
+    .await?;
+
+    let users = fetch_user_usage(&client, &tuning, &range, anchor).await?;
+
+    Ok(TeamMetrics {
+        active_users_over_time,
+        usage_by_os,
+        users,
+    })
+}
@@ -0,0 +1,35 @@
+// tauri-app/src-tauri/src/test_support.rs
+//
+// Fixtures shared by the unit tests
+
+use std::path::{Path, PathBuf};
+
+/// One history.jsonl line for `session` at `timestamp` (unix milliseconds)
+pub fn history_entry(session: &str, timestamp: i64) -> String {
+    format!(
+        "{{\"display\":\"hi\",\"timestamp\":{},\"project\":\"/work/app\",\"sessionId\":\"{}\"}}\n",
+        timestamp, session
+    )
+}
+
+/// Scratch directory unique to the test and process, removed when dropped
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("ccm-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,151 @@
+// tauri-app/src-tauri/src/timezone.rs
+
+use crate::error::AppError;
+use crate::history_index::clear_history_indexes;
+use crate::settings::{load_setting, save_setting};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const TIMEZONE_KEY: &str = "timezone";
+const WEEK_START_KEY: &str = "weekStart";
+
+/// IANA zone that days are bucketed in; None follows the system clock
+static TIMEZONE: Mutex<Option<Tz>> = Mutex::new(None);
+
+/// First day of the week for weekly periods and budgets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+static WEEK_START: Mutex<WeekStart> = Mutex::new(WeekStart::Monday);
+
+fn configured_week_start() -> WeekStart {
+    WEEK_START.lock().map(|guard| *guard).unwrap_or_default()
+}
+
+fn set_week_start_configured(week_start: WeekStart) {
+    if let Ok(mut guard) = WEEK_START.lock() {
+        *guard = week_start;
+    }
+}
+
+fn configured() -> Option<Tz> {
+    TIMEZONE.lock().ok().and_then(|guard| *guard)
+}
+
+fn set_configured(timezone: Option<Tz>) {
+    if let Ok(mut guard) = TIMEZONE.lock() {
+        *guard = timezone;
+    }
+}
+
+fn parse(name: &str) -> Result<Tz, AppError> {
+    name.parse()
+        .map_err(|_| AppError::InvalidInput(format!("Unknown timezone: {}", name)))
+}
+
+/// Apply the saved timezone and week start to daily and weekly aggregation
+pub fn load_timezone(app: &AppHandle) {
+    let name: Option<String> = load_setting(app, TIMEZONE_KEY);
+    set_configured(name.and_then(|name| parse(&name).ok()));
+    set_week_start_configured(load_setting(app, WEEK_START_KEY));
+}
+
+/// Wall-clock date and time of a unix timestamp in the configured timezone
+pub fn datetime_of(timestamp: i64) -> NaiveDateTime {
+    let Some(utc) = DateTime::from_timestamp(timestamp, 0) else {
+        return NaiveDateTime::default();
+    };
+    match configured() {
+        Some(tz) => utc.with_timezone(&tz).naive_local(),
+        None => utc.with_timezone(&Local).naive_local(),
+    }
+}
+
+/// Calendar date of a unix timestamp in the configured timezone
+pub fn date_of(timestamp: i64) -> NaiveDate {
+    datetime_of(timestamp).date()
+}
+
+/// Today's date in the configured timezone
+pub fn today() -> NaiveDate {
+    date_of(chrono::Utc::now().timestamp())
+}
+
+fn resolve<Z: TimeZone>(tz: &Z, datetime: NaiveDateTime) -> Option<i64> {
+    tz.from_local_datetime(&datetime)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(datetime + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|dt| dt.timestamp())
+}
+
+/// Unix timestamp of a wall-clock date and time in the configured timezone.
+/// Ambiguous times resolve to the earlier instant and times skipped by a DST
+/// change to an hour later.
+pub fn timestamp_of(datetime: NaiveDateTime) -> i64 {
+    let timestamp = match configured() {
+        Some(tz) => resolve(&tz, datetime),
+        None => resolve(&Local, datetime),
+    };
+    timestamp.unwrap_or_default()
+}
+
+/// Unix timestamp of midnight at the start of `date` in the configured timezone
+pub fn start_of_day(date: NaiveDate) -> i64 {
+    timestamp_of(date.and_hms_opt(0, 0, 0).unwrap_or_default())
+}
+
+/// The latest `first_day` on or before `date`
+pub fn week_start_on(date: NaiveDate, first_day: Weekday) -> NaiveDate {
+    let days_back =
+        (date.weekday().num_days_from_monday() + 7 - first_day.num_days_from_monday()) % 7;
+    date - Duration::days(days_back as i64)
+}
+
+/// First day of the week containing `date`
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    let first_day = match configured_week_start() {
+        WeekStart::Monday => Weekday::Mon,
+        WeekStart::Sunday => Weekday::Sun,
+    };
+    week_start_on(date, first_day)
+}
+
+/// The configured IANA timezone name, or None for the system timezone
+#[tauri::command]
+pub async fn get_timezone(app: AppHandle) -> Result<Option<String>, AppError> {
+    Ok(load_setting(&app, TIMEZONE_KEY))
+}
+
+#[tauri::command]
+pub async fn set_timezone(app: AppHandle, timezone: Option<String>) -> Result<(), AppError> {
+    let timezone = timezone.filter(|name| !name.trim().is_empty());
+    let tz = timezone.as_deref().map(parse).transpose()?;
+    save_setting(&app, TIMEZONE_KEY, &timezone)?;
+    set_configured(tz);
+    // Cached history days were bucketed in the old timezone
+    clear_history_indexes();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_week_start(app: AppHandle) -> Result<WeekStart, AppError> {
+    Ok(load_setting(&app, WEEK_START_KEY))
+}
+
+#[tauri::command]
+pub async fn set_week_start(app: AppHandle, week_start: WeekStart) -> Result<(), AppError> {
+    save_setting(&app, WEEK_START_KEY, &week_start)?;
+    set_week_start_configured(week_start);
+    Ok(())
+}
@@ -0,0 +1,219 @@
+// tauri-app/src-tauri/src/transcript_archive.rs
+
+use crate::local_data::{category_dir, collect_files, remove_empty_dirs, DataCategory};
+use crate::processes::scan_claude_processes;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const ARCHIVE_EXTENSION: &str = "zst";
+const COMPRESSION_LEVEL: i32 = 19;
+const DEFAULT_ARCHIVE_AFTER_DAYS: u32 = 30;
+
+/// A transcript JSONL file, either live under ~/.claude/projects or compressed
+/// in the app's archive directory
+#[derive(Debug, Clone)]
+pub struct TranscriptFile {
+    pub session_id: String,
+    /// Encoded project folder name (same layout in both locations)
+    pub project_folder: String,
+    pub path: PathBuf,
+    pub archived: bool,
+}
+
+impl TranscriptFile {
+    /// Line reader over the transcript, decompressing archived files transparently
+    pub fn open(&self) -> Result<Box<dyn BufRead + Send>, String> {
+        let file = File::open(&self.path)
+            .map_err(|e| format!("Failed to open transcript {}: {}", self.session_id, e))?;
+        if self.archived {
+            let decoder = zstd::stream::read::Decoder::new(file).map_err(|e| e.to_string())?;
+            Ok(Box::new(BufReader::new(decoder)))
+        } else {
+            Ok(Box::new(BufReader::new(file)))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveResult {
+    pub files_archived: u32,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+pub fn projects_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".claude").join("projects"))
+}
+
+pub fn archive_dir() -> Option<PathBuf> {
+    category_dir(DataCategory::Archive).map(|d| d.join("transcripts"))
+}
+
+fn transcript_file(root: &Path, path: PathBuf, archived: bool) -> Option<TranscriptFile> {
+    let file_name = path.file_name()?.to_str()?;
+    let session_id = match archived {
+        true => file_name.strip_suffix(".jsonl.zst")?,
+        false => file_name.strip_suffix(".jsonl")?,
+    }
+    .to_string();
+    let project_folder = path
+        .strip_prefix(root)
+        .ok()?
+        .components()
+        .next()?
+        .as_os_str()
+        .to_string_lossy()
+        .to_string();
+
+    Some(TranscriptFile {
+        session_id,
+        project_folder,
+        path,
+        archived,
+    })
+}
+
+/// Every transcript on disk, live ones first. A session that exists in both
+/// places (restored or re-opened after archiving) is only listed once.
+pub fn all_transcripts() -> Vec<TranscriptFile> {
+    let mut transcripts = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (root, archived) in [(projects_dir(), false), (archive_dir(), true)] {
+        let Some(root) = root else {
+            continue;
+        };
+        let mut files = Vec::new();
+        collect_files(&root, &mut files);
+
+        for (path, _, _) in files {
+            if let Some(transcript) = transcript_file(&root, path, archived) {
+                if seen.insert(transcript.session_id.clone()) {
+                    transcripts.push(transcript);
+                }
+            }
+        }
+    }
+
+    transcripts
+}
+
+/// Locate a session's transcript, live or archived
+pub fn find_transcript(session_id: &str) -> Option<TranscriptFile> {
+    all_transcripts()
+        .into_iter()
+        .find(|t| t.session_id == session_id)
+}
+
+fn compress_file(source: &Path, destination: &Path) -> Result<u64, String> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    // Write to a temp name first so a crash never leaves a truncated archive
+    let tmp_path = destination.with_extension("zst.tmp");
+    let input = File::open(source).map_err(|e| e.to_string())?;
+    let output = File::create(&tmp_path).map_err(|e| e.to_string())?;
+    zstd::stream::copy_encode(input, output, COMPRESSION_LEVEL).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, destination).map_err(|e| e.to_string())?;
+
+    Ok(fs::metadata(destination).map_err(|e| e.to_string())?.len())
+}
+
+/// Compress transcripts untouched for `older_than_days` into the archive directory
+/// and remove the originals. Sessions with a running Claude process are skipped.
+fn archive_old_transcripts(older_than_days: u32) -> Result<ArchiveResult, String> {
+    let projects_root = projects_dir().ok_or("Could not find home directory")?;
+    let archive_root = archive_dir().ok_or("Could not find app data directory")?;
+
+    let live_sessions: HashSet<String> = scan_claude_processes()
+        .into_iter()
+        .filter_map(|p| p.session_id)
+        .collect();
+    let cutoff = SystemTime::now() - Duration::from_secs(older_than_days as u64 * 24 * 3600);
+
+    let mut files = Vec::new();
+    collect_files(&projects_root, &mut files);
+
+    let mut result = ArchiveResult::default();
+    for (path, size, modified) in files {
+        if modified >= cutoff {
+            continue;
+        }
+        let Some(transcript) = transcript_file(&projects_root, path, false) else {
+            continue;
+        };
+        if live_sessions.contains(&transcript.session_id) {
+            continue;
+        }
+
+        let relative = transcript
+            .path
+            .strip_prefix(&projects_root)
+            .map_err(|e| e.to_string())?;
+        let destination = archive_root
+            .join(relative)
+            .with_extension(format!("jsonl.{}", ARCHIVE_EXTENSION));
+
+        let compressed_size = compress_file(&transcript.path, &destination)?;
+        fs::remove_file(&transcript.path).map_err(|e| e.to_string())?;
+
+        result.files_archived += 1;
+        result.bytes_before += size;
+        result.bytes_after += compressed_size;
+    }
+
+    remove_empty_dirs(&projects_root);
+    Ok(result)
+}
+
+/// Decompress an archived transcript back into ~/.claude/projects (e.g. to resume it)
+fn restore_archived_transcript(session_id: &str) -> Result<(), String> {
+    let transcript = find_transcript(session_id)
+        .filter(|t| t.archived)
+        .ok_or(format!("No archived transcript for session {}", session_id))?;
+    let projects_root = projects_dir().ok_or("Could not find home directory")?;
+    let archive_root = archive_dir().ok_or("Could not find app data directory")?;
+
+    // Mirror archive_old_transcripts: same relative path, minus the .zst suffix,
+    // so subagent transcripts nested under a session folder land back in place
+    let relative = transcript
+        .path
+        .strip_prefix(&archive_root)
+        .map_err(|e| e.to_string())?;
+    let destination = projects_root.join(relative).with_extension("");
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    // Write to a temp name first so a crash never leaves a truncated transcript
+    let tmp_path = destination.with_extension("jsonl.tmp");
+    let mut reader = transcript.open()?;
+    let mut output = File::create(&tmp_path).map_err(|e| e.to_string())?;
+    std::io::copy(&mut reader, &mut output).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, &destination).map_err(|e| e.to_string())?;
+
+    fs::remove_file(&transcript.path).map_err(|e| e.to_string())?;
+    remove_empty_dirs(&archive_root);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn archive_transcripts(older_than_days: Option<u32>) -> Result<ArchiveResult, String> {
+    let days = older_than_days.unwrap_or(DEFAULT_ARCHIVE_AFTER_DAYS).max(1);
+    tauri::async_runtime::spawn_blocking(move || archive_old_transcripts(days))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn restore_transcript(session_id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || restore_archived_transcript(&session_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
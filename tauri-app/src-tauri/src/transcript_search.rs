@@ -0,0 +1,330 @@
+// tauri-app/src-tauri/src/transcript_search.rs
+
+use crate::claude_storage::project_names;
+use crate::local_data::{category_dir, DataCategory};
+use crate::transcript_archive::{all_transcripts, TranscriptFile};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+const INDEX_FILE: &str = "transcript-search-index.json";
+const MIN_TERM_LEN: usize = 2;
+const MAX_SNIPPETS_PER_SESSION: usize = 3;
+const SNIPPET_CONTEXT_CHARS: usize = 80;
+const DEFAULT_RESULT_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedTranscript {
+    session_id: String,
+    project_folder: String,
+    path: PathBuf,
+    archived: bool,
+    modified: i64,
+    size: u64,
+}
+
+/// Inverted index from lowercase word to the transcripts containing it. Only
+/// used to narrow candidates; matches are confirmed against the transcript text.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SearchIndex {
+    transcripts: Vec<Option<IndexedTranscript>>,
+    postings: HashMap<String, HashSet<u32>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSearchHit {
+    pub session_id: String,
+    pub project: Option<String>,
+    pub project_path: Option<String>,
+    pub match_count: u32,
+    pub snippets: Vec<String>,
+    /// Transcript last modified, in milliseconds since the epoch
+    pub last_modified: i64,
+}
+
+static SEARCH_INDEX: Mutex<Option<SearchIndex>> = Mutex::new(None);
+
+fn index_path() -> Option<PathBuf> {
+    category_dir(DataCategory::Index).map(|d| d.join(INDEX_FILE))
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= MIN_TERM_LEN)
+        .map(|w| w.to_lowercase())
+}
+
+/// Collect the human-readable text of a transcript entry (message content,
+/// text blocks, tool input/output strings)
+fn collect_text(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(s) => out.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|v| collect_text(v, out)),
+        Value::Object(map) => {
+            for key in ["content", "text", "input", "output"] {
+                if let Some(v) = map.get(key) {
+                    collect_text(v, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Text of each message in a transcript, one entry per JSONL line
+fn transcript_messages(transcript: &TranscriptFile) -> Vec<String> {
+    let Ok(reader) = transcript.open() else {
+        return Vec::new();
+    };
+
+    reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .filter_map(|entry| {
+            let mut parts = Vec::new();
+            collect_text(entry.get("message")?, &mut parts);
+            (!parts.is_empty()).then(|| parts.join(" "))
+        })
+        .collect()
+}
+
+fn modified_millis(transcript: &TranscriptFile) -> (i64, u64) {
+    fs::metadata(&transcript.path)
+        .map(|m| {
+            let modified = m
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            (modified, m.len())
+        })
+        .unwrap_or((0, 0))
+}
+
+impl SearchIndex {
+    fn load() -> Self {
+        index_path()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let Some(path) = index_path() else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(bytes) = serde_json::to_vec(self) {
+            let _ = fs::write(path, bytes);
+        }
+    }
+
+    fn remove_postings(&mut self, id: u32) {
+        self.postings.retain(|_, ids| {
+            ids.remove(&id);
+            !ids.is_empty()
+        });
+    }
+
+    fn index_transcript(&mut self, id: u32, transcript: &TranscriptFile) {
+        for message in transcript_messages(transcript) {
+            for term in tokenize(&message) {
+                self.postings.entry(term).or_default().insert(id);
+            }
+        }
+    }
+
+    /// Re-index transcripts that are new or changed since the last refresh and drop
+    /// ones that no longer exist. Returns whether anything changed.
+    fn refresh(&mut self) -> bool {
+        let mut changed = false;
+        let current = all_transcripts();
+        let current_ids: HashSet<&str> = current.iter().map(|t| t.session_id.as_str()).collect();
+
+        for id in 0..self.transcripts.len() as u32 {
+            let stale = self.transcripts[id as usize]
+                .as_ref()
+                .is_some_and(|t| !current_ids.contains(t.session_id.as_str()));
+            if stale {
+                self.remove_postings(id);
+                self.transcripts[id as usize] = None;
+                changed = true;
+            }
+        }
+
+        let positions: HashMap<String, usize> = self
+            .transcripts
+            .iter()
+            .enumerate()
+            .filter_map(|(id, t)| t.as_ref().map(|t| (t.session_id.clone(), id)))
+            .collect();
+
+        for transcript in current {
+            let (modified, size) = modified_millis(&transcript);
+            let existing = positions.get(&transcript.session_id).copied();
+
+            let id = match existing {
+                Some(id) => {
+                    let indexed = self.transcripts[id].as_ref();
+                    if indexed.is_some_and(|t| {
+                        t.path == transcript.path && t.modified == modified && t.size == size
+                    }) {
+                        continue;
+                    }
+                    self.remove_postings(id as u32);
+                    id as u32
+                }
+                None => {
+                    self.transcripts.push(None);
+                    (self.transcripts.len() - 1) as u32
+                }
+            };
+
+            self.index_transcript(id, &transcript);
+            self.transcripts[id as usize] = Some(IndexedTranscript {
+                session_id: transcript.session_id,
+                project_folder: transcript.project_folder,
+                path: transcript.path,
+                archived: transcript.archived,
+                modified,
+                size,
+            });
+            changed = true;
+        }
+
+        changed
+    }
+
+    fn candidates(&self, terms: &[String]) -> Vec<u32> {
+        let mut postings: Vec<&HashSet<u32>> = Vec::new();
+        for term in terms {
+            match self.postings.get(term) {
+                Some(ids) => postings.push(ids),
+                None => return Vec::new(),
+            }
+        }
+        postings.sort_by_key(|ids| ids.len());
+
+        let Some((first, rest)) = postings.split_first() else {
+            return Vec::new();
+        };
+        first
+            .iter()
+            .copied()
+            .filter(|id| rest.iter().all(|ids| ids.contains(id)))
+            .collect()
+    }
+}
+
+/// Cut a snippet of text around the first occurrence of `needle`
+fn snippet(text: &str, lower: &str, needle: &str) -> Option<String> {
+    let start = lower.find(needle)?;
+    // Lowercasing can change byte lengths, so map back through char offsets
+    let char_start = lower[..start].chars().count();
+    let chars: Vec<char> = text.chars().collect();
+    let from = char_start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (char_start + needle.chars().count() + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+    let mut snippet: String = chars[from..to.max(from)].iter().collect();
+    snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
+
+fn search(query: &str, limit: usize) -> Result<Vec<TranscriptSearchHit>, String> {
+    let needle = query.trim().to_lowercase();
+    let terms: Vec<String> = tokenize(&needle).collect();
+    if terms.is_empty() {
+        return Err(format!(
+            "Search query needs at least one word of {} or more characters",
+            MIN_TERM_LEN
+        ));
+    }
+
+    let mut guard = SEARCH_INDEX.lock().map_err(|e| e.to_string())?;
+    let index = guard.get_or_insert_with(SearchIndex::load);
+    if index.refresh() {
+        index.save();
+    }
+
+    let names = project_names();
+    let mut hits: Vec<TranscriptSearchHit> = index
+        .candidates(&terms)
+        .into_iter()
+        .filter_map(|id| index.transcripts[id as usize].clone())
+        .filter_map(|indexed| {
+            let transcript = TranscriptFile {
+                session_id: indexed.session_id.clone(),
+                project_folder: indexed.project_folder.clone(),
+                path: indexed.path.clone(),
+                archived: indexed.archived,
+            };
+
+            // Confirm the whole query appears as a phrase, not just its words
+            let mut match_count = 0;
+            let mut snippets = Vec::new();
+            for message in transcript_messages(&transcript) {
+                let lower = message.to_lowercase();
+                if !lower.contains(&needle) {
+                    continue;
+                }
+                match_count += lower.matches(&needle).count() as u32;
+                if snippets.len() < MAX_SNIPPETS_PER_SESSION {
+                    snippets.extend(snippet(&message, &lower, &needle));
+                }
+            }
+            if match_count == 0 {
+                return None;
+            }
+
+            let project_path = names.get(&indexed.project_folder).cloned();
+            Some(TranscriptSearchHit {
+                session_id: indexed.session_id,
+                project: project_path.as_deref().map(|p| {
+                    std::path::Path::new(p)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| p.to_string())
+                }),
+                project_path,
+                match_count,
+                snippets,
+                last_modified: indexed.modified,
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.match_count
+            .cmp(&a.match_count)
+            .then_with(|| b.last_modified.cmp(&a.last_modified))
+    });
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+#[tauri::command]
+pub async fn search_transcripts(
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<TranscriptSearchHit>, String> {
+    let limit = limit.unwrap_or(DEFAULT_RESULT_LIMIT);
+    tauri::async_runtime::spawn_blocking(move || search(&query, limit))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,290 @@
+// tauri-app/src-tauri/src/transcripts.rs
+
+use crate::error::AppError;
+use crate::pricing::{resolve_profile, ModelRates, PricingProfile};
+use crate::settings::frontend_pricing_provider;
+use crate::transcript_archive::{all_transcripts, find_transcript, TranscriptFile};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufRead;
+use tauri::AppHandle;
+
+/// Token usage reported on an assistant message in a transcript
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct Usage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+}
+
+impl Usage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_read_input_tokens
+            + self.cache_creation_input_tokens
+    }
+
+    pub fn cost(&self, rates: ModelRates) -> f64 {
+        (self.input_tokens as f64 * rates.input
+            + self.output_tokens as f64 * rates.output
+            + self.cache_read_input_tokens as f64 * rates.cache_read
+            + self.cache_creation_input_tokens as f64 * rates.cache_creation)
+            / 1_000_000.0
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranscriptMessage {
+    pub id: Option<String>,
+    pub model: Option<String>,
+    pub usage: Option<Usage>,
+    #[serde(default)]
+    pub content: Value,
+}
+
+/// One line of a Claude Code conversation JSONL file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptEntry {
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub session_id: Option<String>,
+    pub request_id: Option<String>,
+    pub message: Option<TranscriptMessage>,
+}
+
+/// Parsed entries of a transcript, skipping lines that aren't valid JSON
+pub fn read_entries(reader: impl BufRead) -> impl Iterator<Item = TranscriptEntry> {
+    reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+}
+
+/// Streamed assistant messages are written once per content block, each
+/// repeating the message's usage. Tracks which (message id, request id) pairs
+/// have been counted so every response is counted once.
+#[derive(Debug, Default)]
+pub struct UsageDedupe {
+    seen: HashSet<(String, Option<String>)>,
+    pub duplicates: u32,
+}
+
+impl UsageDedupe {
+    /// Whether this entry's usage should be counted; entries without a message
+    /// id can't be matched and always count
+    pub fn first_sighting(&mut self, entry: &TranscriptEntry) -> bool {
+        let Some(id) = entry.message.as_ref().and_then(|m| m.id.clone()) else {
+            return true;
+        };
+        let first = self.seen.insert((id, entry.request_id.clone()));
+        if !first {
+            self.duplicates += 1;
+        }
+        first
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelUsageTotals {
+    pub model: String,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallCount {
+    pub tool: String,
+    pub count: u32,
+}
+
+/// Token and cost totals of one session, from its transcript alone
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUsage {
+    pub session_id: String,
+    pub project_folder: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+    /// User and assistant messages, streamed repeats counted once
+    pub messages: u32,
+    /// Assistant responses with a usage block
+    pub responses: u32,
+    pub by_model: Vec<ModelUsageTotals>,
+    /// Tool calls by tool name, most used first
+    pub tool_calls: Vec<ToolCallCount>,
+    pub first_activity: Option<i64>,
+    pub last_activity: Option<i64>,
+    pub duplicate_entries_skipped: u32,
+}
+
+/// Running totals of one transcript, fed entry by entry so lines appended
+/// later can be added without re-reading the file
+pub struct UsageAccumulator {
+    pricing: PricingProfile,
+    totals: SessionUsage,
+    by_model: BTreeMap<String, ModelUsageTotals>,
+    tool_calls: BTreeMap<String, u32>,
+    seen_messages: UsageDedupe,
+    dedupe: UsageDedupe,
+}
+
+impl UsageAccumulator {
+    pub fn new(transcript: &TranscriptFile, pricing: PricingProfile) -> Self {
+        Self {
+            pricing,
+            totals: SessionUsage {
+                session_id: transcript.session_id.clone(),
+                project_folder: transcript.project_folder.clone(),
+                ..Default::default()
+            },
+            by_model: BTreeMap::new(),
+            tool_calls: BTreeMap::new(),
+            seen_messages: UsageDedupe::default(),
+            dedupe: UsageDedupe::default(),
+        }
+    }
+
+    pub fn add(&mut self, entry: TranscriptEntry) {
+        let totals = &mut self.totals;
+        if let Some(timestamp) = entry.timestamp.map(|t| t.timestamp()) {
+            totals.first_activity.get_or_insert(timestamp);
+            totals.last_activity = totals.last_activity.max(Some(timestamp));
+        }
+        let is_message = matches!(entry.kind.as_deref(), Some("user" | "assistant"));
+        if is_message && entry.message.is_some() && self.seen_messages.first_sighting(&entry) {
+            totals.messages += 1;
+        }
+        // Each streamed entry carries different content blocks, so every entry is counted
+        let content = entry.message.as_ref().and_then(|m| m.content.as_array());
+        for item in content.into_iter().flatten() {
+            if item["type"] == "tool_use" {
+                let tool = item["name"].as_str().unwrap_or("unknown");
+                *self.tool_calls.entry(tool.to_string()).or_default() += 1;
+            }
+        }
+        let Some(usage) = entry.message.as_ref().and_then(|m| m.usage) else {
+            return;
+        };
+        if !self.dedupe.first_sighting(&entry) {
+            return;
+        }
+        let model = entry
+            .message
+            .and_then(|m| m.model)
+            .unwrap_or_else(|| "unknown".to_string());
+        let cost = usage.cost(self.pricing.rates_for(&model));
+
+        totals.input_tokens += usage.input_tokens;
+        totals.output_tokens += usage.output_tokens;
+        totals.cache_read_tokens += usage.cache_read_input_tokens;
+        totals.cache_creation_tokens += usage.cache_creation_input_tokens;
+        totals.total_tokens += usage.total();
+        totals.cost_usd += cost;
+        totals.responses += 1;
+
+        let model_totals = self
+            .by_model
+            .entry(model.clone())
+            .or_insert(ModelUsageTotals {
+                model,
+                ..Default::default()
+            });
+        model_totals.tokens += usage.total();
+        model_totals.cost_usd += cost;
+    }
+
+    /// Totals of every entry added so far
+    pub fn usage(&self) -> SessionUsage {
+        let mut totals = self.totals.clone();
+        totals.by_model = self.by_model.values().cloned().collect();
+        totals
+            .by_model
+            .sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+        totals.tool_calls = self
+            .tool_calls
+            .iter()
+            .map(|(tool, count)| ToolCallCount {
+                tool: tool.clone(),
+                count: *count,
+            })
+            .collect();
+        totals
+            .tool_calls
+            .sort_by_key(|t| std::cmp::Reverse(t.count));
+        totals.duplicate_entries_skipped = self.dedupe.duplicates;
+        totals
+    }
+}
+
+/// Total a transcript's deduplicated usage, priced per model
+pub fn session_usage(
+    transcript: &TranscriptFile,
+    pricing: &PricingProfile,
+) -> Result<SessionUsage, AppError> {
+    let reader = transcript.open().map_err(AppError::FileNotFound)?;
+    let mut accumulator = UsageAccumulator::new(transcript, pricing.clone());
+    for entry in read_entries(reader) {
+        accumulator.add(entry);
+    }
+    Ok(accumulator.usage())
+}
+
+/// Usage of one session computed from its transcript, independent of Prometheus
+#[tauri::command]
+pub async fn get_transcript_session_usage(
+    app: AppHandle,
+    session_id: String,
+    pricing_provider: Option<String>,
+) -> Result<SessionUsage, AppError> {
+    let pricing = resolve_profile(
+        &app,
+        &pricing_provider.unwrap_or_else(|| frontend_pricing_provider(&app)),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        let transcript = find_transcript(&session_id).ok_or_else(|| {
+            AppError::FileNotFound(format!("No transcript for session {}", session_id))
+        })?;
+        session_usage(&transcript, &pricing)
+    })
+    .await
+    .map_err(|e| AppError::Other(e.to_string()))?
+}
+
+/// Usage of every session with a transcript, most expensive first
+#[tauri::command]
+pub async fn get_transcript_usage(
+    app: AppHandle,
+    pricing_provider: Option<String>,
+) -> Result<Vec<SessionUsage>, AppError> {
+    let pricing = resolve_profile(
+        &app,
+        &pricing_provider.unwrap_or_else(|| frontend_pricing_provider(&app)),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sessions: Vec<SessionUsage> = all_transcripts()
+            .iter()
+            .filter_map(|t| session_usage(t, &pricing).ok())
+            .filter(|s| s.responses > 0)
+            .collect();
+        sessions.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+        sessions
+    })
+    .await
+    .map_err(|e| AppError::Other(e.to_string()))
+}
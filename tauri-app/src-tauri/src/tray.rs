@@ -1,3 +1,5 @@
+use crate::alertmanager::tray_alert_count;
+use crate::budgets::{tray_budget_state, BudgetState};
 use std::sync::Mutex;
 use tauri::{tray::TrayIcon, State};
 
@@ -34,7 +36,16 @@ pub async fn update_tray_stats(
     if let Some(tray) = tray_guard.as_ref() {
         // Green circle for connected, red for disconnected
         let status_indicator = if is_connected { "🟢" } else { "🔴" };
-        let title = format!("{} {}", status_indicator, format_cost_short(total_cost));
+        let mut title = format!("{} {}", status_indicator, format_cost_short(total_cost));
+        let alerts = tray_alert_count();
+        if alerts > 0 {
+            title.push_str(&format!(" ⚠️{}", alerts));
+        }
+        match tray_budget_state() {
+            Some(BudgetState::Exceeded) => title.push_str(" 🛑"),
+            Some(BudgetState::Warning) => title.push_str(" 🟡"),
+            _ => {}
+        }
         tray.set_title(Some(&title))
             .map_err(|e| format!("Failed to set tray title: {}", e))?;
     }
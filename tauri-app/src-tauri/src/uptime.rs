@@ -0,0 +1,166 @@
+// tauri-app/src-tauri/src/uptime.rs
+
+use crate::events::publish;
+use crate::settings::{frontend_prometheus_url, load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const UPTIME_KEY: &str = "prometheusUptime";
+
+/// Checks further apart than this mean the app wasn't running, so the gap is
+/// left unknown instead of extending the previous segment across it
+const MAX_CHECK_GAP_MS: i64 = 10 * 60 * 1000;
+
+/// How long segments are kept
+const RETENTION_MS: i64 = 90 * 24 * 3600 * 1000;
+
+/// Checks are batched in memory and written at most this often, unless the
+/// outcome changed
+const SAVE_INTERVAL_MS: i64 = 5 * 60 * 1000;
+
+/// A run of consecutive checks with the same outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeSegment {
+    pub start: i64,
+    pub end: i64,
+    pub up: bool,
+    pub url: String,
+    pub checks: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeTimeline {
+    pub segments: Vec<UptimeSegment>,
+    pub up_seconds: i64,
+    pub down_seconds: i64,
+    /// Share of monitored time Prometheus was reachable (None if never checked)
+    pub uptime_percent: Option<f64>,
+}
+
+/// Segments loaded from the store, with the time they were last saved
+struct UptimeStore {
+    segments: Vec<UptimeSegment>,
+    saved_at: i64,
+}
+
+static UPTIME: Mutex<Option<UptimeStore>> = Mutex::new(None);
+
+fn normalize(url: &str) -> &str {
+    url.trim().trim_end_matches('/')
+}
+
+/// Record the outcome of a connection/health check of the configured Prometheus,
+/// publishing `prometheus-down` or `prometheus-up` when it differs from the
+/// previous check. Checks of other URLs (e.g. one typed into settings but not
+/// yet saved) are ignored.
+pub fn record_check(app: &AppHandle, url: &str, up: bool, error: Option<String>) {
+    if normalize(url) != normalize(&frontend_prometheus_url(app)) {
+        return;
+    }
+    let Ok(mut guard) = UPTIME.lock() else {
+        return;
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    let store = guard.get_or_insert_with(|| UptimeStore {
+        segments: load_setting(app, UPTIME_KEY),
+        saved_at: now,
+    });
+    let segments = &mut store.segments;
+    let changed = segments
+        .last()
+        .is_some_and(|last| last.url == url && last.up != up);
+    if changed {
+        let event = if up {
+            "prometheus-up"
+        } else {
+            "prometheus-down"
+        };
+        publish(app, event, json!({ "url": url, "error": error }));
+    }
+
+    match segments.last_mut() {
+        Some(last) if last.up == up && last.url == url && now - last.end <= MAX_CHECK_GAP_MS => {
+            last.end = now;
+            last.checks += 1;
+            if error.is_some() {
+                last.last_error = error;
+            }
+        }
+        _ => segments.push(UptimeSegment {
+            start: now,
+            end: now,
+            up,
+            url: url.to_string(),
+            checks: 1,
+            last_error: error,
+        }),
+    }
+
+    segments.retain(|s| now - s.end <= RETENTION_MS);
+    if changed || now - store.saved_at >= SAVE_INTERVAL_MS {
+        let _ = save_setting(app, UPTIME_KEY, &store.segments);
+        store.saved_at = now;
+    }
+}
+
+/// Recorded segments, including checks not yet written to the store
+fn current_segments(app: &AppHandle) -> Vec<UptimeSegment> {
+    match UPTIME.lock().ok().as_deref() {
+        Some(Some(store)) => store.segments.clone(),
+        _ => load_setting(app, UPTIME_KEY),
+    }
+}
+
+#[tauri::command]
+pub async fn get_uptime_timeline(
+    app: AppHandle,
+    start: Option<i64>,
+    end: Option<i64>,
+) -> Result<UptimeTimeline, String> {
+    let start = start.unwrap_or(i64::MIN);
+    let end = end.unwrap_or(i64::MAX);
+
+    // Clip segments to the requested window (timestamps in milliseconds)
+    let segments: Vec<UptimeSegment> = current_segments(&app)
+        .into_iter()
+        .filter(|s| s.end >= start && s.start <= end)
+        .map(|mut s| {
+            s.start = s.start.max(start);
+            s.end = s.end.min(end);
+            s
+        })
+        .collect();
+
+    let seconds = |up: bool| -> i64 {
+        segments
+            .iter()
+            .filter(|s| s.up == up)
+            .map(|s| (s.end - s.start) / 1000)
+            .sum()
+    };
+    let up_seconds = seconds(true);
+    let down_seconds = seconds(false);
+    let uptime_percent = (!segments.is_empty()).then(|| {
+        let total = up_seconds + down_seconds;
+        if total == 0 {
+            // Only single checks so far; fall back to counting them
+            let up_checks: u32 = segments.iter().filter(|s| s.up).map(|s| s.checks).sum();
+            let all_checks: u32 = segments.iter().map(|s| s.checks).sum();
+            up_checks as f64 / all_checks as f64 * 100.0
+        } else {
+            up_seconds as f64 / total as f64 * 100.0
+        }
+    });
+
+    Ok(UptimeTimeline {
+        segments,
+        up_seconds,
+        down_seconds,
+        uptime_percent,
+    })
+}
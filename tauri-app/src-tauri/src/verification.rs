@@ -0,0 +1,263 @@
+// tauri-app/src-tauri/src/verification.rs
+
+use crate::commands::resolve_time_range;
+use crate::pricing::{resolve_profile, PricingProfile};
+use crate::prometheus::PrometheusClient;
+use crate::query_tuning::format_duration;
+use crate::timezone;
+use crate::transcript_archive::all_transcripts;
+use crate::transcripts::{read_entries, UsageDedupe};
+use chrono::{DateTime, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::UNIX_EPOCH;
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DayUsage {
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptTotals {
+    pub days: BTreeMap<NaiveDate, DayUsage>,
+    pub transcripts_scanned: u32,
+    pub duplicate_entries: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayVerification {
+    pub date: String,
+    pub transcript_tokens: u64,
+    pub prometheus_tokens: u64,
+    pub token_difference: i64,
+    /// Difference relative to the transcript total (0 when both are zero)
+    pub token_difference_percent: f64,
+    pub transcript_cost_usd: f64,
+    pub prometheus_cost_usd: f64,
+    pub cost_difference_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+    pub start: i64,
+    pub end: i64,
+    pub days: Vec<DayVerification>,
+    pub transcript_tokens: u64,
+    pub prometheus_tokens: u64,
+    pub transcript_cost_usd: f64,
+    pub prometheus_cost_usd: f64,
+    pub transcripts_scanned: u32,
+    /// Streamed messages repeat their usage block; repeats are counted once
+    pub duplicate_entries_skipped: u32,
+    pub prometheus_available: bool,
+}
+
+/// Token usage and estimated cost of one assistant message in a transcript
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    pub timestamp: DateTime<chrono::Utc>,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Visit every transcript usage block between `start` and `end` (unix seconds),
+/// counting streamed repeats of a message once. Returns (transcripts scanned,
+/// duplicate entries skipped).
+pub fn for_each_usage(
+    start: i64,
+    end: i64,
+    pricing: &PricingProfile,
+    mut visit: impl FnMut(UsageRecord),
+) -> (u32, u32) {
+    let mut scanned = 0;
+    let mut dedupe = UsageDedupe::default();
+
+    for transcript in all_transcripts() {
+        // A file last written before the range can't contain entries in it
+        let modified = std::fs::metadata(&transcript.path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64);
+        if modified.is_some_and(|m| m < start) {
+            continue;
+        }
+        let Ok(reader) = transcript.open() else {
+            continue;
+        };
+        scanned += 1;
+
+        for entry in read_entries(reader) {
+            let Some(timestamp) = entry.timestamp else {
+                continue;
+            };
+            let Some(usage) = entry.message.as_ref().and_then(|m| m.usage) else {
+                continue;
+            };
+            if timestamp.timestamp() < start || timestamp.timestamp() >= end {
+                continue;
+            }
+            if !dedupe.first_sighting(&entry) {
+                continue;
+            }
+
+            let model = entry.message.and_then(|m| m.model).unwrap_or_default();
+            visit(UsageRecord {
+                timestamp,
+                tokens: usage.total(),
+                cost_usd: usage.cost(pricing.rates_for(&model)),
+            });
+        }
+    }
+
+    (scanned, dedupe.duplicates)
+}
+
+/// Recompute per-day token and cost totals from transcript usage blocks for
+/// entries between `start` and `end` (unix seconds)
+pub fn transcript_totals(start: i64, end: i64, pricing: &PricingProfile) -> TranscriptTotals {
+    let mut days: BTreeMap<NaiveDate, DayUsage> = BTreeMap::new();
+    let (transcripts_scanned, duplicate_entries) = for_each_usage(start, end, pricing, |record| {
+        let day = days
+            .entry(timezone::date_of(record.timestamp.timestamp()))
+            .or_default();
+        day.tokens += record.tokens;
+        day.cost_usd += record.cost_usd;
+    });
+
+    TranscriptTotals {
+        days,
+        transcripts_scanned,
+        duplicate_entries,
+    }
+}
+
+/// Calendar days in the configured timezone overlapping [start, end) as
+/// (date, day start, day end) in unix seconds
+pub fn local_days(start: i64, end: i64) -> Vec<(NaiveDate, i64, i64)> {
+    let mut days = Vec::new();
+    let mut date = timezone::date_of(start);
+    loop {
+        let day_start = timezone::start_of_day(date).max(start);
+        if day_start >= end {
+            break;
+        }
+        let next = date + Duration::days(1);
+        let day_end = timezone::start_of_day(next).min(end);
+        days.push((date, day_start, day_end));
+        date = next;
+    }
+    days
+}
+
+/// Sum of a counter's increase over [day_start, day_end)
+pub async fn prometheus_day_total(
+    client: &PrometheusClient,
+    metric: &str,
+    day_start: i64,
+    day_end: i64,
+) -> Result<f64, String> {
+    let query = format!(
+        "sum(increase({}[{}]))",
+        metric,
+        format_duration(day_end - day_start)
+    );
+    // A single-point range query evaluates the expression at `day_end`
+    let results = client
+        .query_range(&query, day_end, day_end, "60s")
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(results
+        .first()
+        .and_then(|r| r.values.as_ref())
+        .and_then(|v| v.last())
+        .and_then(|(_, v)| v.parse::<f64>().ok())
+        .unwrap_or(0.0))
+}
+
+fn percent_difference(expected: f64, actual: f64) -> f64 {
+    if expected == 0.0 {
+        if actual == 0.0 {
+            0.0
+        } else {
+            100.0
+        }
+    } else {
+        (actual - expected) / expected * 100.0
+    }
+}
+
+#[tauri::command]
+pub async fn verify_usage_totals(
+    app: AppHandle,
+    time_range: String,
+    prometheus_url: String,
+    custom_start: Option<i64>,
+    custom_end: Option<i64>,
+    pricing_provider: Option<String>,
+) -> Result<VerificationReport, String> {
+    let (start, end, _) = resolve_time_range(&time_range, custom_start, custom_end)?;
+    let pricing = resolve_profile(&app, pricing_provider.as_deref().unwrap_or_default());
+
+    let totals =
+        tauri::async_runtime::spawn_blocking(move || transcript_totals(start, end, &pricing))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let client = PrometheusClient::new(&prometheus_url).map_err(|e| e.to_string())?;
+    let prometheus_available = client.test_connection().await.unwrap_or(false);
+
+    let mut days = Vec::new();
+    for (date, day_start, day_end) in local_days(start, end) {
+        let local = totals.days.get(&date).copied().unwrap_or_default();
+        let (prometheus_tokens, prometheus_cost) = if prometheus_available {
+            (
+                prometheus_day_total(
+                    &client,
+                    "claude_code_token_usage_tokens_total",
+                    day_start,
+                    day_end,
+                )
+                .await?,
+                prometheus_day_total(
+                    &client,
+                    "claude_code_cost_usage_USD_total",
+                    day_start,
+                    day_end,
+                )
+                .await?,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        days.push(DayVerification {
+            date: date.format("%Y-%m-%d").to_string(),
+            transcript_tokens: local.tokens,
+            prometheus_tokens: prometheus_tokens.round() as u64,
+            token_difference: prometheus_tokens.round() as i64 - local.tokens as i64,
+            token_difference_percent: percent_difference(local.tokens as f64, prometheus_tokens),
+            transcript_cost_usd: local.cost_usd,
+            prometheus_cost_usd: prometheus_cost,
+            cost_difference_usd: prometheus_cost - local.cost_usd,
+        });
+    }
+
+    Ok(VerificationReport {
+        start,
+        end,
+        transcript_tokens: days.iter().map(|d| d.transcript_tokens).sum(),
+        prometheus_tokens: days.iter().map(|d| d.prometheus_tokens).sum(),
+        transcript_cost_usd: days.iter().map(|d| d.transcript_cost_usd).sum(),
+        prometheus_cost_usd: days.iter().map(|d| d.prometheus_cost_usd).sum(),
+        days,
+        transcripts_scanned: totals.transcripts_scanned,
+        duplicate_entries_skipped: totals.duplicate_entries,
+        prometheus_available,
+    })
+}
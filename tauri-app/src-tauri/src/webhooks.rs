@@ -0,0 +1,145 @@
+// tauri-app/src-tauri/src/webhooks.rs
+
+use crate::alerts::{webhook_body, WebhookFormat};
+use crate::settings::{load_setting, save_setting};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::AppHandle;
+
+const WEBHOOKS_KEY: &str = "webhooks";
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Events a webhook can subscribe to
+pub const WEBHOOK_EVENTS: [&str; 9] = [
+    "session-started",
+    "session-idle",
+    "session-resumed",
+    "session-ended",
+    "session-cost-threshold",
+    "session-runaway",
+    "budget-exceeded",
+    "prometheus-down",
+    "prometheus-up",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// Event names to deliver (empty = all)
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Sent as the X-Webhook-Secret header so the receiver can verify the sender
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Slack and Discord webhooks get a formatted message instead of the raw event
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl WebhookConfig {
+    fn subscribes_to(&self, event: &str) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.iter().any(|e| e == event))
+    }
+}
+
+fn load_webhooks(app: &AppHandle) -> Vec<WebhookConfig> {
+    load_setting(app, WEBHOOKS_KEY)
+}
+
+pub(crate) async fn deliver(webhook: &WebhookConfig, body: &Value) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut request = client.post(&webhook.url).json(body);
+    if let Some(secret) = &webhook.secret {
+        request = request.header("X-Webhook-Secret", secret);
+    }
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook returned {}", response.status()));
+    }
+    Ok(())
+}
+
+pub(crate) fn envelope(event: &str, payload: &Value) -> Value {
+    json!({
+        "event": event,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "data": payload,
+    })
+}
+
+/// POST the event to every enabled webhook subscribed to it, in the background.
+/// Slack and Discord webhooks are skipped when `notify` is false (quiet hours).
+pub fn dispatch(app: &AppHandle, event: &str, payload: &Value, notify: bool) {
+    let webhooks: Vec<WebhookConfig> = load_webhooks(app)
+        .into_iter()
+        .filter(|w| w.subscribes_to(event) && (notify || w.format == WebhookFormat::Generic))
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let event = event.to_string();
+    let payload = payload.clone();
+    tauri::async_runtime::spawn(async move {
+        for webhook in webhooks {
+            let body = webhook_body(webhook.format, &event, &payload);
+            if let Err(e) = deliver(&webhook, &body).await {
+                eprintln!("Webhook {} failed: {}", webhook.name, e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_webhooks(app: AppHandle) -> Result<Vec<WebhookConfig>, String> {
+    Ok(load_webhooks(&app))
+}
+
+#[tauri::command]
+pub async fn save_webhook(app: AppHandle, webhook: WebhookConfig) -> Result<WebhookConfig, String> {
+    if !(webhook.url.starts_with("http://") || webhook.url.starts_with("https://")) {
+        return Err("Webhook URL must start with http:// or https://".to_string());
+    }
+    if let Some(unknown) = webhook
+        .events
+        .iter()
+        .find(|e| !WEBHOOK_EVENTS.contains(&e.as_str()))
+    {
+        return Err(format!("Unknown webhook event: {}", unknown));
+    }
+
+    let mut webhook = webhook;
+    if webhook.id.is_empty() {
+        webhook.id = format!("webhook-{}", chrono::Utc::now().timestamp_millis());
+    }
+
+    let mut webhooks = load_webhooks(&app);
+    match webhooks.iter_mut().find(|w| w.id == webhook.id) {
+        Some(existing) => *existing = webhook.clone(),
+        None => webhooks.push(webhook.clone()),
+    }
+    save_setting(&app, WEBHOOKS_KEY, &webhooks)?;
+    Ok(webhook)
+}
+
+#[tauri::command]
+pub async fn delete_webhook(app: AppHandle, webhook_id: String) -> Result<(), String> {
+    let mut webhooks = load_webhooks(&app);
+    webhooks.retain(|w| w.id != webhook_id);
+    save_setting(&app, WEBHOOKS_KEY, &webhooks)
+}
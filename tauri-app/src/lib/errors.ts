@@ -0,0 +1,20 @@
+import type { AppError } from './types';
+
+const hints: Partial<Record<AppError['kind'], string>> = {
+  connectionFailed: 'Check the Prometheus URL in Settings and that the server is running.',
+  timeout: 'Prometheus is slow to respond. Try a shorter time range.',
+  fileNotFound: 'Use Claude Code to generate usage data.',
+};
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === 'object' && e !== null && 'kind' in e && 'message' in e;
+}
+
+// Readable message for an error thrown by invoke(), with a hint on what to do about it
+export function errorMessage(e: unknown): string {
+  if (isAppError(e)) {
+    const hint = hints[e.kind];
+    return hint && !e.message.includes(hint) ? `${e.message} ${hint}` : e.message;
+  }
+  return String(e);
+}
@@ -8,12 +8,47 @@ export interface DashboardMetrics {
   commitCount: number;
   pullRequestCount: number;
   tokensByModel: ModelTokens[];
+  costByModel: ModelCost[];
+  usageByTerminal: UsageBreakdown[];
   tokensOverTime: TimeSeriesPoint[];
+  costOverTime: TimeSeriesPoint[];
+  tokensByTypeOverTime: TokenTypeSeries;
+  linesAddedOverTime: TimeSeriesPoint[];
+  linesRemovedOverTime: TimeSeriesPoint[];
+  sessionsOverTime: TimeSeriesPoint[];
+  tokensByVersionOverTime: LabeledSeries[];
   // Token type breakdown
   inputTokens: number;
   outputTokens: number;
   cacheReadTokens: number;
   cacheCreationTokens: number;
+  // Estimated USD per token type, priced per model
+  costByTokenType: TokenTypeCost;
+  // Output tokens per second of active time
+  outputTokensPerSecond: number;
+  outputTokensPerSecondByModel: LabeledSeries[];
+  // USD per hour over the last 15 minutes, and per bucket
+  burnRateUsdPerHour: number;
+  burnRateOverTime: TimeSeriesPoint[];
+  // Connection result per Prometheus endpoint queried
+  endpoints: EndpointStatus[];
+  // Increases with each request; a response older than the latest can be dropped
+  generation: number;
+  // 'local' when Prometheus was unreachable and values come from the stats cache
+  source: 'prometheus' | 'local';
+}
+
+export interface TokenTypeCost {
+  input: number;
+  output: number;
+  cacheRead: number;
+  cacheCreation: number;
+}
+
+export interface EndpointStatus {
+  url: string;
+  connected: boolean;
+  error: string | null;
 }
 
 export interface ModelTokens {
@@ -21,9 +56,37 @@ export interface ModelTokens {
   tokens: number;
 }
 
+export interface ModelCost {
+  model: string;
+  costUsd: number;
+  // Share of total cost, 0-100
+  percentage: number;
+}
+
+export interface UsageBreakdown {
+  label: string;
+  tokens: number;
+  costUsd: number;
+  sessions: number;
+}
+
 export interface TimeSeriesPoint {
   timestamp: number;
   value: number;
+  // Set on usage series when the bucket is still being ingested
+  partial?: boolean;
+}
+
+export interface LabeledSeries {
+  label: string;
+  points: TimeSeriesPoint[];
+}
+
+export interface TokenTypeSeries {
+  input: TimeSeriesPoint[];
+  output: TimeSeriesPoint[];
+  cacheRead: TimeSeriesPoint[];
+  cacheCreation: TimeSeriesPoint[];
 }
 
 export interface Settings {
@@ -32,7 +95,7 @@ export interface Settings {
   pricingProvider: 'anthropic' | 'aws-bedrock' | 'google-vertex';
 }
 
-export type TimeRange = '15m' | '1h' | '4h' | '1d' | '7d' | '30d' | '90d' | 'custom';
+export type TimeRange = '15m' | '1h' | '4h' | '1d' | 'today' | 'yesterday' | '7d' | '30d' | '90d' | 'custom';
 
 export interface CustomTimeRange {
   start: number; // Unix timestamp in seconds
@@ -44,6 +107,8 @@ export const TIME_RANGE_OPTIONS: { value: TimeRange; label: string }[] = [
   { value: '1h', label: 'Last Hour' },
   { value: '4h', label: 'Last 4 Hours' },
   { value: '1d', label: 'Last Day' },
+  { value: 'today', label: 'Today' },
+  { value: 'yesterday', label: 'Yesterday' },
   { value: '7d', label: 'Last Week' },
   { value: '30d', label: 'Last Month' },
   { value: '90d', label: 'Last 3 Months' },
@@ -51,19 +116,37 @@ export const TIME_RANGE_OPTIONS: { value: TimeRange; label: string }[] = [
 ];
 
 // Insights types
-export type PeriodType = 'this_week' | 'last_7_days' | 'this_month';
+export type PeriodType =
+  | 'this_week'
+  | 'iso_week'
+  | 'last_7_days'
+  | 'last_4_weeks'
+  | 'last_12_weeks'
+  | 'last_30_days'
+  | 'this_month'
+  | 'this_quarter'
+  | 'this_year'
+  | 'all_time';
 
 export interface InsightsData {
   period: string;
+  // Current period, inclusive (YYYY-MM-DD)
+  startDate: string;
+  endDate: string;
   comparison: PeriodComparison;
   dailyActivity: DailyActivityPoint[];
   sessionsPerDay: DailyActivityPoint[];
+  toolCallsPerDay: DailyActivityPoint[];
   peakActivity: PeakActivity;
+  unreadableSections: string[];
+  // 'prometheus' when sessions, tokens and cost came from Prometheus
+  source: 'prometheus' | 'local';
 }
 
 export interface PeriodComparison {
   messages: MetricComparison;
   sessions: MetricComparison;
+  toolCalls: MetricComparison;
   tokens: MetricComparison;
   estimatedCost: MetricComparison;
 }
@@ -88,8 +171,15 @@ export interface PeakActivity {
 
 export const PERIOD_OPTIONS: { value: PeriodType; label: string }[] = [
   { value: 'this_week', label: 'This Week' },
+  { value: 'iso_week', label: 'ISO Week' },
   { value: 'last_7_days', label: 'Last 7 Days' },
+  { value: 'last_4_weeks', label: 'Last 4 Weeks' },
+  { value: 'last_12_weeks', label: 'Last 12 Weeks' },
+  { value: 'last_30_days', label: 'Last 30 Days' },
   { value: 'this_month', label: 'This Month' },
+  { value: 'this_quarter', label: 'This Quarter' },
+  { value: 'this_year', label: 'This Year' },
+  { value: 'all_time', label: 'All Time' },
 ];
 
 // Sessions types
@@ -98,6 +188,10 @@ export interface SessionMetrics {
   project: string | null;
   projectPath: string | null;
   timestamp: number;
+  startedAt: number;
+  endedAt: number;
+  // Wall-clock time from the first to the last history entry
+  durationSeconds: number;
   messageCount: number;
   totalCostUsd: number;
   totalTokens: number;
@@ -107,6 +201,8 @@ export interface SessionMetrics {
   cacheCreationTokens: number;
   activeTimeSeconds: number;
   tokensByModel: ModelTokenCount[];
+  origin: string | null;
+  status: 'active' | 'idle' | 'ended';
 }
 
 export interface ModelTokenCount {
@@ -127,12 +223,57 @@ export interface SessionsData {
   sessions: SessionMetrics[];
   projects: ProjectStats[];
   totalCount: number;
+  origins: string[];
+  endpoints: EndpointStatus[];
+}
+
+export interface ToolCallCount {
+  tool: string;
+  count: number;
+}
+
+export interface ModelUsageTotals {
+  model: string;
+  tokens: number;
+  costUsd: number;
+}
+
+export interface SessionDetail {
+  session: SessionMetrics;
+  // Per-second rates, as on the dashboard charts
+  costOverTime: TimeSeriesPoint[];
+  tokensOverTime: TimeSeriesPoint[];
+  toolUsage: ToolCallCount[];
+  modelMix: ModelUsageTotals[];
+  endpoints: EndpointStatus[];
+}
+
+export interface ActiveSession {
+  sessionId: string;
+  project: string;
+  projectPath: string;
+  startedAt: number;
+  lastActivity: number;
+  elapsedSeconds: number;
+  state: 'active' | 'idle' | 'ended';
+  messageCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
+  totalTokens: number;
+  costUsd: number;
+  model: string | null;
 }
 
 
 // Local Stats Cache types
 export interface LocalStatsCacheData {
   totalTokens: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
   totalSessions: number;
   totalMessages: number;
   activeDays: number;
@@ -143,6 +284,7 @@ export interface LocalStatsCacheData {
   dailyActivity: DailyActivityPoint[];
   tokensByModel: ModelTokensData[];
   activityByHour: HourActivity[];
+  unreadableSections: string[];
 }
 
 export interface ModelTokensData {
@@ -204,3 +346,93 @@ export interface PrometheusHealthMetrics {
   memoryOverTime: TimeSeriesPoint[];
   samplesRateOverTime: TimeSeriesPoint[];
 }
+
+export type AppErrorKind =
+  | 'connectionFailed'
+  | 'timeout'
+  | 'queryFailed'
+  | 'noData'
+  | 'parseError'
+  | 'fileNotFound'
+  | 'invalidInput'
+  | 'cancelled'
+  | 'other';
+
+// Error returned by backend commands
+export interface AppError {
+  kind: AppErrorKind;
+  message: string;
+}
+
+// Label matchers applied to every dashboard query; unset fields don't filter
+export interface LabelFilters {
+  model?: string;
+  userEmail?: string;
+  host?: string;
+  project?: string;
+}
+
+export type LabelFilter = keyof LabelFilters;
+
+export type BudgetPeriod = 'daily' | 'weekly' | 'monthly';
+
+export interface BudgetSettings {
+  enabled: boolean;
+  dailyUsd: number | null;
+  weeklyUsd: number | null;
+  monthlyUsd: number | null;
+  // Status turns to 'warning' once this share of a budget is spent (0-100)
+  warningPercent: number;
+  pollIntervalSeconds: number;
+}
+
+export interface BudgetStatus {
+  period: BudgetPeriod;
+  periodStart: number;
+  limitUsd: number;
+  spentUsd: number;
+  // Negative once the budget is exceeded
+  remainingUsd: number;
+  percentUsed: number;
+  status: 'under' | 'warning' | 'exceeded';
+}
+
+export interface BudgetProjection {
+  period: BudgetPeriod;
+  limitUsd: number;
+  spentUsd: number;
+  // Average over the last 24 hours
+  burnRateUsdPerHour: number;
+  periodEnd: number;
+  // When the budget runs out at this pace; null if the period resets first
+  exhaustedAt: number | null;
+}
+
+export interface HeatmapDay {
+  date: string;
+  // 0 = Monday ... 6 = Sunday
+  weekday: number;
+  messages: number;
+  tokens: number;
+  costUsd: number;
+  // 0 for no activity, else 1-4 by quartile among active days
+  level: number;
+}
+
+export interface ActivityHeatmap {
+  year: number;
+  metric: 'messages' | 'tokens' | 'cost';
+  days: HeatmapDay[];
+  activeDays: number;
+  totalMessages: number;
+  totalTokens: number;
+  totalCostUsd: number;
+}
+
+// Estimated messages per weekday (rows, Monday first) and hour (columns)
+export interface ActivityPunchcard {
+  cells: number[][];
+  max: number;
+  peakWeekday: number | null;
+  peakHour: number | null;
+}